@@ -0,0 +1,127 @@
+// Field-by-field comparison of two `DebugValue` trees.
+
+use std::fmt;
+
+use crate::DebugValue;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    // The value at `path` is different on each side.
+    Changed { path: String, old: DebugValue, new: DebugValue },
+    // Only the new value has something at `path`.
+    Added { path: String, value: DebugValue },
+    // Only the old value has something at `path`.
+    Removed { path: String, value: DebugValue },
+}
+
+impl Change {
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Changed { path, .. } | Change::Added { path, .. } | Change::Removed { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::Changed { path, old, new } => write!(f, "~ {}: {} -> {}", path, old, new),
+            Change::Added { path, value } => write!(f, "+ {}: {}", path, value),
+            Change::Removed { path, value } => write!(f, "- {}: {}", path, value),
+        }
+    }
+}
+
+// Compare `old` with `new`. Paths start at `root`, then follow the tree:
+// `.field` for struct fields, `.0` for tuple items, `[2]` for sequence
+// items, `["key"]` for map entries and `{element}` for set elements.
+pub fn diff(root: &str, old: &DebugValue, new: &DebugValue) -> Vec<Change> {
+    let mut changes = Vec::new();
+    walk(root.to_owned(), old, new, &mut changes);
+    changes
+}
+
+fn walk(path: String, old: &DebugValue, new: &DebugValue, changes: &mut Vec<Change>) {
+    use DebugValue::*;
+
+    match (old, new) {
+        (
+            Struct { name: old_name, fields: old_fields, .. },
+            Struct { name: new_name, fields: new_fields, .. },
+        ) if old_name == new_name => {
+            for (field, old_value) in old_fields {
+                let child = format!("{}.{}", path, field);
+                match new_fields.iter().find(|(f, _)| f == field) {
+                    Some((_, new_value)) => walk(child, old_value, new_value, changes),
+                    None => changes.push(Change::Removed { path: child, value: old_value.clone() }),
+                }
+            }
+            for (field, new_value) in new_fields {
+                if !old_fields.iter().any(|(f, _)| f == field) {
+                    let child = format!("{}.{}", path, field);
+                    changes.push(Change::Added { path: child, value: new_value.clone() });
+                }
+            }
+        }
+        (Tuple { name: old_name, items: old_items }, Tuple { name: new_name, items: new_items })
+            if old_name == new_name =>
+        {
+            items(&path, old_items, new_items, |i| format!(".{}", i), changes);
+        }
+        (Seq(old_items), Seq(new_items)) => {
+            items(&path, old_items, new_items, |i| format!("[{}]", i), changes);
+        }
+        // A `HashSet` prints in no particular order, so elements are matched
+        // by value and only ones missing from one side are reported.
+        (Set(old_items), Set(new_items)) => {
+            for old_item in old_items.iter().filter(|item| !new_items.contains(item)) {
+                changes.push(Change::Removed { path: format!("{}{{{}}}", path, old_item), value: old_item.clone() });
+            }
+            for new_item in new_items.iter().filter(|item| !old_items.contains(item)) {
+                changes.push(Change::Added { path: format!("{}{{{}}}", path, new_item), value: new_item.clone() });
+            }
+        }
+        (Map(old_entries), Map(new_entries)) => {
+            for (key, old_value) in old_entries {
+                let child = format!("{}[{}]", path, key);
+                match new_entries.iter().find(|(k, _)| k == key) {
+                    Some((_, new_value)) => walk(child, old_value, new_value, changes),
+                    None => changes.push(Change::Removed { path: child, value: old_value.clone() }),
+                }
+            }
+            for (key, new_value) in new_entries {
+                if !old_entries.iter().any(|(k, _)| k == key) {
+                    let child = format!("{}[{}]", path, key);
+                    changes.push(Change::Added { path: child, value: new_value.clone() });
+                }
+            }
+        }
+        // Leaves, and anything whose shape or type name changed, are
+        // compared as a whole.
+        _ => {
+            if old != new {
+                changes.push(Change::Changed { path, old: old.clone(), new: new.clone() });
+            }
+        }
+    }
+}
+
+// Positional comparison; extra items on either side are additions or
+// removals at the end.
+fn items(
+    path: &str,
+    old: &[DebugValue],
+    new: &[DebugValue],
+    step: impl Fn(usize) -> String,
+    changes: &mut Vec<Change>,
+) {
+    for i in 0..old.len().max(new.len()) {
+        let child = format!("{}{}", path, step(i));
+        match (old.get(i), new.get(i)) {
+            (Some(o), Some(n)) => walk(child, o, n, changes),
+            (Some(o), None) => changes.push(Change::Removed { path: child, value: o.clone() }),
+            (None, Some(n)) => changes.push(Change::Added { path: child, value: n.clone() }),
+            (None, None) => {}
+        }
+    }
+}
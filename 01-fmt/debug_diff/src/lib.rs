@@ -0,0 +1,236 @@
+// Turn `{:?}` / `{:#?}` output back into a tree, and diff two trees.
+//
+// `assert_eq!` on two values with a derived `Debug` shows both values in
+// full, and finding the one field that differs is left to the reader. This
+// crate parses the Debug text instead and reports just the differences:
+//
+//     ~ person.age: 27 -> 28
+//
+// Only the text is needed, so it also works on logged or saved output.
+
+mod diff;
+mod parse;
+
+use std::fmt;
+
+pub use diff::{diff, Change};
+pub use parse::{parse, ParseError};
+
+// One node of parsed Debug output.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DebugValue {
+    // `Person { name: "Peter", age: 27 }`. `non_exhaustive` is set when the
+    // field list ended with `..`.
+    Struct { name: String, fields: Vec<(String, DebugValue)>, non_exhaustive: bool },
+    // `Deep(Structure(7))`, `Some(3)`, or a plain tuple `(1, true)` with no
+    // name.
+    Tuple { name: Option<String>, items: Vec<DebugValue> },
+    // A unit struct or a field-less enum variant: `Unit`, `None`, `PageLoad`.
+    Unit(String),
+    // `[1, 2, 3]`: a slice, array, `Vec` or `VecDeque`.
+    Seq(Vec<DebugValue>),
+    // `{1, 2, 3}`: a `HashSet` or `BTreeSet`.
+    Set(Vec<DebugValue>),
+    // `{"a": 1}`: a `HashMap` or `BTreeMap`.
+    Map(Vec<(DebugValue, DebugValue)>),
+    Str(String),
+    Char(char),
+    Bool(bool),
+    // Kept as written so `1.0` and `1` stay different, as they are in Debug.
+    Number(String),
+    // Output of a hand-written `Debug` impl that doesn't look like a derived
+    // one, such as `PhantomData<i32>`. It is compared as a whole.
+    Opaque(String),
+}
+
+impl DebugValue {
+    // Parse the `Debug` output of `value`. Output that doesn't parse is kept
+    // as a single `Opaque` leaf.
+    pub fn of<T: fmt::Debug>(value: &T) -> DebugValue {
+        let text = format!("{:?}", value);
+        parse(&text).unwrap_or(DebugValue::Opaque(text))
+    }
+}
+
+// Prints the value back in compact `{:?}` form.
+impl fmt::Display for DebugValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn list(f: &mut fmt::Formatter, open: &str, items: &[DebugValue], close: &str) -> fmt::Result {
+            write!(f, "{}", open)?;
+            for (i, item) in items.iter().enumerate() {
+                if i != 0 { write!(f, ", ")?; }
+                write!(f, "{}", item)?;
+            }
+            write!(f, "{}", close)
+        }
+
+        match self {
+            DebugValue::Struct { name, fields, non_exhaustive } => {
+                write!(f, "{} {{ ", name)?;
+                for (i, (field, value)) in fields.iter().enumerate() {
+                    if i != 0 { write!(f, ", ")?; }
+                    write!(f, "{}: {}", field, value)?;
+                }
+                if *non_exhaustive {
+                    write!(f, "{}..", if fields.is_empty() { "" } else { ", " })?;
+                }
+                write!(f, " }}")
+            }
+            DebugValue::Tuple { name, items } => {
+                write!(f, "{}", name.as_deref().unwrap_or(""))?;
+                // A one element tuple needs its comma: `(5,)`.
+                let close = if name.is_none() && items.len() == 1 { ",)" } else { ")" };
+                list(f, "(", items, close)
+            }
+            DebugValue::Unit(name) => write!(f, "{}", name),
+            DebugValue::Seq(items) => list(f, "[", items, "]"),
+            DebugValue::Set(items) => list(f, "{", items, "}"),
+            DebugValue::Map(entries) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i != 0 { write!(f, ", ")?; }
+                    write!(f, "{}: {}", key, value)?;
+                }
+                write!(f, "}}")
+            }
+            DebugValue::Str(s) => write!(f, "{:?}", s),
+            DebugValue::Char(c) => write!(f, "{:?}", c),
+            DebugValue::Bool(b) => write!(f, "{}", b),
+            DebugValue::Number(n) | DebugValue::Opaque(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+// Diff the Debug output of two values, rooted at `root`.
+pub fn diff_values<T: fmt::Debug>(root: &str, old: &T, new: &T) -> Vec<Change> {
+    diff(root, &DebugValue::of(old), &DebugValue::of(new))
+}
+
+// Like `assert_eq!`, but on failure lists only the fields that differ.
+#[macro_export]
+macro_rules! assert_debug_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        let changes = $crate::diff_values(stringify!($left), &$left, &$right);
+        if !changes.is_empty() {
+            let lines: Vec<String> = changes.iter().map(|c| format!("  {}", c)).collect();
+            panic!("assertion `left == right` failed (Debug output differs):\n{}", lines.join("\n"));
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, BTreeSet, HashSet};
+    use std::marker::PhantomData;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    struct Structure(i32);
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    struct Deep(Structure);
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    struct Person<'a> {
+        name: &'a str,
+        age: u8,
+        tags: Vec<&'a str>,
+        scores: BTreeMap<&'a str, f64>,
+        nickname: Option<char>,
+    }
+
+    fn peter(age: u8) -> Person<'static> {
+        Person {
+            name: "Pe\"ter\n",
+            age,
+            tags: vec!["a", "b"],
+            scores: BTreeMap::from([("math", 1.5), ("art", -2e-7)]),
+            nickname: Some('\''),
+        }
+    }
+
+    #[test]
+    fn compact_and_pretty_output_parse_the_same() {
+        let compact = parse(&format!("{:?}", peter(27))).unwrap();
+        let pretty = parse(&format!("{:#?}", peter(27))).unwrap();
+        assert_eq!(compact, pretty);
+        assert_eq!(compact.to_string(), format!("{:?}", peter(27)));
+
+        let deep = DebugValue::of(&Deep(Structure(7)));
+        assert_eq!(deep.to_string(), "Deep(Structure(7))");
+        assert_eq!(DebugValue::of(&(5u32,)).to_string(), "(5,)");
+        assert_eq!(DebugValue::of(&(f64::NAN, -f64::INFINITY, ())).to_string(), "(NaN, -inf, ())");
+    }
+
+    #[test]
+    fn diff_reports_paths() {
+        let mut older = peter(27);
+        older.tags.push("c");
+        let mut newer = peter(28);
+        newer.scores.insert("music", 3.0);
+        newer.nickname = None;
+
+        let lines: Vec<String> = diff_values("person", &older, &newer).iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            lines,
+            [
+                "~ person.age: 27 -> 28",
+                "- person.tags[2]: \"c\"",
+                "+ person.scores[\"music\"]: 3.0",
+                "~ person.nickname: Some('\\'') -> None",
+            ]
+        );
+        assert!(diff_values("deep", &Deep(Structure(7)), &Deep(Structure(7))).is_empty());
+        assert_eq!(diff_values("deep", &Deep(Structure(7)), &Deep(Structure(8)))[0].path(), "deep.0.0");
+    }
+
+    #[test]
+    #[should_panic(expected = "~ Deep(Structure(7)).0.0: 7 -> 8")]
+    fn assert_debug_eq_panics_with_the_diff() {
+        assert_debug_eq!(Deep(Structure(7)), Deep(Structure(8)));
+    }
+
+    #[test]
+    fn falls_back_to_whole_text_for_hand_written_debug() {
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        struct Marked(PhantomData<i32>, u8);
+
+        let phantom = DebugValue::of(&PhantomData::<i32>);
+        assert_eq!(phantom, DebugValue::Opaque(String::from("PhantomData<i32>")));
+        assert_eq!(phantom.to_string(), "PhantomData<i32>");
+        assert!(diff_values("p", &PhantomData::<i32>, &PhantomData).is_empty());
+
+        let lines: Vec<String> =
+            diff_values("m", &Marked(PhantomData, 1), &Marked(PhantomData, 2)).iter().map(|c| c.to_string()).collect();
+        assert_eq!(lines, ["~ m: Marked(PhantomData<i32>, 1) -> Marked(PhantomData<i32>, 2)"]);
+    }
+
+    #[test]
+    fn compares_sets_by_membership() {
+        // Print the same elements in two different orders, the way two equal
+        // `HashSet`s may.
+        let forward = parse(&format!("{:?}", (0..20).collect::<BTreeSet<_>>())).unwrap();
+        let backward = parse(&format!("{{{}}}", (0..20).rev().map(|n| n.to_string()).collect::<Vec<_>>().join(", ")))
+            .unwrap();
+        assert!(diff("s", &forward, &backward).is_empty());
+
+        let older = HashSet::from(["a", "b", "c"]);
+        let newer = HashSet::from(["c", "d", "a"]);
+        let lines: Vec<String> = diff_values("s", &older, &newer).iter().map(|c| c.to_string()).collect();
+        assert_eq!(lines, ["- s{\"b\"}: \"b\"", "+ s{\"d\"}: \"d\""]);
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!(parse("Person { age 27 }").unwrap_err().position, 13);
+        assert_eq!(parse("[1, 2").unwrap_err().message, "expected `]`");
+        assert!(parse("\"open").is_err());
+        let too_deep = parse(&"[".repeat(200_000)).unwrap_err();
+        assert_eq!((too_deep.position, too_deep.message.as_str()), (128, "nesting too deep"));
+        assert!(parse(&format!("{}{}", "[".repeat(127), "]".repeat(127))).is_ok());
+    }
+}
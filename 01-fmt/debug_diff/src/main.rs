@@ -0,0 +1,44 @@
+// The `Person` and `Deep` types from `debug_trait`, compared through their
+// Debug output instead of as flat strings.
+
+use debug_diff::{diff, diff_values, parse};
+
+#[allow(dead_code)] // only read through `Debug`
+#[derive(Debug)]
+struct Structure(i32);
+
+#[allow(dead_code)]
+#[derive(Debug)]
+struct Deep(Structure);
+
+#[allow(dead_code)]
+#[derive(Debug)]
+struct Person<'a> {
+    name: &'a str,
+    age: u8,
+}
+
+fn main() {
+    let peter = Person { name: "Peter", age: 27 };
+    let older = Person { name: "Peter", age: 28 };
+
+    println!("Old: {:?}", peter);
+    println!("New: {:?}", older);
+    for change in diff_values("person", &peter, &older) {
+        println!("{}", change);
+    }
+
+    // Pretty printed output parses to the same tree, so saved `{:#?}` text
+    // can be compared with fresh `{:?}` text.
+    let saved = format!("{:#?}", Deep(Structure(7)));
+    let fresh = format!("{:?}", Deep(Structure(8)));
+    let changes = diff("deep", &parse(&saved).unwrap(), &parse(&fresh).unwrap());
+    for change in &changes {
+        println!("{}", change);
+    }
+
+    match parse("Person { name: \"Peter\", age 27 }") {
+        Ok(value) => println!("{}", value),
+        Err(e) => println!("error: {}", e),
+    }
+}
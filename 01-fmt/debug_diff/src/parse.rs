@@ -0,0 +1,310 @@
+// A recursive-descent parser for what `#[derive(Debug)]` and the standard
+// library's `Debug` impls print. Whitespace is insignificant and trailing
+// commas are allowed, so `{:?}` and `{:#?}` output parse to the same tree.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::DebugValue;
+
+// Nesting deeper than this is rejected instead of overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // Byte offset into the input.
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl Error for ParseError {}
+
+pub fn parse(input: &str) -> Result<DebugValue, ParseError> {
+    let mut parser = Parser { src: input, pos: 0, depth: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos < input.len() {
+        return Err(parser.error("unexpected trailing input"));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    // Values under way, each one inside the last.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.src.len() - trimmed.len();
+    }
+
+    // Skip whitespace, then consume `token` if it is next.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+        if self.eat(token) { Ok(()) } else { Err(self.error(&format!("expected `{}`", token))) }
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        ParseError { position: self.pos, message: message.to_owned() }
+    }
+
+    fn value(&mut self) -> Result<DebugValue, ParseError> {
+        self.skip_whitespace();
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.depth += 1;
+        let value = self.unnested();
+        self.depth -= 1;
+        value
+    }
+
+    fn unnested(&mut self) -> Result<DebugValue, ParseError> {
+        match self.peek() {
+            Some('"') => self.string().map(DebugValue::Str),
+            Some('\'') => self.char(),
+            Some('[') => {
+                self.bump();
+                self.list(']').map(DebugValue::Seq)
+            }
+            Some('(') => {
+                self.bump();
+                self.list(')').map(|items| DebugValue::Tuple { name: None, items })
+            }
+            Some('{') => {
+                self.bump();
+                self.map_or_set()
+            }
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '+' => self.number(),
+            Some(c) if c.is_alphabetic() || c == '_' => self.named(),
+            Some(c) => Err(self.error(&format!("unexpected character {:?}", c))),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    // `Name`, `Name(..)`, `Name { .. }`, or the words `true`, `false`,
+    // `NaN` and `inf`.
+    fn named(&mut self) -> Result<DebugValue, ParseError> {
+        let name = self.path();
+        match name.as_str() {
+            "true" => return Ok(DebugValue::Bool(true)),
+            "false" => return Ok(DebugValue::Bool(false)),
+            "NaN" | "inf" => return Ok(DebugValue::Number(name)),
+            _ => {}
+        }
+
+        if self.eat("(") {
+            let items = self.list(')')?;
+            Ok(DebugValue::Tuple { name: Some(name), items })
+        } else if self.eat("{") {
+            let mut fields = Vec::new();
+            let mut non_exhaustive = false;
+            loop {
+                if self.eat("}") {
+                    break;
+                }
+                // `finish_non_exhaustive` prints `Name { a: 1, .. }`.
+                if self.eat("..") {
+                    non_exhaustive = true;
+                    self.expect("}")?;
+                    break;
+                }
+                self.skip_whitespace();
+                let field = self.identifier().ok_or_else(|| self.error("expected a field name"))?;
+                self.expect(":")?;
+                fields.push((field, self.value()?));
+                if !self.eat(",") {
+                    self.expect("}")?;
+                    break;
+                }
+            }
+            Ok(DebugValue::Struct { name, fields, non_exhaustive })
+        } else {
+            Ok(DebugValue::Unit(name))
+        }
+    }
+
+    // An identifier, possibly qualified with `::`.
+    fn path(&mut self) -> String {
+        let mut path = self.identifier().unwrap_or_default();
+        while self.rest().starts_with("::") {
+            self.pos += 2;
+            path.push_str("::");
+            path.push_str(&self.identifier().unwrap_or_default());
+        }
+        path
+    }
+
+    fn identifier(&mut self) -> Option<String> {
+        let rest = self.rest();
+        let len = rest
+            .char_indices()
+            .find(|&(_, c)| !(c.is_alphanumeric() || c == '_'))
+            .map_or(rest.len(), |(i, _)| i);
+        if len == 0 || rest.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        let ident = rest[..len].to_owned();
+        self.pos += len;
+        Some(ident)
+    }
+
+    // Comma separated values up to `close`, with an optional trailing comma.
+    fn list(&mut self, close: char) -> Result<Vec<DebugValue>, ParseError> {
+        let close = close.to_string();
+        let mut items = Vec::new();
+        loop {
+            if self.eat(&close) {
+                return Ok(items);
+            }
+            items.push(self.value()?);
+            if !self.eat(",") {
+                self.expect(&close)?;
+                return Ok(items);
+            }
+        }
+    }
+
+    // `{k: v, ..}` is a map, `{a, b}` is a set. `{}` is taken to be an empty
+    // map since the two cannot be told apart.
+    fn map_or_set(&mut self) -> Result<DebugValue, ParseError> {
+        if self.eat("}") {
+            return Ok(DebugValue::Map(Vec::new()));
+        }
+        let first = self.value()?;
+        if self.eat(":") {
+            let mut entries = vec![(first, self.value()?)];
+            while self.eat(",") {
+                if self.eat("}") {
+                    return Ok(DebugValue::Map(entries));
+                }
+                let key = self.value()?;
+                self.expect(":")?;
+                entries.push((key, self.value()?));
+            }
+            self.expect("}")?;
+            Ok(DebugValue::Map(entries))
+        } else {
+            let mut items = vec![first];
+            if self.eat(",") {
+                items.extend(self.list('}')?);
+            } else {
+                self.expect("}")?;
+            }
+            Ok(DebugValue::Set(items))
+        }
+    }
+
+    // Numbers are kept as text: `27`, `-1.5e-7`, `0x1b` (from `{:#x?}`),
+    // `-inf`. Comparing the text is exactly what a Debug diff needs.
+    fn number(&mut self) -> Result<DebugValue, ParseError> {
+        let start = self.pos;
+        if matches!(self.peek(), Some('-' | '+')) {
+            self.bump();
+        }
+        if self.rest().starts_with("inf") {
+            self.pos += 3;
+            return Ok(DebugValue::Number(self.src[start..self.pos].to_owned()));
+        }
+        if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return Err(self.error("expected a digit"));
+        }
+        let mut previous = ' ';
+        while let Some(c) = self.peek() {
+            let exponent_sign = (c == '-' || c == '+') && matches!(previous, 'e' | 'E');
+            if !(c.is_ascii_alphanumeric() || c == '.' || c == '_' || exponent_sign) {
+                break;
+            }
+            previous = c;
+            self.bump();
+        }
+        Ok(DebugValue::Number(self.src[start..self.pos].to_owned()))
+    }
+
+    fn char(&mut self) -> Result<DebugValue, ParseError> {
+        let start = self.pos;
+        self.bump(); // the opening `'`
+        let c = match self.bump() {
+            Some('\\') => self.escape()?,
+            Some(c) => c,
+            None => return Err(self.error("unterminated character literal")),
+        };
+        if self.bump() != Some('\'') {
+            return Err(ParseError { position: start, message: "unterminated character literal".to_owned() });
+        }
+        Ok(DebugValue::Char(c))
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        self.bump(); // the opening `"`
+        let mut out = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(out),
+                Some('\\') => out.push(self.escape()?),
+                Some(c) => out.push(c),
+                None => {
+                    return Err(ParseError { position: start, message: "unterminated string".to_owned() });
+                }
+            }
+        }
+    }
+
+    // The escapes `char::escape_debug` produces, after the backslash.
+    fn escape(&mut self) -> Result<char, ParseError> {
+        let at = self.pos;
+        match self.bump() {
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some('0') => Ok('\0'),
+            Some(c @ ('\\' | '"' | '\'')) => Ok(c),
+            Some('u') => {
+                self.expect("{")?;
+                let digits_start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                    self.bump();
+                }
+                let code = u32::from_str_radix(&self.src[digits_start..self.pos], 16).ok();
+                self.expect("}")?;
+                code.and_then(char::from_u32)
+                    .ok_or(ParseError { position: at, message: "invalid unicode escape".to_owned() })
+            }
+            _ => Err(ParseError { position: at, message: "unknown escape".to_owned() }),
+        }
+    }
+}
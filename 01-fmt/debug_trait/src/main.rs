@@ -1,10 +1,12 @@
+use json::{FromJson, FromJsonError, Json, ToJson};
+
 // Derive the `fmt::Debug` implementation for `Structure`. `Structure`
 // is a structure which contains a single `i32`.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 struct Structure(i32);
 
 // pretty printing
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 struct Person<'a> {
     name: &'a str,
     age: u8
@@ -13,9 +15,48 @@ struct Person<'a> {
 
 // Put a `Structure` inside of the structure `Deep`. Make it printable
 // also.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 struct Deep(Structure);
 
+// Unlike `Debug`, JSON has to be written out by hand. A tuple struct with
+// one field is stored as just that field.
+impl ToJson for Structure {
+    fn to_json(&self) -> Json {
+        self.0.to_json()
+    }
+}
+
+impl FromJson<'_> for Structure {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        i32::from_json(json).map(Structure)
+    }
+}
+
+impl ToJson for Deep {
+    fn to_json(&self) -> Json {
+        self.0.to_json()
+    }
+}
+
+impl FromJson<'_> for Deep {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        Structure::from_json(json).map(Deep)
+    }
+}
+
+impl ToJson for Person<'_> {
+    fn to_json(&self) -> Json {
+        Json::object([("name", self.name.to_json()), ("age", self.age.to_json())])
+    }
+}
+
+// `name` borrows straight from the parsed document, so no `String` is needed.
+impl<'a> FromJson<'a> for Person<'a> {
+    fn from_json(json: &'a Json) -> Result<Self, FromJsonError> {
+        Ok(Person { name: json.field("name")?, age: json.field("age")? })
+    }
+}
+
 fn main() {
     // Printing with `{:?}` is similar to with `{}`.
     println!("{:?} months in a year.", 12);
@@ -38,4 +79,26 @@ fn main() {
 
     // Pretty print
     println!("{:#?}", peter);
-}
\ No newline at end of file
+
+    // And as JSON, which can be read back.
+    println!("{}", peter.to_json());
+    println!("{:#}", peter.to_json());
+    let document = Json::parse(r#"{"name": "Peter", "age": 27}"#).unwrap();
+    println!("{:?}", Person::from_json(&document));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let text = json::to_string(&Deep(Structure(7)));
+        assert_eq!(text, "7");
+        assert_eq!(json::from_str::<Deep>(&text), Ok(Deep(Structure(7))));
+
+        let peter = Person { name: "Peter", age: 27 };
+        let document = Json::parse(&json::to_string(&peter)).unwrap();
+        assert_eq!(Person::from_json(&document), Ok(peter));
+    }
+}
@@ -0,0 +1,120 @@
+// Arithmetic on the magnitudes behind `Decimal`: vectors of decimal digits,
+// least significant first, with no zeros at the most significant end. Zero
+// is the empty vector.
+
+use std::cmp::Ordering;
+
+pub(crate) fn trim(mut digits: Vec<u8>) -> Vec<u8> {
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    digits
+}
+
+// The digits of any integer, e.g. a `u64` or `u128`.
+pub(crate) fn of(n: impl ToString) -> Vec<u8> {
+    trim(n.to_string().bytes().rev().map(|b| b - b'0').collect())
+}
+
+// `digits * 10^places`.
+pub(crate) fn shift(digits: &[u8], places: usize) -> Vec<u8> {
+    if digits.is_empty() {
+        return Vec::new();
+    }
+    let mut shifted = vec![0; places];
+    shifted.extend_from_slice(digits);
+    shifted
+}
+
+pub(crate) fn compare(a: &[u8], b: &[u8]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+pub(crate) fn add(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0;
+    for i in 0..a.len().max(b.len()) {
+        let d = a.get(i).unwrap_or(&0) + b.get(i).unwrap_or(&0) + carry;
+        sum.push(d % 10);
+        carry = d / 10;
+    }
+    if carry > 0 {
+        sum.push(carry);
+    }
+    sum
+}
+
+// `a - b`, for `a >= b`.
+pub(crate) fn sub(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    for (i, &d) in a.iter().enumerate() {
+        let take = b.get(i).unwrap_or(&0) + borrow;
+        borrow = u8::from(d < take);
+        difference.push(d + 10 * borrow - take);
+    }
+    trim(difference)
+}
+
+// Schoolbook multiplication: every digit of `a` times every digit of `b`,
+// summed into columns, with the carries done once at the end.
+pub(crate) fn mul(a: &[u8], b: &[u8]) -> Vec<u8> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut columns = vec![0u64; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            columns[i + j] += u64::from(x) * u64::from(y);
+        }
+    }
+    let mut carry = 0;
+    let product = columns
+        .into_iter()
+        .map(|column| {
+            let total = column + carry;
+            carry = total / 10;
+            (total % 10) as u8
+        })
+        .collect();
+    trim(product)
+}
+
+// `(a / b, a % b)` by long division, one digit of the quotient at a time.
+// `b` must not be zero.
+pub(crate) fn div_rem(a: &[u8], b: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let mut quotient = vec![0; a.len()];
+    let mut remainder = Vec::new();
+    for (i, &d) in a.iter().enumerate().rev() {
+        remainder.insert(0, d);
+        remainder = trim(remainder);
+        while compare(&remainder, b) != Ordering::Less {
+            remainder = sub(&remainder, b);
+            quotient[i] += 1;
+        }
+    }
+    (trim(quotient), remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(digits: &[u8]) -> String {
+        digits.iter().rev().map(|d| char::from(b'0' + d)).collect()
+    }
+
+    #[test]
+    fn does_long_arithmetic() {
+        let a = of(u128::MAX);
+        let b = of(987_654_321u32);
+        assert_eq!(text(&add(&a, &b)), "340282366920938463463374607432755865776");
+        assert_eq!(text(&sub(&a, &a)), "");
+        assert_eq!(text(&sub(&of(1000), &of(1))), "999");
+        assert_eq!(text(&mul(&a, &b)), "336081350049572338814702556271664586713972447055");
+        let (q, r) = div_rem(&a, &b);
+        assert_eq!((text(&q), text(&r)), ("344535896503143495550377496330".to_owned(), "282069525".to_owned()));
+        assert_eq!(text(&shift(&of(12), 3)), "12000");
+        assert_eq!(compare(&of(99), &of(100)), Ordering::Less);
+    }
+}
@@ -0,0 +1,442 @@
+// Exact decimal numbers.
+//
+// An `f64` is binary, so most decimal fractions are stored slightly off:
+// `2.675` is really 2.67499999999999982236431605997495353221893310546875,
+// and `{:.2}` prints it as `2.67`. A `Decimal` keeps the digits it was
+// given, so it prints `2.68`, and `0.1 + 0.2` is exactly `0.3`.
+//
+// A `Decimal` is an integer coefficient of any size and a scale, the number
+// of digits after the decimal point: `1.50` is 150 with scale 2. The scale
+// is kept, so `1.50` prints as `1.50`, but it still equals `1.5`.
+//
+// Addition, subtraction and multiplication are exact. Division usually is
+// not, so `checked_div` asks for the scale of the result and how to round.
+// `{:.N}` rounds half to even; `round` takes any of the `Rounding` modes.
+
+mod digits;
+mod round;
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+pub use round::Rounding;
+
+use round::Rest;
+
+// The largest exponent `from_str` accepts either way. `1e4000000000` would
+// otherwise be four billion digits, written out in memory.
+pub const MAX_EXPONENT: u32 = 1_000_000;
+
+#[derive(Clone)]
+pub struct Decimal {
+    negative: bool,
+    // The digits of the coefficient, as laid out in `digits`.
+    coefficient: Vec<u8>,
+    scale: u32,
+}
+
+impl Decimal {
+    // `coefficient / 10^scale`: `Decimal::new(2675, 3)` is 2.675.
+    pub fn new(coefficient: i64, scale: u32) -> Decimal {
+        Decimal::from_parts(coefficient < 0, digits::of(coefficient.unsigned_abs()), scale)
+    }
+
+    pub fn zero() -> Decimal {
+        Decimal::from_parts(false, Vec::new(), 0)
+    }
+
+    // Zero is never negative, so `-0.001` rounded to `0.00` is just `0.00`.
+    fn from_parts(negative: bool, coefficient: Vec<u8>, scale: u32) -> Decimal {
+        let coefficient = digits::trim(coefficient);
+        Decimal { negative: negative && !coefficient.is_empty(), coefficient, scale }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficient.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    // Digits after the decimal point.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn abs(&self) -> Decimal {
+        Decimal { negative: false, ..self.clone() }
+    }
+
+    // The number with exactly `scale` digits after the point. Going up just
+    // adds zeros; going down rounds by `mode`.
+    pub fn round(&self, scale: u32, mode: Rounding) -> Decimal {
+        if scale >= self.scale {
+            let coefficient = digits::shift(&self.coefficient, (scale - self.scale) as usize);
+            return Decimal::from_parts(self.negative, coefficient, scale);
+        }
+        let dropped = (self.scale - scale) as usize;
+        let divisor = digits::shift(&[1], dropped);
+        let (kept, remainder) = digits::div_rem(&self.coefficient, &divisor);
+        Decimal::rounded(self.negative, kept, Rest::of(&remainder, &divisor), scale, mode)
+    }
+
+    // `self / rhs` with `scale` digits after the point, or `None` when `rhs`
+    // is zero.
+    pub fn checked_div(&self, rhs: &Decimal, scale: u32, mode: Rounding) -> Option<Decimal> {
+        if rhs.is_zero() {
+            return None;
+        }
+        // The quotient's coefficient is
+        // `self.coefficient * 10^(scale + rhs.scale - self.scale) / rhs.coefficient`;
+        // a negative power moves to the divisor instead.
+        let power = i64::from(scale) + i64::from(rhs.scale) - i64::from(self.scale);
+        let (dividend, divisor) = if power >= 0 {
+            (digits::shift(&self.coefficient, power as usize), rhs.coefficient.clone())
+        } else {
+            (self.coefficient.clone(), digits::shift(&rhs.coefficient, power.unsigned_abs() as usize))
+        };
+        let (quotient, remainder) = digits::div_rem(&dividend, &divisor);
+        let negative = self.negative != rhs.negative;
+        Some(Decimal::rounded(negative, quotient, Rest::of(&remainder, &divisor), scale, mode))
+    }
+
+    fn rounded(negative: bool, kept: Vec<u8>, rest: Rest, scale: u32, mode: Rounding) -> Decimal {
+        let odd = kept.first().is_some_and(|d| d % 2 == 1);
+        let kept = if mode.rounds_up(negative, odd, rest) { digits::add(&kept, &[1]) } else { kept };
+        Decimal::from_parts(negative, kept, scale)
+    }
+
+    // Both coefficients at the larger of the two scales.
+    fn aligned(&self, other: &Decimal) -> (Vec<u8>, Vec<u8>, u32) {
+        let scale = self.scale.max(other.scale);
+        let a = digits::shift(&self.coefficient, (scale - self.scale) as usize);
+        let b = digits::shift(&other.coefficient, (scale - other.scale) as usize);
+        (a, b, scale)
+    }
+
+    // The digits without the sign, e.g. `0.05` or `1234.50`.
+    fn unsigned(&self) -> String {
+        let scale = self.scale as usize;
+        let mut text: String = self.coefficient.iter().rev().map(|d| char::from(b'0' + d)).collect();
+        if text.len() <= scale {
+            text.insert_str(0, &"0".repeat(scale + 1 - text.len()));
+        }
+        if scale > 0 {
+            text.insert(text.len() - scale, '.');
+        }
+        text
+    }
+}
+
+impl Default for Decimal {
+    fn default() -> Decimal {
+        Decimal::zero()
+    }
+}
+
+macro_rules! from_signed {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Decimal {
+                fn from(n: $ty) -> Decimal {
+                    Decimal::from_parts(n < 0, digits::of(n.unsigned_abs()), 0)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! from_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Decimal {
+                fn from(n: $ty) -> Decimal {
+                    Decimal::from_parts(false, digits::of(n), 0)
+                }
+            }
+        )*
+    };
+}
+
+from_signed!(i8, i16, i32, i64, i128, isize);
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+// Equal by value: `1.5 == 1.50`.
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Decimal) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Decimal) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (negative, _) => {
+                let (a, b, _) = self.aligned(other);
+                let magnitude = digits::compare(&a, &b);
+                if negative { magnitude.reverse() } else { magnitude }
+            }
+        }
+    }
+}
+
+impl Add for &Decimal {
+    type Output = Decimal;
+
+    fn add(self, rhs: &Decimal) -> Decimal {
+        let (a, b, scale) = self.aligned(rhs);
+        if self.negative == rhs.negative {
+            return Decimal::from_parts(self.negative, digits::add(&a, &b), scale);
+        }
+        // Opposite signs: the larger magnitude wins.
+        match digits::compare(&a, &b) {
+            Ordering::Less => Decimal::from_parts(rhs.negative, digits::sub(&b, &a), scale),
+            _ => Decimal::from_parts(self.negative, digits::sub(&a, &b), scale),
+        }
+    }
+}
+
+impl Sub for &Decimal {
+    type Output = Decimal;
+
+    fn sub(self, rhs: &Decimal) -> Decimal {
+        self + &-rhs
+    }
+}
+
+impl Mul for &Decimal {
+    type Output = Decimal;
+
+    fn mul(self, rhs: &Decimal) -> Decimal {
+        let coefficient = digits::mul(&self.coefficient, &rhs.coefficient);
+        Decimal::from_parts(self.negative != rhs.negative, coefficient, self.scale + rhs.scale)
+    }
+}
+
+// The operators above, for owned values too.
+macro_rules! by_value {
+    ($($trait:ident $method:ident),*) => {
+        $(
+            impl $trait for Decimal {
+                type Output = Decimal;
+
+                fn $method(self, rhs: Decimal) -> Decimal {
+                    $trait::$method(&self, &rhs)
+                }
+            }
+        )*
+    };
+}
+
+by_value!(Add add, Sub sub, Mul mul);
+
+impl Neg for &Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Decimal::from_parts(!self.negative, self.coefficient.clone(), self.scale)
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Decimal::from_parts(!self.negative, self.coefficient, self.scale)
+    }
+}
+
+impl Sum for Decimal {
+    fn sum<I: Iterator<Item = Decimal>>(iter: I) -> Decimal {
+        iter.fold(Decimal::zero(), |total, n| total + n)
+    }
+}
+
+impl<'a> Sum<&'a Decimal> for Decimal {
+    fn sum<I: Iterator<Item = &'a Decimal>>(iter: I) -> Decimal {
+        iter.fold(Decimal::zero(), |total, n| &total + n)
+    }
+}
+
+// All the digits, or `{:.N}` rounded half to even. Width, fill, `+` and `0`
+// work as they do for the built-in numbers.
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rounded;
+        let value = match f.precision() {
+            Some(precision) => {
+                rounded = self.round(precision as u32, Rounding::HalfEven);
+                &rounded
+            }
+            None => self,
+        };
+        f.pad_integral(!value.negative, "", &value.unsigned())
+    }
+}
+
+impl fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Decimal").field(&format_args!("{}", self)).finish()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseDecimalError {
+    Empty,
+    Invalid(String),
+}
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseDecimalError::Empty => write!(f, "cannot parse decimal from empty string"),
+            ParseDecimalError::Invalid(text) => write!(f, "invalid decimal literal `{}`", text),
+        }
+    }
+}
+
+impl Error for ParseDecimalError {}
+
+// `12`, `-0.05`, `+.5`, `3.`, `1.5e3`, `25E-4`. The scale is the number of
+// digits written after the point, less the exponent: `1.50` has scale 2 and
+// `1.5e3` has scale 0.
+impl FromStr for Decimal {
+    type Err = ParseDecimalError;
+
+    fn from_str(s: &str) -> Result<Decimal, ParseDecimalError> {
+        let text = s.trim();
+        if text.is_empty() {
+            return Err(ParseDecimalError::Empty);
+        }
+        let invalid = || ParseDecimalError::Invalid(text.to_owned());
+
+        let (negative, unsigned) = match text.as_bytes()[0] {
+            b'-' => (true, &text[1..]),
+            b'+' => (false, &text[1..]),
+            _ => (false, text),
+        };
+        let (number, exponent) = match unsigned.split_once(['e', 'E']) {
+            Some((number, exponent)) => (number, exponent.parse::<i64>().map_err(|_| invalid())?),
+            None => (unsigned, 0),
+        };
+        if exponent.unsigned_abs() > u64::from(MAX_EXPONENT) {
+            return Err(invalid());
+        }
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if whole.len() + fraction.len() == 0 || !all_digits(whole) || !all_digits(fraction) {
+            return Err(invalid());
+        }
+
+        let coefficient = whole.bytes().chain(fraction.bytes()).rev().map(|b| b - b'0').collect();
+        // A long fraction can still take the scale past a `u32`.
+        let scale = fraction.len() as i64 - exponent;
+        let places = u32::try_from(scale.unsigned_abs()).map_err(|_| invalid())?;
+        if scale >= 0 {
+            Ok(Decimal::from_parts(negative, coefficient, places))
+        } else {
+            let coefficient = digits::shift(&digits::trim(coefficient), places as usize);
+            Ok(Decimal::from_parts(negative, coefficient, 0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(text: &str) -> Decimal {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_prints() {
+        assert_eq!(d("2.675").to_string(), "2.675");
+        assert_eq!(d("-0.05").to_string(), "-0.05");
+        assert_eq!(d("+.5").to_string(), "0.5");
+        assert_eq!(d("3.").to_string(), "3");
+        assert_eq!(d("1.50").to_string(), "1.50");
+        assert_eq!(d("1.5e3").to_string(), "1500");
+        assert_eq!(d("25E-4").to_string(), "0.0025");
+        assert_eq!(d("-0.000").to_string(), "0.000");
+        assert_eq!(d("123456789012345678901234567890.123").to_string(), "123456789012345678901234567890.123");
+        assert_eq!(Decimal::new(-2675, 3), d("-2.675"));
+        assert_eq!(Decimal::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(format!("{:?}", d("1.5")), "Decimal(1.5)");
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert_eq!("".parse::<Decimal>(), Err(ParseDecimalError::Empty));
+        assert_eq!("1.2.3".parse::<Decimal>(), Err(ParseDecimalError::Invalid("1.2.3".to_owned())));
+        for text in [".", "-", "e5", "1e", "1,5", "0x10", "1.5e99999999999"] {
+            assert!(text.parse::<Decimal>().is_err(), "{}", text);
+        }
+        // Exponents past `MAX_EXPONENT` are refused rather than written out.
+        for text in ["1e4000000000", "1e-4000000000", "1e1000001"] {
+            assert!(text.parse::<Decimal>().is_err(), "{}", text);
+        }
+        assert_eq!(d("1e1000000").to_string().len(), 1_000_001);
+        assert_eq!(d("1e-1000000").scale(), MAX_EXPONENT);
+        assert_eq!("abc".parse::<Decimal>().unwrap_err().to_string(), "invalid decimal literal `abc`");
+    }
+
+    #[test]
+    fn does_exact_arithmetic() {
+        assert_eq!((d("0.1") + d("0.2")).to_string(), "0.3");
+        assert_eq!((d("1.5") - d("2.25")).to_string(), "-0.75");
+        assert_eq!((d("-1.5") + d("1.5")).to_string(), "0.0");
+        assert_eq!((d("1.25") * d("-0.4")).to_string(), "-0.500");
+        assert_eq!((&d("99999999999999999999") * &d("99999999999999999999")).to_string(), "9999999999999999999800000000000000000001");
+        assert_eq!((-d("3.1")).to_string(), "-3.1");
+        let total: Decimal = ["0.1", "0.1", "0.1"].iter().map(|s| d(s)).sum();
+        assert_eq!(total, d("0.3"));
+    }
+
+    #[test]
+    fn divides_at_a_scale() {
+        let third = d("1").checked_div(&d("3"), 5, Rounding::HalfEven).unwrap();
+        assert_eq!(third.to_string(), "0.33333");
+        assert_eq!(d("2").checked_div(&d("3"), 2, Rounding::HalfUp).unwrap().to_string(), "0.67");
+        assert_eq!(d("2").checked_div(&d("3"), 2, Rounding::Truncate).unwrap().to_string(), "0.66");
+        assert_eq!(d("-1").checked_div(&d("8"), 2, Rounding::HalfEven).unwrap().to_string(), "-0.12");
+        assert_eq!(d("-1").checked_div(&d("8"), 2, Rounding::Floor).unwrap().to_string(), "-0.13");
+        assert_eq!(d("1234.5678").checked_div(&d("0.01"), 0, Rounding::HalfEven).unwrap().to_string(), "123457");
+        assert_eq!(d("1").checked_div(&d("0.000"), 2, Rounding::HalfEven), None);
+    }
+
+    #[test]
+    fn compares_by_value() {
+        assert_eq!(d("1.5"), d("1.50"));
+        assert!(d("-2") < d("-1.99"));
+        assert!(d("0.1") > d("-5"));
+        assert!(d("10") > d("9.999"));
+        assert_eq!(d("0"), d("-0.00"));
+    }
+
+    #[test]
+    fn formats_with_width_and_precision() {
+        assert_eq!(format!("{:.2}", d("2.675")), "2.68");
+        assert_eq!(format!("{:.2}", d("2.665")), "2.66");
+        assert_eq!(format!("{:.4}", d("2.5")), "2.5000");
+        assert_eq!(format!("[{:>8.1}]", d("-3.14")), "[    -3.1]");
+        assert_eq!(format!("[{:<8}]", d("1.5")), "[1.5     ]");
+        assert_eq!(format!("{:+}", d("1.5")), "+1.5");
+        assert_eq!(format!("{:08.2}", d("-1.5")), "-0001.50");
+    }
+}
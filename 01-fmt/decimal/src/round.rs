@@ -0,0 +1,96 @@
+// How to round a `Decimal` that has more digits than there is room for.
+
+use std::cmp::Ordering;
+
+use crate::digits;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rounding {
+    // To the nearest, with halfway cases going to the even neighbour: 2.665
+    // becomes 2.66 and 2.675 becomes 2.68. It has no bias up or down over
+    // many values, which is why it is the default (and what banks use).
+    #[default]
+    HalfEven,
+    // To the nearest, with halfway cases going away from zero: 2.665 becomes
+    // 2.67 and -2.665 becomes -2.67. The one taught at school.
+    HalfUp,
+    // Towards negative infinity.
+    Floor,
+    // Towards positive infinity.
+    Ceiling,
+    // Towards zero: the extra digits are just dropped.
+    Truncate,
+}
+
+// What was cut off, compared with half a unit in the last place kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Rest {
+    Zero,
+    BelowHalf,
+    Half,
+    AboveHalf,
+}
+
+impl Rest {
+    // For a cut-off part of `remainder / divisor`, where `remainder < divisor`.
+    pub(crate) fn of(remainder: &[u8], divisor: &[u8]) -> Rest {
+        if remainder.is_empty() {
+            return Rest::Zero;
+        }
+        match digits::compare(&digits::add(remainder, remainder), divisor) {
+            Ordering::Less => Rest::BelowHalf,
+            Ordering::Equal => Rest::Half,
+            Ordering::Greater => Rest::AboveHalf,
+        }
+    }
+}
+
+impl Rounding {
+    // Whether the magnitude that was kept goes up by one in its last place.
+    // `odd` is whether that last place is odd.
+    pub(crate) fn rounds_up(self, negative: bool, odd: bool, rest: Rest) -> bool {
+        match self {
+            Rounding::HalfEven => rest == Rest::AboveHalf || (rest == Rest::Half && odd),
+            Rounding::HalfUp => matches!(rest, Rest::Half | Rest::AboveHalf),
+            Rounding::Floor => negative && rest != Rest::Zero,
+            Rounding::Ceiling => !negative && rest != Rest::Zero,
+            Rounding::Truncate => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Decimal;
+
+    #[test]
+    fn rounds_each_way() {
+        let modes = [Rounding::HalfEven, Rounding::HalfUp, Rounding::Floor, Rounding::Ceiling, Rounding::Truncate];
+        let cases = [
+            ("2.675", ["2.68", "2.68", "2.67", "2.68", "2.67"]),
+            ("2.665", ["2.66", "2.67", "2.66", "2.67", "2.66"]),
+            ("-2.665", ["-2.66", "-2.67", "-2.67", "-2.66", "-2.66"]),
+            ("2.6651", ["2.67", "2.67", "2.66", "2.67", "2.66"]),
+            ("2.6649", ["2.66", "2.66", "2.66", "2.67", "2.66"]),
+            ("2.66", ["2.66", "2.66", "2.66", "2.66", "2.66"]),
+            ("-0.001", ["0.00", "0.00", "-0.01", "0.00", "0.00"]),
+        ];
+        for (text, expected) in cases {
+            let value: Decimal = text.parse().unwrap();
+            for (mode, expected) in modes.iter().zip(expected) {
+                assert_eq!(value.round(2, *mode).to_string(), expected, "{} {:?}", text, mode);
+            }
+        }
+    }
+
+    #[test]
+    fn rounds_away_every_digit() {
+        let value: Decimal = "0.5".parse().unwrap();
+        assert_eq!(value.round(0, Rounding::HalfEven).to_string(), "0");
+        assert_eq!(value.round(0, Rounding::HalfUp).to_string(), "1");
+        assert_eq!("0.004".parse::<Decimal>().unwrap().round(1, Rounding::Ceiling).to_string(), "0.1");
+        assert_eq!("999.96".parse::<Decimal>().unwrap().round(1, Rounding::HalfUp).to_string(), "1000.0");
+        assert_eq!("1.5".parse::<Decimal>().unwrap().round(3, Rounding::Floor).to_string(), "1.500");
+    }
+}
@@ -0,0 +1,173 @@
+// Generic versions of the `List` display from `main.rs`.
+//
+// `Seq` wraps anything that can be iterated more than once (a reference to a
+// collection, a slice iterator, a range, ...) whose items implement
+// `Display`, and prints it as a list:
+//
+//     Seq::new(&v)                                  [1, 2, 3]
+//     Seq::new(&v).indexed()                        [0: 1, 1: 2, 2: 3]
+//     Seq::new(&v).brackets("{", "}").separator("; ")   {1; 2; 3}
+//     Seq::new(1..=99).truncate(2, 1)               [1, 2, …, 99]
+//
+// Formatting flags are handed down to every item, so `{:>4}` pads each
+// element rather than the list as a whole, and `{:.1}` sets the precision
+// of each float. Items can be `Seq`s themselves for nested collections.
+
+use std::fmt;
+
+#[derive(Clone, Copy)]
+pub struct Seq<'a, I> {
+    items: I,
+    open: &'a str,
+    close: &'a str,
+    separator: &'a str,
+    index_separator: Option<&'a str>,
+    truncate: Option<(usize, usize)>,
+    ellipsis: &'a str,
+}
+
+impl<'a, I> Seq<'a, I>
+where
+    I: IntoIterator + Clone,
+    I::Item: fmt::Display,
+{
+    pub fn new(items: I) -> Seq<'a, I> {
+        Seq {
+            items,
+            open: "[",
+            close: "]",
+            separator: ", ",
+            index_separator: None,
+            truncate: None,
+            ellipsis: "…",
+        }
+    }
+
+    pub fn brackets(self, open: &'a str, close: &'a str) -> Seq<'a, I> {
+        Seq { open, close, ..self }
+    }
+
+    pub fn separator(self, separator: &'a str) -> Seq<'a, I> {
+        Seq { separator, ..self }
+    }
+
+    // Print each item's index in front of it, as `List` does: `0: 1`.
+    pub fn indexed(self) -> Seq<'a, I> {
+        self.index_separator(": ")
+    }
+
+    // Like `indexed`, with something other than `: ` between index and item.
+    pub fn index_separator(self, separator: &'a str) -> Seq<'a, I> {
+        Seq { index_separator: Some(separator), ..self }
+    }
+
+    // Only show the first `head` and the last `tail` items when there are
+    // more than `head + tail`, with an ellipsis in between.
+    pub fn truncate(self, head: usize, tail: usize) -> Seq<'a, I> {
+        Seq { truncate: Some((head, tail)), ..self }
+    }
+
+    // What to print in place of the hidden items. Defaults to `…`.
+    pub fn ellipsis(self, ellipsis: &'a str) -> Seq<'a, I> {
+        Seq { ellipsis, ..self }
+    }
+}
+
+impl<I> fmt::Display for Seq<'_, I>
+where
+    I: IntoIterator + Clone,
+    I::Item: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Work out which indices are hidden, if any. Counting needs a pass
+        // of its own, which is why the items have to be `Clone`.
+        let hidden = match self.truncate {
+            Some((head, tail)) => {
+                let len = self.items.clone().into_iter().count();
+                if len > head + tail { Some(head..len - tail) } else { None }
+            }
+            None => None,
+        };
+
+        f.write_str(self.open)?;
+        let mut first = true;
+        for (index, item) in self.items.clone().into_iter().enumerate() {
+            if let Some(hidden) = &hidden
+                && hidden.contains(&index)
+            {
+                if index == hidden.start {
+                    if !first {
+                        f.write_str(self.separator)?;
+                    }
+                    f.write_str(self.ellipsis)?;
+                    first = false;
+                }
+                continue;
+            }
+
+            if !first {
+                f.write_str(self.separator)?;
+            }
+            first = false;
+            if let Some(separator) = self.index_separator {
+                write!(f, "{}{}", index, separator)?;
+            }
+            // Passing our own formatter on keeps the caller's width,
+            // precision, fill and flags for the item.
+            fmt::Display::fmt(&item, f)?;
+        }
+        f.write_str(self.close)
+    }
+}
+
+// `.seq()` on anything `Seq::new` accepts, for use right inside `println!`.
+pub trait DisplaySeq: IntoIterator + Clone + Sized
+where
+    Self::Item: fmt::Display,
+{
+    fn seq<'a>(self) -> Seq<'a, Self> {
+        Seq::new(self)
+    }
+}
+
+impl<I> DisplaySeq for I
+where
+    I: IntoIterator + Clone,
+    I::Item: fmt::Display,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prints_like_list_and_more() {
+        let v = vec![1, 2, 3];
+        assert_eq!(Seq::new(&v).to_string(), "[1, 2, 3]");
+        assert_eq!(Seq::new(&v).indexed().to_string(), "[0: 1, 1: 2, 2: 3]");
+        assert_eq!(v.iter().seq().brackets("<", ">").separator(" ").to_string(), "<1 2 3>");
+        assert_eq!(Seq::new(["a", "b"]).index_separator("=").brackets("", "").to_string(), "0=a, 1=b");
+        assert_eq!(Seq::new(Vec::<i32>::new()).to_string(), "[]");
+    }
+
+    #[test]
+    fn truncates_long_output() {
+        assert_eq!(Seq::new(1..=99).truncate(2, 1).to_string(), "[1, 2, …, 99]");
+        assert_eq!(Seq::new(1..=99).truncate(0, 2).ellipsis("...").to_string(), "[..., 98, 99]");
+        assert_eq!(Seq::new(1..=3).truncate(2, 1).to_string(), "[1, 2, 3]");
+        assert_eq!(Seq::new(0..10).indexed().truncate(1, 1).to_string(), "[0: 0, …, 9: 9]");
+    }
+
+    #[test]
+    fn passes_flags_to_items_and_nests() {
+        let v = [1.0, 2.25, -3.5];
+        assert_eq!(format!("{:6.2}", Seq::new(&v)), "[  1.00,   2.25,  -3.50]");
+        assert_eq!(format!("{:+}", Seq::new(&v)), "[+1, +2.25, -3.5]");
+        assert_eq!(format!("{:*<3}", Seq::new(["a", "b"])), "[a**, b**]");
+
+        let grid = [vec![1, 2], vec![3, 4]];
+        let rows = grid.iter().map(|row| Seq::new(row).brackets("(", ")"));
+        assert_eq!(format!("{:02}", Seq::new(rows)), "[(01, 02), (03, 04)]");
+    }
+}
@@ -1,6 +1,10 @@
 use std::fmt; // Import the `fmt` module.
 
+use display_testcase_list::{DisplaySeq, Seq};
+use json::{FromJson, FromJsonError, Json, ToJson};
+
 // Define a structure named `List` containing a `Vec`.
+#[derive(Debug, PartialEq)]
 struct List(Vec<i32>);
 
 impl fmt::Display for List {
@@ -18,7 +22,47 @@ impl fmt::Display for List {
     }
 }
 
+// In JSON a `List` is just its array.
+impl ToJson for List {
+    fn to_json(&self) -> Json {
+        self.0.to_json()
+    }
+}
+
+impl FromJson<'_> for List {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        Vec::from_json(json).map(List)
+    }
+}
+
 fn main() {
     let v = List(vec![1, 2, 3]);
     println!("{}", v);
-}
\ No newline at end of file
+
+    let text = json::to_string(&v);
+    println!("{} -> {}", text, json::from_str::<List>(&text).unwrap());
+
+    // `Seq` prints any collection of `Display` items the same way, without
+    // writing a new `impl` for every type.
+    println!("{}", Seq::new(&v.0).indexed());
+    println!("{}", ["Dublin", "Oslo", "Vancouver"].seq().brackets("{", "}").separator(" | "));
+    println!("{}", (1..=99).seq().truncate(2, 1));
+
+    // Width and precision apply to each element...
+    println!("{:>6.2}", Seq::new(&[1.23456, 22.5, 333.0]));
+    // ...also inside nested lists.
+    let matrix = [[1, 2, 3], [4, 5, 6]];
+    println!("{:03}", matrix.iter().map(|row| row.seq().brackets("(", ")")).seq());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let list = List(vec![1, -2, 3]);
+        assert_eq!(json::to_string(&list), "[1,-2,3]");
+        assert_eq!(json::from_str::<List>("[1, -2, 3]"), Ok(list));
+    }
+}
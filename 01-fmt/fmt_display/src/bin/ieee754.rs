@@ -0,0 +1,48 @@
+// Explains the bits of every float typed on stdin, one per line:
+//
+//     $ echo 3.3 | cargo run --bin ieee754
+//     3.3 as f64
+//       bits:      0 10000000000 1010011001100110011001100110011001100110011001100110
+//       ...
+//
+// A line is read as an `f64` unless it ends in `f32`. Raw bits can be given
+// in hex, `0x7fc00001`: up to 8 digits are an `f32`, more an `f64`. That is
+// the only way to type a NaN with a payload.
+
+use std::io::{self, BufRead};
+
+use fmt_display::ieee754::{Ieee754, Precision};
+
+fn parse(line: &str) -> Result<Ieee754, String> {
+    if let Some(hex) = line.strip_prefix("0x").or_else(|| line.strip_prefix("0X")) {
+        let hex = hex.replace('_', "");
+        let bits = u64::from_str_radix(&hex, 16).map_err(|e| format!("invalid bits `{}`: {}", line, e))?;
+        let precision = if hex.len() <= 8 { Precision::Single } else { Precision::Double };
+        return Ok(Ieee754::from_bits(precision, bits));
+    }
+    match line.strip_suffix("f32") {
+        Some(number) => number.trim_end_matches('_').parse::<f32>().map(Ieee754::from),
+        None => line.strip_suffix("f64").unwrap_or(line).trim_end_matches('_').parse::<f64>().map(Ieee754::from),
+    }
+    .map_err(|e| format!("invalid float `{}`: {}", line, e))
+}
+
+fn main() {
+    for line in io::stdin().lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match parse(line) {
+            Ok(bits) => println!("{}\n", bits),
+            Err(message) => eprintln!("error: {}\n", message),
+        }
+    }
+}
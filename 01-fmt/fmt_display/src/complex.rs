@@ -0,0 +1,384 @@
+// Complex numbers, grown from the `Complex` that `main.rs` only printed.
+//
+// The type is generic over the float it is made of so that the `ffi`
+// example can hand a `Complex<f32>` to C's `csqrtf` (hence `#[repr(C)]`:
+// two floats, real part first, just like C's `float complex`), while
+// everything else uses the default `Complex<f64>`.
+//
+//     let z: Complex = "3 + 4i".parse()?;
+//     z.modulus()                      5
+//     z * z.conj()                     25 + 0i
+//     format!("{:+.2}", z.sqrt())      +2.00 + 1.00i
+
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+use json::{FromJson, FromJsonError, Json, ToJson};
+
+// What `Complex` needs from its parts. Implemented for `f32` and `f64`.
+pub trait Float:
+    Copy
+    + PartialEq
+    + PartialOrd
+    + fmt::Display
+    + fmt::Debug
+    + FromStr
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const NAN: Self;
+
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn is_sign_negative(self) -> bool;
+}
+
+macro_rules! float {
+    ($($ty:ty),*) => {
+        $(
+            impl Float for $ty {
+                const ZERO: Self = 0.0;
+                const ONE: Self = 1.0;
+                const NAN: Self = <$ty>::NAN;
+
+                fn abs(self) -> Self { <$ty>::abs(self) }
+                fn sqrt(self) -> Self { <$ty>::sqrt(self) }
+                fn hypot(self, other: Self) -> Self { <$ty>::hypot(self, other) }
+                fn atan2(self, other: Self) -> Self { <$ty>::atan2(self, other) }
+                fn exp(self) -> Self { <$ty>::exp(self) }
+                fn ln(self) -> Self { <$ty>::ln(self) }
+                fn powf(self, n: Self) -> Self { <$ty>::powf(self, n) }
+                fn sin(self) -> Self { <$ty>::sin(self) }
+                fn cos(self) -> Self { <$ty>::cos(self) }
+                fn is_sign_negative(self) -> bool { <$ty>::is_sign_negative(self) }
+            }
+        )*
+    };
+}
+
+float!(f32, f64);
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex<T = f64> {
+    pub real: T,
+    pub imag: T,
+}
+
+impl<T: Float> Complex<T> {
+    pub fn new(real: T, imag: T) -> Complex<T> {
+        Complex { real, imag }
+    }
+
+    // The imaginary unit.
+    pub fn i() -> Complex<T> {
+        Complex::new(T::ZERO, T::ONE)
+    }
+
+    // `r` times `e` to the `theta` i.
+    pub fn from_polar(r: T, theta: T) -> Complex<T> {
+        Complex::new(r * theta.cos(), r * theta.sin())
+    }
+
+    // `(modulus, argument)`, the inverse of `from_polar`.
+    pub fn to_polar(self) -> (T, T) {
+        (self.modulus(), self.arg())
+    }
+
+    pub fn conj(self) -> Complex<T> {
+        Complex::new(self.real, -self.imag)
+    }
+
+    // The distance from zero, `|z|`. `hypot` avoids overflowing on the
+    // squares of large parts.
+    pub fn modulus(self) -> T {
+        self.real.hypot(self.imag)
+    }
+
+    // The angle from the positive real axis, in `(-π, π]`.
+    pub fn arg(self) -> T {
+        self.imag.atan2(self.real)
+    }
+
+    pub fn exp(self) -> Complex<T> {
+        Complex::from_polar(self.real.exp(), self.imag)
+    }
+
+    // The principal natural logarithm: its imaginary part is `arg`.
+    pub fn ln(self) -> Complex<T> {
+        Complex::new(self.modulus().ln(), self.arg())
+    }
+
+    // The principal square root, the one with a non-negative real part.
+    // The sign of the imaginary part follows `imag`, `-0.0` included, so
+    // `-1 - 0i` has the root `0 - 1i` as in C's `csqrt`.
+    pub fn sqrt(self) -> Complex<T> {
+        let two = T::ONE + T::ONE;
+        let r = self.modulus();
+        let real = ((r + self.real) / two).sqrt();
+        let imag = ((r - self.real) / two).sqrt();
+        Complex::new(real, if self.imag.is_sign_negative() { -imag } else { imag })
+    }
+
+    // `self` raised to a real power. Zero has no angle, so it follows the
+    // real `0^n`: 1 for `n == 0`, 0 for a positive `n`, infinity for a
+    // negative one.
+    pub fn powf(self, n: T) -> Complex<T> {
+        if self.real == T::ZERO && self.imag == T::ZERO {
+            return Complex::new(T::ZERO.powf(n), T::ZERO);
+        }
+        let (r, theta) = self.to_polar();
+        Complex::from_polar(r.powf(n), theta * n)
+    }
+
+    // `self` raised to a complex power, `e` to the `n ln(self)`. For zero,
+    // `|0^n|` is `0^n.real`: zero when that is positive, and with a real
+    // part of zero or less only a real `n` gives an answer.
+    pub fn pow(self, n: Complex<T>) -> Complex<T> {
+        if self.real == T::ZERO && self.imag == T::ZERO {
+            return if n.imag == T::ZERO {
+                self.powf(n.real)
+            } else if n.real > T::ZERO {
+                self
+            } else {
+                Complex::new(T::NAN, T::NAN)
+            };
+        }
+        (n * self.ln()).exp()
+    }
+}
+
+impl<T: Float> From<T> for Complex<T> {
+    fn from(real: T) -> Complex<T> {
+        Complex::new(real, T::ZERO)
+    }
+}
+
+impl<T: Float> Add for Complex<T> {
+    type Output = Complex<T>;
+
+    fn add(self, other: Complex<T>) -> Complex<T> {
+        Complex::new(self.real + other.real, self.imag + other.imag)
+    }
+}
+
+impl<T: Float> Sub for Complex<T> {
+    type Output = Complex<T>;
+
+    fn sub(self, other: Complex<T>) -> Complex<T> {
+        Complex::new(self.real - other.real, self.imag - other.imag)
+    }
+}
+
+// (a + bi)(c + di) = (ac - bd) + (ad + bc)i
+impl<T: Float> Mul for Complex<T> {
+    type Output = Complex<T>;
+
+    fn mul(self, other: Complex<T>) -> Complex<T> {
+        Complex::new(
+            self.real * other.real - self.imag * other.imag,
+            self.real * other.imag + self.imag * other.real,
+        )
+    }
+}
+
+// Multiply top and bottom by the conjugate of the divisor, which turns the
+// bottom into the real number c² + d².
+impl<T: Float> Div for Complex<T> {
+    type Output = Complex<T>;
+
+    fn div(self, other: Complex<T>) -> Complex<T> {
+        let bottom = other.real * other.real + other.imag * other.imag;
+        let top = self * other.conj();
+        Complex::new(top.real / bottom, top.imag / bottom)
+    }
+}
+
+impl<T: Float> Neg for Complex<T> {
+    type Output = Complex<T>;
+
+    fn neg(self) -> Complex<T> {
+        Complex::new(-self.real, -self.imag)
+    }
+}
+
+// `3.3 + 7.2i`, or `3.3 - 7.2i` when the imaginary part is negative. The
+// precision applies to both parts (`{:.1}`), and `{:+}` puts a sign in front
+// of the real part.
+impl<T: Float> fmt::Display for Complex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (f.precision(), f.sign_plus()) {
+            (Some(p), true) => write!(f, "{:+.*}", p, self.real)?,
+            (Some(p), false) => write!(f, "{:.*}", p, self.real)?,
+            (None, true) => write!(f, "{:+}", self.real)?,
+            (None, false) => write!(f, "{}", self.real)?,
+        }
+        let sign = if self.imag.is_sign_negative() { '-' } else { '+' };
+        match f.precision() {
+            Some(p) => write!(f, " {} {:.*}i", sign, p, self.imag.abs()),
+            None => write!(f, " {} {}i", sign, self.imag.abs()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseComplexError {
+    Empty,
+    Invalid(String),
+}
+
+impl fmt::Display for ParseComplexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseComplexError::Empty => write!(f, "cannot parse complex number from empty string"),
+            ParseComplexError::Invalid(text) => write!(f, "invalid complex number literal `{}`", text),
+        }
+    }
+}
+
+impl Error for ParseComplexError {}
+
+// Accepts what `Display` writes and the usual shorthands: `3.3 + 7.2i`,
+// `-1-2i`, `2.5`, `-i`, `1e-3 + 4i`. Spaces are ignored.
+impl<T: Float> FromStr for Complex<T> {
+    type Err = ParseComplexError;
+
+    fn from_str(s: &str) -> Result<Complex<T>, ParseComplexError> {
+        let text: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        if text.is_empty() {
+            return Err(ParseComplexError::Empty);
+        }
+        let invalid = || ParseComplexError::Invalid(s.trim().to_owned());
+        let number = |part: &str| part.parse::<T>().map_err(|_| invalid());
+        // The imaginary part may leave out the `1` in `i`, `+i` and `-i`.
+        let imaginary = |part: &str| match part {
+            "" | "+" => Ok(T::ONE),
+            "-" => Ok(-T::ONE),
+            _ => number(part),
+        };
+
+        // The sign between the parts is the last `+` or `-` that does not
+        // start the text or an exponent.
+        let bytes = text.as_bytes();
+        let split = (1..bytes.len())
+            .rev()
+            .find(|&i| matches!(bytes[i], b'+' | b'-') && !matches!(bytes[i - 1], b'e' | b'E'));
+
+        match (text.strip_suffix('i'), split) {
+            (Some(rest), Some(i)) => Ok(Complex::new(number(&rest[..i])?, imaginary(&rest[i..])?)),
+            (Some(rest), None) => Ok(Complex::new(T::ZERO, imaginary(rest)?)),
+            (None, None) => Ok(Complex::new(number(&text)?, T::ZERO)),
+            (None, Some(_)) => Err(invalid()),
+        }
+    }
+}
+
+impl<T: Float + ToJson> ToJson for Complex<T> {
+    fn to_json(&self) -> Json {
+        Json::object([("real", self.real.to_json()), ("imag", self.imag.to_json())])
+    }
+}
+
+impl<'a, T: Float + FromJson<'a>> FromJson<'a> for Complex<T> {
+    fn from_json(json: &'a Json) -> Result<Self, FromJsonError> {
+        Ok(Complex { real: json.field("real")?, imag: json.field("imag")? })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn close(a: Complex, b: Complex) -> bool {
+        (a - b).modulus() < 1e-12
+    }
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Complex::new(3.0, 4.0);
+        let b = Complex::new(1.0, -2.0);
+        assert_eq!(a + b, Complex::new(4.0, 2.0));
+        assert_eq!(a - b, Complex::new(2.0, 6.0));
+        assert_eq!(a * b, Complex::new(11.0, -2.0));
+        assert_eq!(a / b, Complex::new(-1.0, 2.0));
+        assert_eq!(-a, Complex::new(-3.0, -4.0));
+        assert_eq!(a * a.conj(), Complex::from(25.0));
+        assert_eq!(Complex::<f64>::i() * Complex::i(), Complex::from(-1.0));
+    }
+
+    #[test]
+    fn converts_to_and_from_polar_form() {
+        let z = Complex::new(3.0, 4.0);
+        assert_eq!(z.modulus(), 5.0);
+        let (r, theta) = z.to_polar();
+        assert!(close(Complex::from_polar(r, theta), z));
+        assert_eq!(Complex::new(-1.0, 0.0).arg(), PI);
+        assert!(close(Complex::from_polar(2.0, PI / 2.0), Complex::new(0.0, 2.0)));
+    }
+
+    #[test]
+    fn computes_exp_ln_pow_and_sqrt() {
+        // Euler: e^(iπ) = -1
+        assert!(close(Complex::new(0.0, PI).exp(), Complex::from(-1.0)));
+        let z = Complex::new(1.5, -0.5);
+        assert!(close(z.ln().exp(), z));
+        assert!(close(z.powf(3.0), z * z * z));
+        assert!(close(z.pow(Complex::from(2.0)), z * z));
+        // i^i is real: e^(-π/2)
+        assert!(close(Complex::<f64>::i().pow(Complex::i()), Complex::from((-PI / 2.0).exp())));
+
+        assert_eq!(Complex::new(-4.0, 0.0).sqrt(), Complex::new(0.0, 2.0));
+        assert_eq!(Complex::new(-4.0, -0.0).sqrt(), Complex::new(0.0, -2.0));
+        assert_eq!(Complex::new(3.0, 4.0).sqrt(), Complex::new(2.0, 1.0));
+        assert_eq!(Complex::new(0.0, 0.0).powf(0.0), Complex::from(1.0));
+        assert_eq!(Complex::new(0.0, 0.0).powf(2.0), Complex::from(0.0));
+        assert_eq!(Complex::new(0.0, 0.0).powf(-1.0), Complex::from(f64::INFINITY));
+        assert_eq!(Complex::new(0.0, 0.0).pow(Complex::from(-2.0)), Complex::from(f64::INFINITY));
+        assert_eq!(Complex::new(0.0, 0.0).pow(Complex::new(1.0, 1.0)), Complex::from(0.0));
+        let undefined = Complex::<f64>::new(0.0, 0.0).pow(Complex::new(-1.0, 1.0));
+        assert!(undefined.real.is_nan() && undefined.imag.is_nan());
+    }
+
+    #[test]
+    fn displays_with_precision_and_sign() {
+        let z = Complex::new(3.3, 7.2);
+        assert_eq!(z.to_string(), "3.3 + 7.2i");
+        assert_eq!(z.conj().to_string(), "3.3 - 7.2i");
+        assert_eq!(format!("{:.2}", -z), "-3.30 - 7.20i");
+        assert_eq!(format!("{:+}", z), "+3.3 + 7.2i");
+        assert_eq!(format!("{:+.1}", Complex::new(0.25_f32, -1.0)), "+0.2 - 1.0i");
+    }
+
+    #[test]
+    fn parses_what_it_displays() {
+        let parse = |s: &str| s.parse::<Complex>();
+        assert_eq!(parse("3.3 + 7.2i"), Ok(Complex::new(3.3, 7.2)));
+        assert_eq!(parse("-1-2i"), Ok(Complex::new(-1.0, -2.0)));
+        assert_eq!(parse(" 2.5 "), Ok(Complex::new(2.5, 0.0)));
+        assert_eq!(parse("-i"), Ok(Complex::new(0.0, -1.0)));
+        assert_eq!(parse("4i"), Ok(Complex::new(0.0, 4.0)));
+        assert_eq!(parse("1e-3+2E+1i"), Ok(Complex::new(0.001, 20.0)));
+        assert_eq!(parse(&Complex::new(-0.5, -8.0).to_string()), Ok(Complex::new(-0.5, -8.0)));
+
+        assert_eq!(parse(""), Err(ParseComplexError::Empty));
+        assert_eq!(parse("1 + 2"), Err(ParseComplexError::Invalid("1 + 2".to_owned())));
+        assert_eq!(parse("1 + 2j").unwrap_err().to_string(), "invalid complex number literal `1 + 2j`");
+        assert!(parse("1 + xi").is_err());
+        assert_eq!("1.5-0.5i".parse::<Complex<f32>>(), Ok(Complex::new(1.5_f32, -0.5)));
+    }
+}
@@ -0,0 +1,293 @@
+// What a float looks like in memory.
+//
+// An `f64` is 64 bits: one sign bit, 11 exponent bits and 52 mantissa bits
+// (an `f32` has 1, 8 and 23). For a normal number the value is
+//
+//     (-1)^sign × 1.mantissa × 2^(exponent - bias)
+//
+// with a bias of 1023 (127 for `f32`). An exponent of all zeros means zero
+// or a subnormal number, which has no implicit leading 1; all ones means
+// infinity, or NaN if the mantissa is not zero.
+//
+//     let bits = Ieee754::from(3.3_f32);
+//     format!("{:b}", bits)     0 10000000 10100110011001100110011
+//     format!("{:x}", bits)     0x1.a66666p+1
+//     bits.exponent()           Some(1)
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    Single,
+    Double,
+}
+
+impl Precision {
+    pub fn exponent_bits(self) -> u32 {
+        match self {
+            Precision::Single => 8,
+            Precision::Double => 11,
+        }
+    }
+
+    pub fn mantissa_bits(self) -> u32 {
+        match self {
+            Precision::Single => 23,
+            Precision::Double => 52,
+        }
+    }
+
+    pub fn bias(self) -> i32 {
+        (1 << (self.exponent_bits() - 1)) - 1
+    }
+
+    // The Rust name of the type.
+    pub fn name(self) -> &'static str {
+        match self {
+            Precision::Single => "f32",
+            Precision::Double => "f64",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    Zero,
+    Subnormal,
+    Normal,
+    Infinite,
+    // A quiet NaN has the top mantissa bit set; the payload is the rest of
+    // the mantissa.
+    Nan { quiet: bool, payload: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ieee754 {
+    precision: Precision,
+    bits: u64,
+}
+
+impl From<f32> for Ieee754 {
+    fn from(x: f32) -> Ieee754 {
+        Ieee754 { precision: Precision::Single, bits: x.to_bits() as u64 }
+    }
+}
+
+impl From<f64> for Ieee754 {
+    fn from(x: f64) -> Ieee754 {
+        Ieee754 { precision: Precision::Double, bits: x.to_bits() }
+    }
+}
+
+impl Ieee754 {
+    // Any bits above the width of `precision` are dropped.
+    pub fn from_bits(precision: Precision, bits: u64) -> Ieee754 {
+        let width = 1 + precision.exponent_bits() + precision.mantissa_bits();
+        Ieee754 { precision, bits: bits & (u64::MAX >> (64 - width)) }
+    }
+
+    pub fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.bits >> (self.precision.exponent_bits() + self.precision.mantissa_bits()) == 1
+    }
+
+    // The exponent field as stored.
+    pub fn biased_exponent(&self) -> u32 {
+        let mask = (1 << self.precision.exponent_bits()) - 1;
+        (self.bits >> self.precision.mantissa_bits()) as u32 & mask
+    }
+
+    // The mantissa field as stored, without the implicit leading 1.
+    pub fn mantissa(&self) -> u64 {
+        self.bits & ((1 << self.precision.mantissa_bits()) - 1)
+    }
+
+    // The power of two the significand is multiplied by. Zero and
+    // subnormals share the smallest exponent, `1 - bias`; infinity and NaN
+    // have none.
+    pub fn exponent(&self) -> Option<i32> {
+        let bias = self.precision.bias();
+        match self.class() {
+            Class::Normal => Some(self.biased_exponent() as i32 - bias),
+            Class::Zero | Class::Subnormal => Some(1 - bias),
+            Class::Infinite | Class::Nan { .. } => None,
+        }
+    }
+
+    pub fn class(&self) -> Class {
+        let all_ones = (1 << self.precision.exponent_bits()) - 1;
+        match (self.biased_exponent(), self.mantissa()) {
+            (0, 0) => Class::Zero,
+            (0, _) => Class::Subnormal,
+            (e, 0) if e == all_ones => Class::Infinite,
+            (e, m) if e == all_ones => {
+                let quiet_bit = 1 << (self.precision.mantissa_bits() - 1);
+                Class::Nan { quiet: m & quiet_bit != 0, payload: m & !quiet_bit }
+            }
+            _ => Class::Normal,
+        }
+    }
+
+    // The number itself. `f32`s widen to `f64` without loss.
+    pub fn value(&self) -> f64 {
+        match self.precision {
+            Precision::Single => f32::from_bits(self.bits as u32) as f64,
+            Precision::Double => f64::from_bits(self.bits),
+        }
+    }
+
+    // The significand as a number: `1.mantissa` for normal numbers,
+    // `0.mantissa` otherwise.
+    pub fn significand(&self) -> f64 {
+        let fraction = self.mantissa() as f64 / (1u64 << self.precision.mantissa_bits()) as f64;
+        match self.class() {
+            Class::Normal => 1.0 + fraction,
+            _ => fraction,
+        }
+    }
+}
+
+// The three fields, separated by spaces: `0 10000000 10100110011001100110011`.
+impl fmt::Binary for Ieee754 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let exponent_bits = self.precision.exponent_bits() as usize;
+        let mantissa_bits = self.precision.mantissa_bits() as usize;
+        write!(
+            f,
+            "{} {:0e$b} {:0m$b}",
+            self.is_negative() as u8,
+            self.biased_exponent(),
+            self.mantissa(),
+            e = exponent_bits,
+            m = mantissa_bits,
+        )
+    }
+}
+
+// Hexadecimal float notation, as C's `%a` prints it: `0x1.a66666p+1`. Each
+// hex digit after the point is four mantissa bits, so nothing is rounded.
+impl fmt::LowerHex for Ieee754 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.is_negative() { "-" } else { "" };
+        let (lead, exponent) = match self.class() {
+            Class::Normal => (1, self.exponent().unwrap_or(0)),
+            Class::Subnormal => (0, self.exponent().unwrap_or(0)),
+            Class::Zero => return write!(f, "{}0x0p+0", sign),
+            Class::Infinite => return write!(f, "{}inf", sign),
+            Class::Nan { .. } => return write!(f, "NaN"),
+        };
+
+        // Pad the mantissa on the right to whole hex digits (23 bits become
+        // 24), then drop the trailing zeros.
+        let mantissa_bits = self.precision.mantissa_bits();
+        let digits = mantissa_bits.div_ceil(4);
+        let mantissa = self.mantissa() << (digits * 4 - mantissa_bits);
+        let hex = format!("{:0w$x}", mantissa, w = digits as usize);
+        let hex = hex.trim_end_matches('0');
+        let point = if hex.is_empty() { "" } else { "." };
+        write!(f, "{}0x{}{}{}p{:+}", sign, lead, point, hex, exponent)
+    }
+}
+
+// A few lines explaining every field.
+impl fmt::Display for Ieee754 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let p = self.precision;
+        // Print `f32`s as `f32`s, or 3.3 would come out as 3.299999952316284.
+        let (value, significand) = match p {
+            Precision::Single => (format!("{:?}", self.value() as f32), (self.significand() as f32).to_string()),
+            Precision::Double => (format!("{:?}", self.value()), self.significand().to_string()),
+        };
+        writeln!(f, "{} as {}", value, p.name())?;
+        writeln!(f, "  bits:      {:b}", self)?;
+        writeln!(f, "  hex:       {:#0w$x}", self.bits, w = 2 + (1 + p.exponent_bits() + p.mantissa_bits()) as usize / 4)?;
+        writeln!(f, "  sign:      {} ({})", self.is_negative() as u8, if self.is_negative() { "-" } else { "+" })?;
+        match self.exponent() {
+            Some(e) => writeln!(f, "  exponent:  {} (biased), {} (unbiased, bias {})", self.biased_exponent(), e, p.bias())?,
+            None => writeln!(f, "  exponent:  {} (all ones)", self.biased_exponent())?,
+        }
+        writeln!(f, "  mantissa:  {:#x}", self.mantissa())?;
+        let sign = if self.is_negative() { '-' } else { '+' };
+        match self.class() {
+            Class::Zero => write!(f, "  class:     zero ({}0)", sign),
+            Class::Subnormal | Class::Normal => {
+                let kind = if self.class() == Class::Normal { "normal" } else { "subnormal" };
+                write!(
+                    f,
+                    "  class:     {}, {}{} × 2^{} = {:x}",
+                    kind,
+                    sign,
+                    significand,
+                    self.exponent().unwrap_or(0),
+                    self
+                )
+            }
+            Class::Infinite => write!(f, "  class:     {}infinity", sign),
+            Class::Nan { quiet, payload } => {
+                let kind = if quiet { "quiet" } else { "signaling" };
+                write!(f, "  class:     {} NaN, payload {:#x}", kind, payload)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_floats_into_fields() {
+        let x = Ieee754::from(3.3_f32);
+        assert!(!x.is_negative());
+        assert_eq!(x.biased_exponent(), 128);
+        assert_eq!(x.exponent(), Some(1));
+        assert_eq!(x.mantissa(), 0x533333);
+        assert_eq!(format!("{:b}", x), "0 10000000 10100110011001100110011");
+
+        let y = Ieee754::from(-2.0_f64);
+        assert!(y.is_negative());
+        assert_eq!(y.biased_exponent(), 1024);
+        assert_eq!(y.exponent(), Some(1));
+        assert_eq!(y.mantissa(), 0);
+        assert_eq!(y.value(), -2.0);
+    }
+
+    #[test]
+    fn classifies_special_values() {
+        assert_eq!(Ieee754::from(-0.0_f64).class(), Class::Zero);
+        assert_eq!(Ieee754::from(f64::MIN_POSITIVE).class(), Class::Normal);
+        assert_eq!(Ieee754::from(f64::MIN_POSITIVE / 2.0).class(), Class::Subnormal);
+        assert_eq!(Ieee754::from(f32::from_bits(1)).exponent(), Some(-126));
+        assert_eq!(Ieee754::from(f32::NEG_INFINITY).class(), Class::Infinite);
+        assert_eq!(Ieee754::from(f32::NEG_INFINITY).exponent(), None);
+        assert_eq!(Ieee754::from(f64::NAN).class(), Class::Nan { quiet: true, payload: 0 });
+        let signaling = Ieee754::from_bits(Precision::Single, 0x7f80_0001);
+        assert_eq!(signaling.class(), Class::Nan { quiet: false, payload: 1 });
+        assert_eq!(Ieee754::from_bits(Precision::Single, 0x7fc0_002a).class(), Class::Nan { quiet: true, payload: 42 });
+    }
+
+    #[test]
+    fn writes_hex_floats() {
+        assert_eq!(format!("{:x}", Ieee754::from(3.3_f32)), "0x1.a66666p+1");
+        assert_eq!(format!("{:x}", Ieee754::from(1.0_f64)), "0x1p+0");
+        assert_eq!(format!("{:x}", Ieee754::from(-0.1_f64)), "-0x1.999999999999ap-4");
+        assert_eq!(format!("{:x}", Ieee754::from(f64::from_bits(1))), "0x0.0000000000001p-1022");
+        assert_eq!(format!("{:x}", Ieee754::from(-0.0_f32)), "-0x0p+0");
+        assert_eq!(format!("{:x}", Ieee754::from(f32::INFINITY)), "inf");
+    }
+
+    #[test]
+    fn explains_a_value() {
+        let text = Ieee754::from(-1.5_f32).to_string();
+        assert!(text.starts_with("-1.5 as f32\n"), "{}", text);
+        assert!(text.contains("  hex:       0xbfc00000\n"), "{}", text);
+        assert!(text.ends_with("class:     normal, -1.5 × 2^0 = -0x1.8p+0"), "{}", text);
+    }
+}
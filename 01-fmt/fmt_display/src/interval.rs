@@ -0,0 +1,453 @@
+// Intervals of ordered numbers, grown from `MinMax` in `main.rs`.
+//
+// Each end is either closed (the end point is included) or open (it is
+// not), and `Display` uses the usual notation for that:
+//
+//     Interval::closed(-3, 3)          [-3, 3]
+//     Interval::closed_open(-3, 3)     [-3, 3)
+//     Interval::open(0.5, 1.5)         (0.5, 1.5)
+//
+// An interval whose start is past its end, or whose start equals its end
+// with either side open, holds nothing and prints as `∅`. `IntervalSet`
+// keeps several disjoint intervals and merges them as they are added.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    Closed,
+    Open,
+}
+
+impl Bound {
+    // Closed only if both are: the bound of a sum, or of an intersection
+    // where the end points meet.
+    fn both(self, other: Bound) -> Bound {
+        if self == Bound::Closed && other == Bound::Closed { Bound::Closed } else { Bound::Open }
+    }
+
+    // Closed if either is: the bound of a union where the end points meet.
+    fn either(self, other: Bound) -> Bound {
+        if self == Bound::Closed || other == Bound::Closed { Bound::Closed } else { Bound::Open }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Interval<T> {
+    pub lo: T,
+    pub hi: T,
+    pub lo_bound: Bound,
+    pub hi_bound: Bound,
+}
+
+impl<T: PartialOrd + Copy> Interval<T> {
+    pub fn new(lo: T, lo_bound: Bound, hi: T, hi_bound: Bound) -> Interval<T> {
+        Interval { lo, hi, lo_bound, hi_bound }
+    }
+
+    // `[lo, hi]`
+    pub fn closed(lo: T, hi: T) -> Interval<T> {
+        Interval::new(lo, Bound::Closed, hi, Bound::Closed)
+    }
+
+    // `(lo, hi)`
+    pub fn open(lo: T, hi: T) -> Interval<T> {
+        Interval::new(lo, Bound::Open, hi, Bound::Open)
+    }
+
+    // `[lo, hi)`, the shape of `lo..hi`.
+    pub fn closed_open(lo: T, hi: T) -> Interval<T> {
+        Interval::new(lo, Bound::Closed, hi, Bound::Open)
+    }
+
+    // `(lo, hi]`
+    pub fn open_closed(lo: T, hi: T) -> Interval<T> {
+        Interval::new(lo, Bound::Open, hi, Bound::Closed)
+    }
+
+    // `[x, x]`, holding just `x`.
+    pub fn point(x: T) -> Interval<T> {
+        Interval::closed(x, x)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        // Written so that a NaN end point makes the interval empty.
+        match self.lo.partial_cmp(&self.hi) {
+            Some(Ordering::Less) => false,
+            Some(Ordering::Equal) => self.lo_bound.both(self.hi_bound) == Bound::Open,
+            _ => true,
+        }
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        let above = match self.lo_bound {
+            Bound::Closed => self.lo <= x,
+            Bound::Open => self.lo < x,
+        };
+        let below = match self.hi_bound {
+            Bound::Closed => x <= self.hi,
+            Bound::Open => x < self.hi,
+        };
+        above && below
+    }
+
+    // Whether every number in `other` is also in `self`.
+    pub fn contains_interval(&self, other: &Interval<T>) -> bool {
+        other.is_empty() || (!self.is_empty() && starts_before(self, other) && ends_after(self, other))
+    }
+
+    // Whether the two have a number in common.
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    // The numbers in both. May be empty.
+    pub fn intersection(&self, other: &Interval<T>) -> Interval<T> {
+        let (lo, lo_bound) = if starts_before(self, other) {
+            (other.lo, other.lo_bound)
+        } else {
+            (self.lo, self.lo_bound)
+        };
+        let (hi, hi_bound) = if ends_after(self, other) {
+            (other.hi, other.hi_bound)
+        } else {
+            (self.hi, self.hi_bound)
+        };
+        Interval { lo, hi, lo_bound, hi_bound }
+    }
+
+    // The smallest interval holding both, including any gap between them.
+    pub fn hull(&self, other: &Interval<T>) -> Interval<T> {
+        if other.is_empty() {
+            return *self;
+        }
+        if self.is_empty() {
+            return *other;
+        }
+        let start = if starts_before(self, other) { self } else { other };
+        let end = if ends_after(self, other) { self } else { other };
+        Interval { lo: start.lo, hi: end.hi, lo_bound: start.lo_bound, hi_bound: end.hi_bound }
+    }
+
+    // The numbers in either, if that is a single interval: the two have to
+    // overlap or touch, as `[1, 2)` and `[2, 3]` do.
+    pub fn union(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.is_empty() || other.is_empty() {
+            return Some(self.hull(other));
+        }
+        let (first, second) = if starts_before(self, other) { (self, other) } else { (other, self) };
+        let joined = match second.lo.partial_cmp(&first.hi) {
+            Some(Ordering::Less) => true,
+            Some(Ordering::Equal) => first.hi_bound.either(second.lo_bound) == Bound::Closed,
+            _ => false,
+        };
+        if joined { Some(self.hull(other)) } else { None }
+    }
+}
+
+// Whether `a` starts no later than `b`. At the same end point a closed
+// start is earlier than an open one.
+fn starts_before<T: PartialOrd>(a: &Interval<T>, b: &Interval<T>) -> bool {
+    a.lo < b.lo || (a.lo == b.lo && (a.lo_bound == Bound::Closed || b.lo_bound == Bound::Open))
+}
+
+// Whether `a` ends no earlier than `b`.
+fn ends_after<T: PartialOrd>(a: &Interval<T>, b: &Interval<T>) -> bool {
+    a.hi > b.hi || (a.hi == b.hi && (a.hi_bound == Bound::Closed || b.hi_bound == Bound::Open))
+}
+
+// All empty intervals are equal, whatever their end points.
+impl<T: PartialOrd + Copy> PartialEq for Interval<T> {
+    fn eq(&self, other: &Interval<T>) -> bool {
+        match (self.is_empty(), other.is_empty()) {
+            (true, true) => true,
+            (false, false) => {
+                self.lo == other.lo
+                    && self.hi == other.hi
+                    && self.lo_bound == other.lo_bound
+                    && self.hi_bound == other.hi_bound
+            }
+            _ => false,
+        }
+    }
+}
+
+// Interval arithmetic: the result holds `x op y` for every `x` in the left
+// interval and `y` in the right one. An end of the result is closed only
+// when it is reached, which needs both of the ends it came from.
+
+// [a, b] + [c, d] = [a + c, b + d]
+impl<T: PartialOrd + Copy + Add<Output = T>> Add for Interval<T> {
+    type Output = Interval<T>;
+
+    fn add(self, other: Interval<T>) -> Interval<T> {
+        if self.is_empty() {
+            return self;
+        }
+        if other.is_empty() {
+            return other;
+        }
+        Interval {
+            lo: self.lo + other.lo,
+            hi: self.hi + other.hi,
+            lo_bound: self.lo_bound.both(other.lo_bound),
+            hi_bound: self.hi_bound.both(other.hi_bound),
+        }
+    }
+}
+
+// -[a, b] = [-b, -a]
+impl<T: PartialOrd + Copy + Neg<Output = T>> Neg for Interval<T> {
+    type Output = Interval<T>;
+
+    fn neg(self) -> Interval<T> {
+        Interval { lo: -self.hi, hi: -self.lo, lo_bound: self.hi_bound, hi_bound: self.lo_bound }
+    }
+}
+
+// [a, b] - [c, d] = [a - d, b - c]
+impl<T: PartialOrd + Copy + Sub<Output = T>> Sub for Interval<T> {
+    type Output = Interval<T>;
+
+    fn sub(self, other: Interval<T>) -> Interval<T> {
+        if self.is_empty() {
+            return self;
+        }
+        if other.is_empty() {
+            return other;
+        }
+        Interval {
+            lo: self.lo - other.hi,
+            hi: self.hi - other.lo,
+            lo_bound: self.lo_bound.both(other.hi_bound),
+            hi_bound: self.hi_bound.both(other.lo_bound),
+        }
+    }
+}
+
+// [a, b] × [c, d] runs from the smallest to the largest of ac, ad, bc and
+// bd. A product is reached if both factors are, or if one of them is a
+// closed zero. `T::default()` is taken to be zero, as it is for numbers.
+impl<T: PartialOrd + Copy + Default + Mul<Output = T>> Mul for Interval<T> {
+    type Output = Interval<T>;
+
+    fn mul(self, other: Interval<T>) -> Interval<T> {
+        if self.is_empty() {
+            return self;
+        }
+        if other.is_empty() {
+            return other;
+        }
+        let zero = T::default();
+        let product = |x: T, xb: Bound, y: T, yb: Bound| {
+            let reached = xb.both(yb) == Bound::Closed
+                || (x == zero && xb == Bound::Closed)
+                || (y == zero && yb == Bound::Closed);
+            (x * y, if reached { Bound::Closed } else { Bound::Open })
+        };
+        let products = [
+            product(self.lo, self.lo_bound, other.lo, other.lo_bound),
+            product(self.lo, self.lo_bound, other.hi, other.hi_bound),
+            product(self.hi, self.hi_bound, other.lo, other.lo_bound),
+            product(self.hi, self.hi_bound, other.hi, other.hi_bound),
+        ];
+
+        // On a tie the closed bound wins: the value is reached one way.
+        let mut lo = products[0];
+        let mut hi = products[0];
+        for &(value, bound) in &products[1..] {
+            if value < lo.0 || (value == lo.0 && bound == Bound::Closed) {
+                lo = (value, bound);
+            }
+            if value > hi.0 || (value == hi.0 && bound == Bound::Closed) {
+                hi = (value, bound);
+            }
+        }
+        Interval { lo: lo.0, hi: hi.0, lo_bound: lo.1, hi_bound: hi.1 }
+    }
+}
+
+// `[-3, 3)`. The formatter is passed on to both end points, so `{:.1}`
+// works on float intervals.
+impl<T: PartialOrd + Copy + fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("∅");
+        }
+        f.write_str(if self.lo_bound == Bound::Closed { "[" } else { "(" })?;
+        fmt::Display::fmt(&self.lo, f)?;
+        f.write_str(", ")?;
+        fmt::Display::fmt(&self.hi, f)?;
+        f.write_str(if self.hi_bound == Bound::Closed { "]" } else { ")" })
+    }
+}
+
+// A union of intervals, kept as a sorted list of disjoint ones. Adding an
+// interval that overlaps or touches others merges them into one.
+#[derive(Debug, Clone)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: PartialOrd + Copy> PartialEq for IntervalSet<T> {
+    fn eq(&self, other: &IntervalSet<T>) -> bool {
+        self.intervals == other.intervals
+    }
+}
+
+impl<T: PartialOrd + Copy> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { intervals: Vec::new() }
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        // Everything the new interval joins up with is folded into it; the
+        // rest stays as it is.
+        let mut merged = interval;
+        self.intervals.retain(|existing| match merged.union(existing) {
+            Some(union) => {
+                merged = union;
+                false
+            }
+            None => true,
+        });
+        let at = self.intervals.partition_point(|existing| starts_before(existing, &merged));
+        self.intervals.insert(at, merged);
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.intervals.iter().any(|interval| interval.contains(x))
+    }
+
+    // The intervals, in order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    // How many disjoint intervals there are.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+}
+
+impl<T: PartialOrd + Copy> Default for IntervalSet<T> {
+    fn default() -> IntervalSet<T> {
+        IntervalSet::new()
+    }
+}
+
+impl<T: PartialOrd + Copy> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, intervals: I) {
+        for interval in intervals {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<T: PartialOrd + Copy> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(intervals: I) -> IntervalSet<T> {
+        let mut set = IntervalSet::new();
+        set.extend(intervals);
+        set
+    }
+}
+
+// `[1, 2) ∪ [3, 4]`, or `∅`.
+impl<T: PartialOrd + Copy + fmt::Display> fmt::Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.intervals.is_empty() {
+            return f.write_str("∅");
+        }
+        for (i, interval) in self.intervals.iter().enumerate() {
+            if i != 0 {
+                f.write_str(" ∪ ")?;
+            }
+            fmt::Display::fmt(interval, f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn displays_in_math_notation() {
+        assert_eq!(Interval::closed(-3, 3).to_string(), "[-3, 3]");
+        assert_eq!(Interval::closed_open(-3, 3).to_string(), "[-3, 3)");
+        assert_eq!(format!("{:.1}", Interval::open_closed(0.25, 1.0)), "(0.2, 1.0]");
+        assert_eq!(Interval::open(1, 1).to_string(), "∅");
+        assert_eq!(Interval::closed(2, 1), Interval::open(5, 5));
+    }
+
+    #[test]
+    fn tests_membership() {
+        let a = Interval::closed_open(0, 10);
+        assert!(a.contains(0) && a.contains(9) && !a.contains(10));
+        assert!(Interval::point(4).contains(4));
+        assert!(a.contains_interval(&Interval::open(0, 10)));
+        assert!(!a.contains_interval(&Interval::closed(0, 10)));
+        assert!(a.contains_interval(&Interval::open(7, 3)));
+        assert!(!Interval::open(f64::NAN, 1.0).contains(0.5));
+    }
+
+    #[test]
+    fn combines_intervals() {
+        let a = Interval::closed(0, 5);
+        let b = Interval::open(3, 8);
+        assert_eq!(a.intersection(&b), Interval::open_closed(3, 5));
+        assert_eq!(a.hull(&b), Interval::closed_open(0, 8));
+        assert_eq!(a.union(&b), Some(Interval::closed_open(0, 8)));
+        assert!(a.overlaps(&b));
+
+        // Touching at a point that one of them includes still joins up.
+        let c = Interval::closed_open(1, 2);
+        assert_eq!(c.union(&Interval::closed(2, 3)), Some(Interval::closed(1, 3)));
+        assert_eq!(c.union(&Interval::open(2, 3)), None);
+        assert!(!c.overlaps(&Interval::closed(2, 3)));
+        assert_eq!(c.hull(&Interval::open(5, 6)), Interval::closed_open(1, 6));
+        assert!(c.intersection(&Interval::open(5, 6)).is_empty());
+    }
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Interval::closed(1, 2);
+        let b = Interval::closed_open(-3, 4);
+        assert_eq!(a + b, Interval::closed_open(-2, 6));
+        assert_eq!(a - b, Interval::open_closed(-3, 5));
+        assert_eq!(-b, Interval::open_closed(-4, 3));
+        assert_eq!(a * b, Interval::closed_open(-6, 8));
+        assert_eq!(Interval::open(-1, 1) * Interval::open(-1, 1), Interval::open(-1, 1));
+        // 0 is reached through the closed zero even though (2, 3) is open.
+        assert_eq!(Interval::closed(0, 1) * Interval::open(2, 3), Interval::closed_open(0, 3));
+        assert!((Interval::open(1, 1) + a).is_empty());
+    }
+
+    #[test]
+    fn merges_sets() {
+        let mut set: IntervalSet<i32> =
+            [Interval::closed(5, 7), Interval::closed_open(0, 2), Interval::open(10, 12)].into_iter().collect();
+        assert_eq!(set.to_string(), "[0, 2) ∪ [5, 7] ∪ (10, 12)");
+
+        set.insert(Interval::closed(2, 5));
+        assert_eq!(set.to_string(), "[0, 7] ∪ (10, 12)");
+        set.insert(Interval::open(7, 10));
+        assert_eq!(set.to_string(), "[0, 10) ∪ (10, 12)");
+        set.insert(Interval::point(10));
+        assert_eq!(set.to_string(), "[0, 12)");
+        assert_eq!(set.len(), 1);
+        assert!(set.contains(11) && !set.contains(12));
+
+        assert_eq!(IntervalSet::<f64>::new().to_string(), "∅");
+    }
+}
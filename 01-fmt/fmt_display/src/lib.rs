@@ -0,0 +1,9 @@
+// The parts of the `fmt_display` example that are worth reusing elsewhere.
+
+pub mod complex;
+pub mod ieee754;
+pub mod interval;
+
+pub use complex::Complex;
+pub use ieee754::Ieee754;
+pub use interval::{Interval, IntervalSet};
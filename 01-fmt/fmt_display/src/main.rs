@@ -1,8 +1,11 @@
 use std::fmt; // Import `fmt`
 
+use fmt_display::{Complex, Ieee754, Interval, IntervalSet};
+use json::{FromJson, FromJsonError, Json, ToJson};
+
 // A structure holding two numbers. `Debug` will be derived so the results can
 // be contrasted with `Display`.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 struct MinMax(i64, i64);
 
 // Implement `Display` for `MinMax`.
@@ -13,8 +16,17 @@ impl fmt::Display for MinMax {
     }
 }
 
+// A `MinMax` is the closed interval from its first number to its second.
+// `Interval` (in `src/interval.rs`) can also leave out either end point and
+// do arithmetic.
+impl From<MinMax> for Interval<i64> {
+    fn from(minmax: MinMax) -> Interval<i64> {
+        Interval::closed(minmax.0, minmax.1)
+    }
+}
+
 // Define a structure where the fields are nameable for comparison.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 struct Point2D {
     x: f64,
     y: f64,
@@ -28,26 +40,61 @@ impl fmt::Display for Point2D {
     }
 }
 
+// `{:b}` shows the IEEE-754 bits of each coordinate: sign, exponent and
+// mantissa.
 impl fmt::Binary for Point2D {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // Customize so only `x` and `y` are denoted.
-        write!(f, "x: {}, y: {}", self.x, self.y)
+        write!(f, "x: {:b}, y: {:b}", Ieee754::from(self.x), Ieee754::from(self.y))
     }
 }
 
-#[derive(Debug)]
-struct Complex {
-    real: f64,
-    imag: f64,
+// `{:x}` shows them as hexadecimal floats, `0x1.a666666666666p+1`.
+impl fmt::LowerHex for Point2D {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "x: {:x}, y: {:x}", Ieee754::from(self.x), Ieee754::from(self.y))
+    }
 }
 
-impl fmt::Display for Complex {
+// `{:E}` uses the float's own scientific notation, passing the formatter on
+// so that `{:.2E}` sets the precision of both.
+impl fmt::UpperExp for Point2D {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // Customize so only `x` and `y` are denoted.
-        write!(f, "{} + {}i", self.real, self.imag)
+        f.write_str("x: ")?;
+        fmt::UpperExp::fmt(&self.x, f)?;
+        f.write_str(", y: ")?;
+        fmt::UpperExp::fmt(&self.y, f)
+    }
+}
+
+// `Complex` lives in `src/complex.rs` now. Besides printing as `3.3 + 7.2i`
+// it does arithmetic and can be parsed from the same text.
+
+// JSON versions of the structures (`Complex` has its own). A tuple struct becomes an array,
+// a struct with named fields becomes an object.
+impl ToJson for MinMax {
+    fn to_json(&self) -> Json {
+        (self.0, self.1).to_json()
     }
 }
 
+impl FromJson<'_> for MinMax {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        let (min, max) = FromJson::from_json(json)?;
+        Ok(MinMax(min, max))
+    }
+}
+
+impl ToJson for Point2D {
+    fn to_json(&self) -> Json {
+        Json::object([("x", self.x.to_json()), ("y", self.y.to_json())])
+    }
+}
+
+impl FromJson<'_> for Point2D {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        Ok(Point2D { x: json.field("x")?, y: json.field("y")? })
+    }
+}
 
 fn main() {
     let minmax = MinMax(0, 14);
@@ -63,6 +110,14 @@ fn main() {
              small = small_range,
              big = big_range);
 
+    // As intervals, the ranges can be combined.
+    let big = Interval::from(big_range);
+    let small = Interval::closed_open(-3, 3);
+    println!("{} + {} = {}", big, small, big + small);
+    println!("{} ∩ {} = {}", big, Interval::open(200, 400), big.intersection(&Interval::open(200, 400)));
+    let set: IntervalSet<i64> = [small, Interval::closed(3, 5), Interval::open(10, 20)].into_iter().collect();
+    println!("Merged: {}", set);
+
     let point = Point2D { x: 3.3, y: 7.2 };
 
     println!("Compare points:");
@@ -70,9 +125,12 @@ fn main() {
     println!("Debug: {:?}", point);
     println!("Pretty: {:#?}", point);
 
-    // Error. Both `Debug` and `Display` were implemented, but `{:b}`
-    // requires `fmt::Binary` to be implemented. This will not work.
+    // `{:b}` requires `fmt::Binary` to be implemented. Ours prints the bits
+    // of each `f64`; `{:x}` and `{:E}` have implementations as well.
     println!("What does Point2D look like in binary: {:b}?", point);
+    println!("In hex: {:x}", point);
+    println!("In scientific notation: {:.2E}", point);
+    println!("{}", Ieee754::from(point.x));
 
 
     // After checking the output of the above example, use the Point2D struct as a guide to add a Complex struct to the example. When printed in the same way, the output should be:
@@ -84,4 +142,47 @@ fn main() {
     println!("Debug: {:?}", complex);
     println!("Pretty: {:#?}", complex);
 
-}
\ No newline at end of file
+    // The flags reach both parts, and the text can be parsed back.
+    println!("Precision and sign: {:+.2}", complex);
+    let other: Complex = "-1-2i".parse().unwrap();
+    println!("({}) * ({}) = {:.3}", complex, other, complex * other);
+    println!("({}) / ({}) = {:.3}", complex, other, complex / other);
+    println!("|{}| = {:.3}, arg = {:.3}", complex, complex.modulus(), complex.arg());
+    println!("sqrt(-1) = {}, e^(iπ) = {:.3}", Complex::from(-1.0).sqrt(), Complex::new(0.0, std::f64::consts::PI).exp());
+
+    // Each of them can also be written as JSON and read back.
+    println!("JSON: {} {} {}", minmax.to_json(), point.to_json(), complex.to_json());
+    let parsed: Complex = json::from_str(r#"{"real": 3.3, "imag": 7.2}"#).unwrap();
+    println!("Parsed: {}", parsed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let minmax = MinMax(-300, 300);
+        assert_eq!(json::to_string(&minmax), "[-300,300]");
+        assert_eq!(json::from_str::<MinMax>("[-300, 300]"), Ok(minmax));
+
+        let point = Point2D { x: 3.3, y: 7.2 };
+        assert_eq!(json::to_string(&point), r#"{"x":3.3,"y":7.2}"#);
+        assert_eq!(json::from_str::<Point2D>(&json::to_string(&point)), Ok(point));
+
+        let complex = Complex { real: 3.3, imag: -7.2 };
+        assert_eq!(json::from_str::<Complex>(&json::to_string(&complex)), Ok(complex));
+    }
+
+    #[test]
+    fn formats_point_bits() {
+        let point = Point2D { x: 1.0, y: -0.5 };
+        assert_eq!(
+            format!("{:b}", point),
+            "x: 0 01111111111 0000000000000000000000000000000000000000000000000000, \
+             y: 1 01111111110 0000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(format!("{:x}", point), "x: 0x1p+0, y: -0x1p-1");
+        assert_eq!(format!("{:.1E}", Point2D { x: 3.3, y: 7200.0 }), "x: 3.3E0, y: 7.2E3");
+    }
+}
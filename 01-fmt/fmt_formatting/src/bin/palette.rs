@@ -0,0 +1,96 @@
+// Prints an accessibility and palette report for a list of colors:
+//
+//     $ cargo run --bin palette -- '#80FF5A' '#0003FE' navy
+//     $ echo '#767676 #FFFFFF' | cargo run --bin palette -- --steps 3
+//
+// Colors are anything `Color` parses (hex, `rgb(...)`, names) and are read
+// from stdin when none are given on the command line. `--steps` sets the
+// length of the gradients between neighbouring colors (5 by default).
+
+use std::io::{self, Read};
+use std::process;
+
+use fmt_formatting::color::{Color, ParseColorError};
+use fmt_formatting::palette::{self, Contrast, Level, TextSize};
+use table::{Align, Style, Table};
+
+fn fail(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(1);
+}
+
+fn hex(color: Color) -> String {
+    format!("{:#X}", color)
+}
+
+fn main() {
+    let mut steps = 5;
+    let mut words = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--steps" => {
+                let value = args.next().unwrap_or_else(|| fail("`--steps` needs a number"));
+                steps = value.parse().unwrap_or_else(|_| fail(&format!("invalid number of steps `{}`", value)));
+            }
+            _ => words.push(arg),
+        }
+    }
+    if words.is_empty() {
+        let mut input = String::new();
+        if let Err(e) = io::stdin().read_to_string(&mut input) {
+            fail(&e.to_string());
+        }
+        words = input.split_whitespace().map(str::to_owned).collect();
+    }
+    if words.is_empty() {
+        fail("no colors given");
+    }
+
+    let colors: Vec<Color> = words
+        .iter()
+        .map(|word| word.parse().unwrap_or_else(|e: ParseColorError| fail(&e.to_string())))
+        .collect();
+    let white = Color::rgb(255, 255, 255);
+    let black = Color::rgb(0, 0, 0);
+
+    let mut report = Table::new(["Color", "HSL", "Luminance", "On white", "On black", "Complement", "Triad"]);
+    report.style(Style::BOX).align(2, Align::Right);
+    for &color in &colors {
+        let [_, second, third] = color.triadic();
+        report.row([
+            hex(color),
+            color.to_hsl().to_string(),
+            format!("{:.3}", color.luminance()),
+            color.contrast(white).to_string(),
+            color.contrast(black).to_string(),
+            hex(color.complementary()),
+            format!("{} {}", hex(second), hex(third)),
+        ]);
+    }
+    println!("{}", report);
+
+    // Every color as text on every other one.
+    if colors.len() > 1 {
+        let mut headers = vec![String::from("text \\ background")];
+        headers.extend(colors.iter().map(|&c| hex(c)));
+        let mut matrix = Table::new(headers);
+        for &text in &colors {
+            let mut row = vec![hex(text)];
+            row.extend(colors.iter().map(|&background| {
+                if text == background { String::from("-") } else { text.contrast(background).to_string() }
+            }));
+            matrix.row(row);
+        }
+        println!("{}", matrix);
+    }
+
+    for pair in colors.windows(2) {
+        let stops: Vec<String> = palette::gradient(pair[0], pair[1], steps).into_iter().map(hex).collect();
+        println!("{} -> {}: {}", hex(pair[0]), hex(pair[1]), stops.join(" "));
+    }
+
+    let aa = Contrast::required(Level::Aa, TextSize::Normal);
+    let failing = colors.iter().filter(|c| c.contrast(c.text_color()).ratio < aa).count();
+    println!("\n{} of {} colors can carry normal text in black or white at AA.", colors.len() - failing, colors.len());
+}
@@ -0,0 +1,442 @@
+// The `Color` from the example, with an alpha channel, parsing, and the
+// HSL and HSV color models.
+//
+//     "#80FF5A".parse::<Color>()         Color::rgb(128, 255, 90)
+//     "rgba(128, 255, 90, 0.5)"          the same, half transparent
+//     "rebeccapurple"                    one of the CSS color names
+//
+//     format!("{:x}", color)             80ff5a
+//     format!("{:#X}", color)            #80FF5A
+//
+// HSL and HSV values are kept as `f64`s and only rounded when turned back
+// into a `Color`, so RGB -> HSL -> RGB gives back the color it started from.
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use json::{FromJson, FromJsonError, Json, ToJson};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    // 0 is fully transparent, 255 fully opaque.
+    pub alpha: u8,
+}
+
+// Hue in degrees (0 ≤ h < 360), saturation and lightness from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+// Hue in degrees (0 ≤ h < 360), saturation and value from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsv {
+    pub h: f64,
+    pub s: f64,
+    pub v: f64,
+}
+
+impl Color {
+    // An opaque color.
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Color {
+        Color { red, green, blue, alpha: 255 }
+    }
+
+    pub const fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Color {
+        Color { red, green, blue, alpha }
+    }
+
+    pub fn with_alpha(self, alpha: u8) -> Color {
+        Color { alpha, ..self }
+    }
+
+    // The channels as fractions from 0 to 1.
+    fn fractions(self) -> (f64, f64, f64) {
+        (self.red as f64 / 255.0, self.green as f64 / 255.0, self.blue as f64 / 255.0)
+    }
+
+    // The hue both models share, and the largest and smallest channel.
+    fn hue(self) -> (f64, f64, f64) {
+        let (r, g, b) = self.fractions();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let h = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        (h, max, min)
+    }
+
+    pub fn to_hsl(self) -> Hsl {
+        let (h, max, min) = self.hue();
+        let l = (max + min) / 2.0;
+        let s = if max == min { 0.0 } else { (max - min) / (1.0 - (2.0 * l - 1.0).abs()) };
+        Hsl { h, s, l }
+    }
+
+    pub fn to_hsv(self) -> Hsv {
+        let (h, max, min) = self.hue();
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+        Hsv { h, s, v: max }
+    }
+
+    // The opaque color for `hsl`. Out of range values are clamped, and the
+    // hue wraps around.
+    pub fn from_hsl(hsl: Hsl) -> Color {
+        let s = hsl.s.clamp(0.0, 1.0);
+        let l = hsl.l.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        Color::from_chroma(hsl.h, chroma, l - chroma / 2.0)
+    }
+
+    pub fn from_hsv(hsv: Hsv) -> Color {
+        let s = hsv.s.clamp(0.0, 1.0);
+        let v = hsv.v.clamp(0.0, 1.0);
+        let chroma = v * s;
+        Color::from_chroma(hsv.h, chroma, v - chroma)
+    }
+
+    // Both models come down to a hue, a chroma (the spread between the
+    // largest and smallest channel) and the smallest channel.
+    fn from_chroma(h: f64, chroma: f64, min: f64) -> Color {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        Color::rgb(channel(r + min), channel(g + min), channel(b + min))
+    }
+
+    // Linear interpolation towards `other`, alpha included: `t` of 0 gives
+    // `self`, 1 gives `other`.
+    pub fn blend(self, other: Color, t: f64) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Color {
+            red: mix(self.red, other.red),
+            green: mix(self.green, other.green),
+            blue: mix(self.blue, other.blue),
+            alpha: mix(self.alpha, other.alpha),
+        }
+    }
+
+    // Raise the HSL lightness by `amount` (0 to 1), keeping hue, saturation
+    // and alpha.
+    pub fn lighten(self, amount: f64) -> Color {
+        let hsl = self.to_hsl();
+        Color::from_hsl(Hsl { l: hsl.l + amount, ..hsl }).with_alpha(self.alpha)
+    }
+
+    pub fn darken(self, amount: f64) -> Color {
+        self.lighten(-amount)
+    }
+}
+
+// A fraction from 0 to 1 as a channel value.
+fn channel(fraction: f64) -> u8 {
+    (fraction * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.alpha == 255 {
+            write!(f, "RGB ({}, {}, {}) 0x{:06X}", self.red, self.green, self.blue, self)
+        } else {
+            write!(f, "RGBA ({}, {}, {}, {}) 0x{:08X}", self.red, self.green, self.blue, self.alpha, self)
+        }
+    }
+}
+
+// Six hex digits, or eight if the color is not opaque. `{:#x}` puts a `#`
+// in front, the way CSS writes colors. Width and fill are honored, which is
+// what lets `Display` write `{:06X}`.
+macro_rules! hex {
+    ($trait:ident, $digits:literal) => {
+        impl fmt::$trait for Color {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                let mut text = if f.alternate() { String::from("#") } else { String::new() };
+                for value in [self.red, self.green, self.blue] {
+                    text += &format!($digits, value);
+                }
+                if self.alpha != 255 {
+                    text += &format!($digits, self.alpha);
+                }
+                f.pad_integral(true, "", &text)
+            }
+        }
+    };
+}
+
+hex!(LowerHex, "{:02x}");
+hex!(UpperHex, "{:02X}");
+
+// `hsl(106, 100%, 68%)`, with the precision (0 by default) applied to every
+// number.
+impl Display for Hsl {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let p = f.precision().unwrap_or(0);
+        write!(f, "hsl({:.*}, {:.*}%, {:.*}%)", p, self.h, p, self.s * 100.0, p, self.l * 100.0)
+    }
+}
+
+// `hsv(106, 65%, 100%)`
+impl Display for Hsv {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let p = f.precision().unwrap_or(0);
+        write!(f, "hsv({:.*}, {:.*}%, {:.*}%)", p, self.h, p, self.s * 100.0, p, self.v * 100.0)
+    }
+}
+
+// The CSS named colors people actually use.
+const NAMES: &[(&str, Color)] = &[
+    ("black", Color::rgb(0, 0, 0)),
+    ("white", Color::rgb(255, 255, 255)),
+    ("red", Color::rgb(255, 0, 0)),
+    ("lime", Color::rgb(0, 255, 0)),
+    ("blue", Color::rgb(0, 0, 255)),
+    ("yellow", Color::rgb(255, 255, 0)),
+    ("cyan", Color::rgb(0, 255, 255)),
+    ("aqua", Color::rgb(0, 255, 255)),
+    ("magenta", Color::rgb(255, 0, 255)),
+    ("fuchsia", Color::rgb(255, 0, 255)),
+    ("silver", Color::rgb(192, 192, 192)),
+    ("gray", Color::rgb(128, 128, 128)),
+    ("grey", Color::rgb(128, 128, 128)),
+    ("maroon", Color::rgb(128, 0, 0)),
+    ("olive", Color::rgb(128, 128, 0)),
+    ("green", Color::rgb(0, 128, 0)),
+    ("purple", Color::rgb(128, 0, 128)),
+    ("teal", Color::rgb(0, 128, 128)),
+    ("navy", Color::rgb(0, 0, 128)),
+    ("orange", Color::rgb(255, 165, 0)),
+    ("pink", Color::rgb(255, 192, 203)),
+    ("brown", Color::rgb(165, 42, 42)),
+    ("gold", Color::rgb(255, 215, 0)),
+    ("indigo", Color::rgb(75, 0, 130)),
+    ("rebeccapurple", Color::rgb(102, 51, 153)),
+    ("transparent", Color::rgba(0, 0, 0, 0)),
+];
+
+impl Color {
+    // The CSS name of the color, if it has one.
+    pub fn name(self) -> Option<&'static str> {
+        NAMES.iter().find(|(_, color)| *color == self).map(|(name, _)| *name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    // `#` followed by something other than 3, 4, 6 or 8 hex digits.
+    InvalidHex(String),
+    // A malformed `rgb(...)` or `rgba(...)`, or a channel out of range.
+    InvalidFunction(String),
+    UnknownName(String),
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ParseColorError::InvalidHex(text) => {
+                write!(f, "invalid hex color `{}`: expected 3, 4, 6 or 8 hex digits after `#`", text)
+            }
+            ParseColorError::InvalidFunction(text) => write!(f, "invalid color function `{}`", text),
+            ParseColorError::UnknownName(text) => write!(f, "unknown color name `{}`", text),
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Color, ParseColorError> {
+        let text = s.trim();
+        if let Some(hex) = text.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(|| ParseColorError::InvalidHex(text.to_owned()));
+        }
+        let lower = text.to_ascii_lowercase();
+        if let Some(args) = lower.strip_prefix("rgba(").or_else(|| lower.strip_prefix("rgb(")) {
+            return parse_function(args).ok_or_else(|| ParseColorError::InvalidFunction(text.to_owned()));
+        }
+        NAMES
+            .iter()
+            .find(|(name, _)| *name == lower)
+            .map(|(_, color)| *color)
+            .ok_or_else(|| ParseColorError::UnknownName(text.to_owned()))
+    }
+}
+
+// `80FF5A`, `80FF5A80`, or the short forms `8F5` and `8F58` where every
+// digit is doubled.
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok();
+    let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    match hex.len() {
+        3 | 4 => {
+            let mut channels = [255; 4];
+            for (i, c) in channels.iter_mut().enumerate().take(hex.len()) {
+                *c = digit(i)? * 17;
+            }
+            Some(Color::rgba(channels[0], channels[1], channels[2], channels[3]))
+        }
+        6 => Some(Color::rgb(pair(0)?, pair(2)?, pair(4)?)),
+        8 => Some(Color::rgba(pair(0)?, pair(2)?, pair(4)?, pair(6)?)),
+        _ => None,
+    }
+}
+
+// `128, 255, 90)` or `128, 255, 90, 0.5)`: the alpha is a fraction, as in
+// CSS.
+fn parse_function(args: &str) -> Option<Color> {
+    let args: Vec<&str> = args.strip_suffix(')')?.split(',').map(str::trim).collect();
+    let channel = |text: &str| text.parse::<u8>().ok();
+    let alpha = match args.len() {
+        3 => 255,
+        4 => {
+            let alpha: f64 = args[3].parse().ok()?;
+            if !(0.0..=1.0).contains(&alpha) {
+                return None;
+            }
+            (alpha * 255.0).round() as u8
+        }
+        _ => return None,
+    };
+    Some(Color::rgba(channel(args[0])?, channel(args[1])?, channel(args[2])?, alpha))
+}
+
+// Opaque colors leave the alpha out.
+impl ToJson for Color {
+    fn to_json(&self) -> Json {
+        let mut entries = vec![
+            ("red", self.red.to_json()),
+            ("green", self.green.to_json()),
+            ("blue", self.blue.to_json()),
+        ];
+        if self.alpha != 255 {
+            entries.push(("alpha", self.alpha.to_json()));
+        }
+        Json::object(entries)
+    }
+}
+
+impl FromJson<'_> for Color {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        Ok(Color {
+            red: json.field("red")?,
+            green: json.field("green")?,
+            blue: json.field("blue")?,
+            alpha: match json.get("alpha") {
+                Some(_) => json.field("alpha")?,
+                None => 255,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex_functions_and_names() {
+        let green = Color::rgb(128, 255, 90);
+        assert_eq!("#80FF5A".parse(), Ok(green));
+        assert_eq!("#80ff5a".parse(), Ok(green));
+        assert_eq!("rgb(128,255,90)".parse(), Ok(green));
+        assert_eq!(" RGBA(128, 255, 90, 0.5) ".parse(), Ok(green.with_alpha(128)));
+        assert_eq!("#80FF5A80".parse(), Ok(green.with_alpha(128)));
+        assert_eq!("#8f5".parse(), Ok(Color::rgb(0x88, 0xff, 0x55)));
+        assert_eq!("#8f50".parse(), Ok(Color::rgba(0x88, 0xff, 0x55, 0)));
+        assert_eq!("RebeccaPurple".parse(), Ok(Color::rgb(102, 51, 153)));
+        assert_eq!(Color::rgb(255, 165, 0).name(), Some("orange"));
+
+        let err = |s: &str| s.parse::<Color>().unwrap_err();
+        assert_eq!(err("#80FF5"), ParseColorError::InvalidHex("#80FF5".to_owned()));
+        assert_eq!(err("#80FG5A"), ParseColorError::InvalidHex("#80FG5A".to_owned()));
+        assert_eq!(err("rgb(256, 0, 0)"), ParseColorError::InvalidFunction("rgb(256, 0, 0)".to_owned()));
+        assert_eq!(err("rgba(0, 0, 0, 2)"), ParseColorError::InvalidFunction("rgba(0, 0, 0, 2)".to_owned()));
+        assert_eq!(err("rgb(0, 0)"), ParseColorError::InvalidFunction("rgb(0, 0)".to_owned()));
+        assert_eq!(err("blurple").to_string(), "unknown color name `blurple`");
+    }
+
+    #[test]
+    fn formats_channels() {
+        let green = Color::rgb(128, 255, 90);
+        assert_eq!(green.to_string(), "RGB (128, 255, 90) 0x80FF5A");
+        assert_eq!(Color::rgb(0, 3, 254).to_string(), "RGB (0, 3, 254) 0x0003FE");
+        assert_eq!(format!("{:x}", green), "80ff5a");
+        assert_eq!(format!("{:#X}", green), "#80FF5A");
+        assert_eq!(format!("{:>9x}", green), "   80ff5a");
+        assert_eq!(format!("{:#x}", green.with_alpha(0)), "#80ff5a00");
+        assert_eq!(green.with_alpha(128).to_string(), "RGBA (128, 255, 90, 128) 0x80FF5A80");
+        assert_eq!(format!("{}", green.to_hsl()), "hsl(106, 100%, 68%)");
+        assert_eq!(format!("{:.1}", green.to_hsv()), "hsv(106.2, 64.7%, 100.0%)");
+    }
+
+    #[test]
+    fn round_trips_through_hsl_and_hsv() {
+        // Every 7th value of each channel: 37³ colors.
+        for red in (0..=255).step_by(7) {
+            for green in (0..=255).step_by(7) {
+                for blue in (0..=255).step_by(7) {
+                    let color = Color::rgb(red, green, blue);
+                    assert_eq!(Color::from_hsl(color.to_hsl()), color);
+                    assert_eq!(Color::from_hsv(color.to_hsv()), color);
+                }
+            }
+        }
+        let hsl = Color::rgb(255, 0, 0).to_hsl();
+        assert_eq!((hsl.h, hsl.s, hsl.l), (0.0, 1.0, 0.5));
+        assert_eq!(Color::from_hsl(Hsl { h: 480.0, s: 1.0, l: 0.5 }), Color::rgb(0, 255, 0));
+    }
+
+    #[test]
+    fn blends_lightens_and_darkens() {
+        let black = Color::rgb(0, 0, 0);
+        let white = Color::rgb(255, 255, 255);
+        assert_eq!(black.blend(white, 0.5), Color::rgb(128, 128, 128));
+        assert_eq!(black.blend(white.with_alpha(0), 1.0), white.with_alpha(0));
+        assert_eq!(black.blend(white, 2.0), white);
+
+        let red = Color::rgb(255, 0, 0);
+        assert_eq!(red.lighten(0.25), Color::rgb(255, 128, 128));
+        assert_eq!(red.darken(0.25), Color::rgb(128, 0, 0));
+        assert_eq!(red.lighten(1.0), white);
+        assert_eq!(red.with_alpha(10).darken(0.1).alpha, 10);
+        // Lightening then darkening by the same amount gets back to the
+        // start, give or take the rounding of each step.
+        let color = Color::rgb(90, 128, 200);
+        let back = color.lighten(0.2).darken(0.2);
+        for (a, b) in [(back.red, color.red), (back.green, color.green), (back.blue, color.blue)] {
+            assert!(a.abs_diff(b) <= 1, "{:?}", back);
+        }
+    }
+
+    #[test]
+    fn reads_optional_alpha_from_json() {
+        let green = Color::rgb(128, 255, 90);
+        assert_eq!(json::to_string(&green), r#"{"red":128,"green":255,"blue":90}"#);
+        assert_eq!(json::from_str::<Color>(&json::to_string(&green.with_alpha(3))), Ok(green.with_alpha(3)));
+    }
+}
@@ -0,0 +1,462 @@
+// Points on the Earth, and the `City` from the example built on them.
+//
+// A `Coord` parses from the ways coordinates are usually written:
+//
+//     "53.347778, -6.259722"              signed decimal degrees
+//     "53.348°N 6.260°W"                  decimal degrees with hemispheres
+//     "53°20'52\"N 6°15'35\"W"            degrees, minutes and seconds
+//     "53°20.87'N, 6°15.58'W"             degrees and decimal minutes
+//
+// and prints as decimal degrees (`{}`, `{:.1}`) or, with `{:#}`, as degrees,
+// minutes and seconds. Distances are along the surface of a sphere the size
+// of the Earth, which is within about 0.5% of the real thing.
+//
+// A `City` parses from its name and a `Coord`, separated by a colon, which
+// is also how it prints:
+//
+//     "Dublin: 53°20'52\"N 6°15'35\"W"
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use json::{FromJson, FromJsonError, Json, ToJson};
+
+// The mean radius of the Earth, in kilometres.
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+// Latitude and longitude in degrees: north and east are positive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coord {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl Coord {
+    pub fn new(lat: f64, lon: f64) -> Result<Coord, ParseCoordError> {
+        if !(-90.0..=90.0).contains(&lat) {
+            return Err(ParseCoordError::LatitudeOutOfRange(lat));
+        }
+        if !(-180.0..=180.0).contains(&lon) {
+            return Err(ParseCoordError::LongitudeOutOfRange(lon));
+        }
+        Ok(Coord { lat, lon })
+    }
+
+    // Great-circle distance in kilometres, by the haversine formula.
+    pub fn distance_to(&self, other: &Coord) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let dlat = lat2 - lat1;
+        let dlon = (other.lon - self.lon).to_radians();
+        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+        // `min` keeps rounding from pushing `a` past 1 for antipodal points.
+        2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+    }
+
+    // The compass direction to set off in to reach `other` along a great
+    // circle, in degrees clockwise from north: 0 ≤ bearing < 360.
+    pub fn bearing_to(&self, other: &Coord) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let dlon = (other.lon - self.lon).to_radians();
+        let y = dlon.sin() * lat2.cos();
+        let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+        y.atan2(x).to_degrees().rem_euclid(360.0)
+    }
+
+    // The point halfway along the great circle between the two.
+    pub fn midpoint(&self, other: &Coord) -> Coord {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let lon1 = self.lon.to_radians();
+        let dlon = (other.lon - self.lon).to_radians();
+        let bx = lat2.cos() * dlon.cos();
+        let by = lat2.cos() * dlon.sin();
+        let lat = (lat1.sin() + lat2.sin()).atan2(((lat1.cos() + bx).powi(2) + by.powi(2)).sqrt());
+        let lon = lon1 + by.atan2(lat1.cos() + bx);
+        // Bring the longitude back into -180..=180.
+        let lon = (lon.to_degrees() + 540.0).rem_euclid(360.0) - 180.0;
+        Coord { lat: lat.to_degrees(), lon }
+    }
+}
+
+// `53.348°N 6.260°W` with the precision given (3 places by default), or
+// `53°20'52"N 6°15'35"W` with `{:#}`, where the precision applies to the
+// seconds.
+impl Display for Coord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let lat_c = if self.lat >= 0.0 { 'N' } else { 'S' };
+        let lon_c = if self.lon >= 0.0 { 'E' } else { 'W' };
+        let precision = f.precision();
+        if f.alternate() {
+            let precision = precision.unwrap_or(0);
+            write_dms(f, self.lat.abs(), precision)?;
+            write!(f, "{} ", lat_c)?;
+            write_dms(f, self.lon.abs(), precision)?;
+            write!(f, "{}", lon_c)
+        } else {
+            let precision = precision.unwrap_or(3);
+            write!(f, "{:.*}°{} {:.*}°{}", precision, self.lat.abs(), lat_c, precision, self.lon.abs(), lon_c)
+        }
+    }
+}
+
+fn write_dms(f: &mut Formatter, degrees: f64, precision: usize) -> fmt::Result {
+    // Round the total number of seconds first, so that 59.9999" carries
+    // over into the minutes instead of printing as 60".
+    let scale = 10f64.powi(precision as i32);
+    let total = (degrees * 3600.0 * scale).round() / scale;
+    let d = (total / 3600.0).floor();
+    let m = ((total - d * 3600.0) / 60.0).floor();
+    let s = total - d * 3600.0 - m * 60.0;
+    write!(f, "{}°{}'{:.*}\"", d, m, precision, s)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseCoordError {
+    // Not two angles separated by a comma or a space.
+    Format(String),
+    // One of the two angles could not be read.
+    InvalidAngle(String),
+    // Minutes or seconds of 60 or more.
+    ComponentOutOfRange { name: &'static str, value: f64 },
+    LatitudeOutOfRange(f64),
+    LongitudeOutOfRange(f64),
+    // Two latitudes, two longitudes, or a hemisphere on the wrong axis.
+    Hemisphere(String),
+    // A city without a name or without the colon after it.
+    CityName(String),
+}
+
+impl Display for ParseCoordError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ParseCoordError::Format(text) => {
+                write!(f, "expected a latitude and a longitude, found `{}`", text)
+            }
+            ParseCoordError::InvalidAngle(text) => write!(f, "invalid angle `{}`", text),
+            ParseCoordError::ComponentOutOfRange { name, value } => {
+                write!(f, "{} must be less than 60, found {}", name, value)
+            }
+            ParseCoordError::LatitudeOutOfRange(lat) => {
+                write!(f, "latitude must be between -90 and 90 degrees, found {}", lat)
+            }
+            ParseCoordError::LongitudeOutOfRange(lon) => {
+                write!(f, "longitude must be between -180 and 180 degrees, found {}", lon)
+            }
+            ParseCoordError::Hemisphere(text) => write!(f, "mismatched hemispheres in `{}`", text),
+            ParseCoordError::CityName(text) => {
+                write!(f, "expected a name, a colon and a coordinate, found `{}`", text)
+            }
+        }
+    }
+}
+
+impl Error for ParseCoordError {}
+
+impl FromStr for Coord {
+    type Err = ParseCoordError;
+
+    fn from_str(s: &str) -> Result<Coord, ParseCoordError> {
+        let text = s.trim();
+        let format_error = || ParseCoordError::Format(text.to_owned());
+
+        // A comma separates the two halves if there is one. Otherwise a
+        // hemisphere letter ends the first half, and failing that it is
+        // whitespace.
+        let (first, second) = if let Some(halves) = text.split_once(',') {
+            halves
+        } else if let Some(end) = text.find(['N', 'S', 'E', 'W']).filter(|&i| i + 1 < text.len()) {
+            text.split_at(end + 1)
+        } else {
+            let mut halves = text.split_whitespace();
+            match (halves.next(), halves.next(), halves.next()) {
+                (Some(first), Some(second), None) => (first, second),
+                _ => return Err(format_error()),
+            }
+        };
+
+        let (a, a_hemisphere) = parse_angle(first)?;
+        let (b, b_hemisphere) = parse_angle(second)?;
+        let is_lat = |h: Option<char>| matches!(h, Some('N' | 'S'));
+        let is_lon = |h: Option<char>| matches!(h, Some('E' | 'W'));
+
+        // Longitude may come first, as long as the letters say so.
+        let (lat, lon) = if is_lon(a_hemisphere) || is_lat(b_hemisphere) {
+            if is_lat(a_hemisphere) || is_lon(b_hemisphere) {
+                return Err(ParseCoordError::Hemisphere(text.to_owned()));
+            }
+            (b, a)
+        } else {
+            (a, b)
+        };
+        Coord::new(lat, lon)
+    }
+}
+
+// One angle in decimal degrees, with its hemisphere letter if it had one.
+// A letter of `S` or `W` makes the angle negative.
+fn parse_angle(text: &str) -> Result<(f64, Option<char>), ParseCoordError> {
+    let invalid = || ParseCoordError::InvalidAngle(text.trim().to_owned());
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+    let (body, hemisphere) = match compact.chars().last() {
+        Some(c @ ('N' | 'S' | 'E' | 'W')) => (&compact[..compact.len() - 1], Some(c)),
+        _ => (&compact[..], None),
+    };
+    let (negative, body) = match body.strip_prefix('-') {
+        // `-6°W` would be a double negative.
+        Some(_) if hemisphere.is_some() => return Err(invalid()),
+        Some(rest) => (true, rest),
+        None => (false, body.strip_prefix('+').unwrap_or(body)),
+    };
+
+    // Degrees, then optionally minutes and seconds, each a number followed
+    // by its mark. Only the last one may have a fraction.
+    let marks: [&[char]; 3] = [&['°'], &['\'', '′'], &['"', '″']];
+    let mut parts = [0.0; 3];
+    let mut next = 0;
+    let mut rest = body;
+    let mut fraction = false;
+    while !rest.is_empty() {
+        let end = rest.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(rest.len());
+        let number = &rest[..end];
+        rest = &rest[end..];
+        let Some(mark) = rest.chars().next() else {
+            // A bare number is only allowed on its own, as decimal degrees.
+            if next != 0 {
+                return Err(invalid());
+            }
+            parts[0] = number.parse().map_err(|_| invalid())?;
+            next = 1;
+            break;
+        };
+        let Some(index) = (next..3).find(|&i| marks[i].contains(&mark)) else {
+            return Err(invalid());
+        };
+        if fraction || index != next {
+            return Err(invalid());
+        }
+        parts[index] = number.parse().map_err(|_| invalid())?;
+        fraction = number.contains('.');
+        next = index + 1;
+        rest = &rest[mark.len_utf8()..];
+    }
+    if next == 0 {
+        return Err(invalid());
+    }
+
+    let [degrees, minutes, seconds] = parts;
+    for (name, value) in [("minutes", minutes), ("seconds", seconds)] {
+        if value >= 60.0 {
+            return Err(ParseCoordError::ComponentOutOfRange { name, value });
+        }
+    }
+    let angle = degrees + minutes / 60.0 + seconds / 3600.0;
+    let negative = negative || matches!(hemisphere, Some('S' | 'W'));
+    Ok((if negative { -angle } else { angle }, hemisphere))
+}
+
+// The name may be borrowed, so a `City` can point into a parsed JSON
+// document or at a string literal, or owned when it was parsed from text.
+#[derive(Debug, PartialEq)]
+pub struct City<'a> {
+    pub name: Cow<'a, str>,
+    // Latitude
+    pub lat: f64,
+    // Longitude
+    pub lon: f64,
+}
+
+impl<'a> City<'a> {
+    pub fn new(name: impl Into<Cow<'a, str>>, coord: Coord) -> City<'a> {
+        City { name: name.into(), lat: coord.lat, lon: coord.lon }
+    }
+
+    pub fn coord(&self) -> Coord {
+        Coord { lat: self.lat, lon: self.lon }
+    }
+
+    // Kilometres from here to `other`.
+    pub fn distance_to(&self, other: &City) -> f64 {
+        self.coord().distance_to(&other.coord())
+    }
+
+    // A GeoJSON `Feature` with a `Point` geometry and the name as a
+    // property. Note that GeoJSON puts the longitude first.
+    pub fn to_geojson(&self) -> Json {
+        Json::object([
+            ("type", "Feature".to_json()),
+            (
+                "geometry",
+                Json::object([("type", "Point".to_json()), ("coordinates", [self.lon, self.lat].to_json())]),
+            ),
+            ("properties", Json::object([("name", (*self.name).to_json())])),
+        ])
+    }
+}
+
+impl Display for City<'_> {
+    // `f` is a buffer, and this method must write the formatted string into it.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let lat_c = if self.lat >= 0.0 { 'N' } else { 'S' };
+        let lon_c = if self.lon >= 0.0 { 'E' } else { 'W' };
+
+        // `write!` is like `format!`, but it will write the formatted string
+        // into a buffer (the first argument).
+        write!(f, "{}: {:.3}°{} {:.3}°{}",
+               self.name, self.lat.abs(), lat_c, self.lon.abs(), lon_c)
+    }
+}
+
+impl ToJson for City<'_> {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("name", (*self.name).to_json()),
+            ("lat", self.lat.to_json()),
+            ("lon", self.lon.to_json()),
+        ])
+    }
+}
+
+impl<'a> FromJson<'a> for City<'a> {
+    fn from_json(json: &'a Json) -> Result<Self, FromJsonError> {
+        Ok(City { name: Cow::Borrowed(json.field("name")?), lat: json.field("lat")?, lon: json.field("lon")? })
+    }
+}
+
+impl FromStr for City<'static> {
+    type Err = ParseCoordError;
+
+    // The coordinate may be written any way `Coord` accepts. It never holds
+    // a colon, so the last one ends the name.
+    fn from_str(s: &str) -> Result<City<'static>, ParseCoordError> {
+        let text = s.trim();
+        let (name, coord) = text
+            .rsplit_once(':')
+            .filter(|(name, _)| !name.trim().is_empty())
+            .ok_or_else(|| ParseCoordError::CityName(text.to_owned()))?;
+        Ok(City::new(name.trim().to_owned(), coord.parse()?))
+    }
+}
+
+// The city closest to `to`, with its distance in kilometres.
+pub fn nearest<'c, 'a>(cities: &'c [City<'a>], to: &Coord) -> Option<(&'c City<'a>, f64)> {
+    cities
+        .iter()
+        .map(|city| (city, city.coord().distance_to(to)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+// All of `cities` as a GeoJSON `FeatureCollection`, ready for a map viewer.
+pub fn feature_collection<'c, 'a: 'c>(cities: impl IntoIterator<Item = &'c City<'a>>) -> Json {
+    Json::object([
+        ("type", "FeatureCollection".to_json()),
+        ("features", Json::Array(cities.into_iter().map(City::to_geojson).collect())),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coord(s: &str) -> Coord {
+        s.parse().unwrap()
+    }
+
+    fn close(a: f64, b: f64, within: f64) -> bool {
+        (a - b).abs() < within
+    }
+
+    #[test]
+    fn parses_decimal_and_dms() {
+        let dublin = coord("53.347778, -6.259722");
+        assert_eq!(dublin, Coord { lat: 53.347778, lon: -6.259722 });
+
+        let dms = coord("53°20'52\"N 6°15'35\"W");
+        assert!(close(dms.lat, 53.347778, 1e-4) && close(dms.lon, -6.259722, 1e-4), "{:?}", dms);
+        let minutes = coord("53°20.87'N, 6°15.58'W");
+        assert!(close(minutes.lat, 53.347833, 1e-5) && close(minutes.lon, -6.259667, 1e-5), "{:?}", minutes);
+        assert_eq!(coord("53.5°N 6.25°W"), Coord { lat: 53.5, lon: -6.25 });
+        assert_eq!(coord("33°52′S 151°12′E"), coord("151°12′E 33°52′S"));
+        assert_eq!(coord("-33.5 151"), Coord { lat: -33.5, lon: 151.0 });
+    }
+
+    #[test]
+    fn rejects_bad_coordinates() {
+        let err = |s: &str| s.parse::<Coord>().unwrap_err();
+        assert_eq!(err("53.3"), ParseCoordError::Format("53.3".to_owned()));
+        assert_eq!(err("91, 0"), ParseCoordError::LatitudeOutOfRange(91.0));
+        assert_eq!(err("0, -180.5"), ParseCoordError::LongitudeOutOfRange(-180.5));
+        assert_eq!(err("53°61'N 6°W"), ParseCoordError::ComponentOutOfRange { name: "minutes", value: 61.0 });
+        assert_eq!(err("53°N 6°N"), ParseCoordError::Hemisphere("53°N 6°N".to_owned()));
+        assert_eq!(err("53.5°20'N, 6°W"), ParseCoordError::InvalidAngle("53.5°20'N".to_owned()));
+        assert_eq!(err("-53°N, 6°W"), ParseCoordError::InvalidAngle("-53°N".to_owned()));
+        assert_eq!(err("53°20'52\"N 6x"), ParseCoordError::InvalidAngle("6x".to_owned()));
+        assert_eq!(err("91, 0").to_string(), "latitude must be between -90 and 90 degrees, found 91");
+    }
+
+    #[test]
+    fn prints_decimal_and_dms() {
+        let dublin = Coord { lat: 53.347778, lon: -6.259722 };
+        assert_eq!(dublin.to_string(), "53.348°N 6.260°W");
+        assert_eq!(format!("{:.1}", dublin), "53.3°N 6.3°W");
+        assert_eq!(format!("{:#}", dublin), "53°20'52\"N 6°15'35\"W");
+        assert_eq!(format!("{:#.1}", dublin), "53°20'52.0\"N 6°15'35.0\"W");
+        assert_eq!(format!("{:#}", Coord { lat: -0.99999, lon: 0.0 }), "1°0'0\"S 0°0'0\"E");
+        let back = coord(&format!("{:#.3}", dublin));
+        assert!(close(back.lat, dublin.lat, 1e-6) && close(back.lon, dublin.lon, 1e-6), "{:?}", back);
+    }
+
+    #[test]
+    fn measures_distance_bearing_and_midpoint() {
+        let dublin = Coord { lat: 53.347778, lon: -6.259722 };
+        let oslo = Coord { lat: 59.95, lon: 10.75 };
+        assert!(close(dublin.distance_to(&oslo), 1262.0, 5.0), "{}", dublin.distance_to(&oslo));
+        assert_eq!(dublin.distance_to(&dublin), 0.0);
+
+        let north_pole = Coord { lat: 90.0, lon: 0.0 };
+        assert!(close(dublin.bearing_to(&north_pole), 0.0, 1e-9));
+        assert!(close(Coord { lat: 0.0, lon: 0.0 }.bearing_to(&Coord { lat: 0.0, lon: -10.0 }), 270.0, 1e-9));
+
+        let mid = Coord { lat: 0.0, lon: 170.0 }.midpoint(&Coord { lat: 0.0, lon: -170.0 });
+        assert!(close(mid.lat, 0.0, 1e-9) && close(mid.lon.abs(), 180.0, 1e-9), "{:?}", mid);
+        let mid = dublin.midpoint(&oslo);
+        assert!(close(mid.distance_to(&dublin), mid.distance_to(&oslo), 1e-6));
+    }
+
+    #[test]
+    fn parses_cities() {
+        let dublin: City = "Dublin: 53°20'52\"N 6°15'35\"W".parse().unwrap();
+        assert_eq!(dublin.name, "Dublin");
+        assert!(close(dublin.lat, 53.347778, 1e-4) && close(dublin.lon, -6.259722, 1e-4), "{:?}", dublin);
+
+        let tokyo = City::new("東京", Coord { lat: 35.689, lon: 139.692 });
+        let back: City = tokyo.to_string().parse().unwrap();
+        assert_eq!((back.name.as_ref(), back.lat, back.lon), ("東京", 35.689, 139.692));
+        assert_eq!(" Rio de Janeiro : -22.9, -43.2 ".parse(), Ok(City::new("Rio de Janeiro", coord("-22.9, -43.2"))));
+
+        let err = |s: &str| s.parse::<City>().unwrap_err();
+        assert_eq!(err("53.3, -6.2"), ParseCoordError::CityName("53.3, -6.2".to_owned()));
+        assert_eq!(err(": 53.3, -6.2"), ParseCoordError::CityName(": 53.3, -6.2".to_owned()));
+        assert_eq!(err("Nowhere: 95, 0"), ParseCoordError::LatitudeOutOfRange(95.0));
+    }
+
+    #[test]
+    fn finds_nearest_city_and_writes_geojson() {
+        let cities = [
+            City { name: "Dublin".into(), lat: 53.347778, lon: -6.259722 },
+            City { name: "Oslo".into(), lat: 59.95, lon: 10.75 },
+            City { name: "Vancouver".into(), lat: 49.25, lon: -123.1 },
+        ];
+        let london = coord("51°30'26\"N 0°7'39\"W");
+        let (city, km) = nearest(&cities, &london).unwrap();
+        assert_eq!(city.name, "Dublin");
+        assert!(close(km, 463.0, 5.0), "{}", km);
+        assert!(nearest(&[], &london).is_none());
+
+        assert_eq!(
+            feature_collection(&cities[1..2]).to_string(),
+            r#"{"type":"FeatureCollection","features":[{"type":"Feature","geometry":{"type":"Point","coordinates":[10.75,59.95]},"properties":{"name":"Oslo"}}]}"#
+        );
+    }
+}
@@ -0,0 +1,9 @@
+// The types from the `fmt_formatting` example, grown into something other
+// examples can use.
+
+pub mod color;
+pub mod geo;
+pub mod palette;
+
+pub use color::Color;
+pub use geo::{City, Coord};
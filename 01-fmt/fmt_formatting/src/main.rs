@@ -1,59 +1,121 @@
-use std::fmt::{self, Formatter, Display};
-
-struct City {
-    name: &'static str,
-    // Latitude
-    lat: f32,
-    // Longitude
-    lon: f32,
-}
+use fmt_formatting::color::{Color, Hsl};
+use fmt_formatting::geo::{self, City, Coord};
+use json::{FromJson, Json, ToJson};
+use table::{Align, Style, Table};
 
-impl Display for City {
-    // `f` is a buffer, and this method must write the formatted string into it.
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let lat_c = if self.lat >= 0.0 { 'N' } else { 'S' };
-        let lon_c = if self.lon >= 0.0 { 'E' } else { 'W' };
+fn main() {
+    let cities = [
+        City { name: "Dublin".into(), lat: 53.347778, lon: -6.259722 },
+        City { name: "Oslo".into(), lat: 59.95, lon: 10.75 },
+        City { name: "東京".into(), lat: 35.689, lon: 139.692 },
+        City { name: "Vancouver".into(), lat: 49.25, lon: -123.1 },
+    ];
+    let colors = [
+        Color::rgb(128, 255, 90),
+        Color::rgb(0, 3, 254),
+        Color::rgb(0, 0, 0),
+    ];
 
-        // `write!` is like `format!`, but it will write the formatted string
-        // into a buffer (the first argument).
-        write!(f, "{}: {:.3}°{} {:.3}°{}",
-               self.name, self.lat.abs(), lat_c, self.lon.abs(), lon_c)
+    for city in &cities {
+        println!("{}", city);
+    }
+    for color in &colors {
+        // Switch this to use {} once you've added an implementation
+        // for fmt::Display.
+        println!("{}", color);
     }
-}
 
-#[derive(Debug)]
-struct Color {
-    red: u8,
-    green: u8,
-    blue: u8,
-}
+    // The same data as tables. `Table` measures columns the way a terminal
+    // draws them, so the borders line up even for names like "東京".
+    let mut city_table = Table::new(["City", "Lat", "Lon"]);
+    city_table.align(1, Align::Right).align(2, Align::Right);
+    for city in &cities {
+        let lat = format!("{:.3}°{}", city.lat.abs(), if city.lat >= 0.0 { 'N' } else { 'S' });
+        let lon = format!("{:.3}°{}", city.lon.abs(), if city.lon >= 0.0 { 'E' } else { 'W' });
+        city_table.row([&*city.name, &lat, &lon]);
+    }
+    println!("{}", city_table);
 
-impl Display for Color {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(
-            f,
-            "RGB ({}, {}, {}) 0x{:06X}",
-            self.red, self.green, self.blue,
-            (self.red as u32) * 65536 + (self.green as u32) * 256 + (self.blue as u32)
-        )
+    let mut color_table = Table::new(["Red", "Green", "Blue", "Hex", "HSL"]);
+    color_table.style(Style::BOX);
+    for column in 0..3 {
+        color_table.align(column, Align::Right);
     }
-}
+    for color in &colors {
+        color_table.row([
+            color.red.to_string(),
+            color.green.to_string(),
+            color.blue.to_string(),
+            format!("{:#X}", color),
+            color.to_hsl().to_string(),
+        ]);
+    }
+    println!("{}", color_table);
 
-fn main() {
-    for city in [
-        City { name: "Dublin", lat: 53.347778, lon: -6.259722 },
-        City { name: "Oslo", lat: 59.95, lon: 10.75 },
-        City { name: "Vancouver", lat: 49.25, lon: -123.1 },
-    ] {
-        println!("{}", city);
+    // Coordinates can be read in degrees, minutes and seconds too, and the
+    // distances between cities worked out.
+    let dublin = cities[0].coord();
+    let london: Coord = "51°30'26\"N 0°7'39\"W".parse().unwrap();
+    println!("London is at {} or {:#}", london, london);
+    for city in &cities[1..] {
+        let there = city.coord();
+        println!(
+            "Dublin to {}: {:.0} km, setting off at {:.0}°, halfway at {:.2}",
+            city.name,
+            dublin.distance_to(&there),
+            dublin.bearing_to(&there),
+            dublin.midpoint(&there)
+        );
     }
-    for color in [
-        Color { red: 128, green: 255, blue: 90 },
-        Color { red: 0, green: 3, blue: 254 },
-        Color { red: 0, green: 0, blue: 0 },
-    ] {
-        // Switch this to use {} once you've added an implementation
-        // for fmt::Display.
-        println!("{}", color);
+    if let Some((city, km)) = geo::nearest(&cities, &london) {
+        println!("The nearest city to London is {} ({:.0} km)", city.name, km);
+    }
+    match "53°61'N 6°W".parse::<Coord>() {
+        Ok(coord) => println!("parsed {}", coord),
+        Err(e) => println!("error: {}", e),
+    }
+    let reykjavik: City = "Reykjavík: 64°8'N 21°56'W".parse().unwrap();
+    println!("{} is {:.0} km from Dublin", reykjavik, reykjavik.distance_to(&cities[0]));
+    println!("As GeoJSON: {}", geo::feature_collection(&cities[..2]));
+
+    // Colors can be parsed from CSS-style text and moved around in HSL.
+    for text in ["#80FF5A", "rgba(0, 3, 254, 0.5)", "rebeccapurple", "#80FF5"] {
+        match text.parse::<Color>() {
+            Ok(color) => println!("{:>20} is {}, {}", text, color, color.to_hsv()),
+            Err(e) => println!("{:>20}: {}", text, e),
+        }
+    }
+    let base = colors[0];
+    println!(
+        "{:#x} lightened: {:#x}, darkened: {:#x}, halfway to navy: {:#x}, hue turned: {:#x}",
+        base,
+        base.lighten(0.2),
+        base.darken(0.2),
+        base.blend("navy".parse().unwrap(), 0.5),
+        Color::from_hsl(Hsl { h: base.to_hsl().h + 180.0, ..base.to_hsl() })
+    );
+
+    // Cities read from JSON borrow their names from the document.
+    let document = Json::parse(r#"[{"name": "Dublin", "lat": 53.347778, "lon": -6.259722}]"#).unwrap();
+    for city in Vec::<City>::from_json(&document).unwrap() {
+        println!("{} as JSON is {}", city, city.to_json());
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let oslo = City { name: "Oslo".into(), lat: 59.95, lon: 10.75 };
+        let text = json::to_string(&oslo);
+        assert_eq!(text, r#"{"name":"Oslo","lat":59.95,"lon":10.75}"#);
+        let document = Json::parse(&text).unwrap();
+        assert_eq!(City::from_json(&document), Ok(oslo));
+
+        let color = Color::rgb(128, 255, 90);
+        assert_eq!(json::from_str::<Color>(&json::to_string(&color)), Ok(color));
+        assert!(json::from_str::<Color>(r#"{"red": 256, "green": 0, "blue": 0}"#).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+// Accessibility checks and palettes for `Color`.
+//
+// Contrast follows WCAG 2: the relative luminance of each color, then
+// `(lighter + 0.05) / (darker + 0.05)`, which runs from 1:1 (the same color)
+// to 21:1 (black on white). Text needs 4.5:1 to pass level AA and 7:1 for
+// AAA; large text (18pt, or 14pt bold) gets by with 3:1 and 4.5:1.
+//
+// Alpha is ignored throughout: a translucent color is checked as if it were
+// opaque.
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::color::{Color, Hsl};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Aa,
+    Aaa,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextSize {
+    Normal,
+    Large,
+}
+
+// A contrast ratio, `ratio` to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contrast {
+    pub ratio: f64,
+}
+
+impl Contrast {
+    // The smallest ratio that passes.
+    pub fn required(level: Level, size: TextSize) -> f64 {
+        match (level, size) {
+            (Level::Aa, TextSize::Large) => 3.0,
+            (Level::Aa, TextSize::Normal) | (Level::Aaa, TextSize::Large) => 4.5,
+            (Level::Aaa, TextSize::Normal) => 7.0,
+        }
+    }
+
+    pub fn passes(&self, level: Level, size: TextSize) -> bool {
+        self.ratio >= Contrast::required(level, size)
+    }
+
+    // The best verdict for normal text, falling back to large text:
+    // `AAA`, `AA`, `AA large` or `fail`.
+    pub fn verdict(&self) -> &'static str {
+        if self.passes(Level::Aaa, TextSize::Normal) {
+            "AAA"
+        } else if self.passes(Level::Aa, TextSize::Normal) {
+            "AA"
+        } else if self.passes(Level::Aa, TextSize::Large) {
+            "AA large"
+        } else {
+            "fail"
+        }
+    }
+}
+
+// `4.52:1 AA`, with the precision (2 by default) applied to the ratio.
+impl Display for Contrast {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:.*}:1 {}", f.precision().unwrap_or(2), self.ratio, self.verdict())
+    }
+}
+
+impl Color {
+    // How bright the color looks, from 0 for black to 1 for white: the
+    // channels are converted from sRGB to linear light and weighted by how
+    // sensitive the eye is to each.
+    pub fn luminance(self) -> f64 {
+        let linear = |channel: u8| {
+            let c = channel as f64 / 255.0;
+            if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+        };
+        0.2126 * linear(self.red) + 0.7152 * linear(self.green) + 0.0722 * linear(self.blue)
+    }
+
+    // The same whichever way round the two colors are.
+    pub fn contrast(self, other: Color) -> Contrast {
+        let (a, b) = (self.luminance(), other.luminance());
+        let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+        Contrast { ratio: (lighter + 0.05) / (darker + 0.05) }
+    }
+
+    // Black or white, whichever is easier to read on this color.
+    pub fn text_color(self) -> Color {
+        let black = Color::rgb(0, 0, 0);
+        let white = Color::rgb(255, 255, 255);
+        if self.contrast(black).ratio >= self.contrast(white).ratio { black } else { white }
+    }
+
+    // The hue turned by `degrees`, keeping saturation, lightness and alpha.
+    pub fn rotate_hue(self, degrees: f64) -> Color {
+        let hsl = self.to_hsl();
+        Color::from_hsl(Hsl { h: hsl.h + degrees, ..hsl }).with_alpha(self.alpha)
+    }
+
+    // The color opposite on the color wheel.
+    pub fn complementary(self) -> Color {
+        self.rotate_hue(180.0)
+    }
+
+    // The color and the two a third of the way round the wheel from it.
+    pub fn triadic(self) -> [Color; 3] {
+        [self, self.rotate_hue(120.0), self.rotate_hue(240.0)]
+    }
+}
+
+// `steps` colors evenly spaced from `from` to `to`, both included. One step
+// is just `from`.
+pub fn gradient(from: Color, to: Color, steps: usize) -> Vec<Color> {
+    match steps {
+        0 => Vec::new(),
+        1 => vec![from],
+        _ => (0..steps).map(|i| from.blend(to, i as f64 / (steps - 1) as f64)).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Color = Color::rgb(0, 0, 0);
+    const WHITE: Color = Color::rgb(255, 255, 255);
+
+    #[test]
+    fn computes_luminance_and_contrast() {
+        assert_eq!(BLACK.luminance(), 0.0);
+        assert_eq!(WHITE.luminance(), 1.0);
+        assert_eq!(BLACK.contrast(WHITE).ratio, 21.0);
+        assert_eq!(WHITE.contrast(WHITE).ratio, 1.0);
+
+        // #767676 on white is the classic "just passes AA" gray.
+        let gray = Color::rgb(0x76, 0x76, 0x76);
+        let contrast = gray.contrast(WHITE);
+        assert_eq!(contrast.to_string(), "4.54:1 AA");
+        assert_eq!(WHITE.contrast(gray), contrast);
+        assert!(contrast.passes(Level::Aa, TextSize::Normal));
+        assert!(contrast.passes(Level::Aaa, TextSize::Large));
+        assert!(!contrast.passes(Level::Aaa, TextSize::Normal));
+    }
+
+    #[test]
+    fn gives_verdicts() {
+        assert_eq!(BLACK.contrast(WHITE).verdict(), "AAA");
+        assert_eq!(Color::rgb(0x77, 0x77, 0x77).contrast(WHITE).verdict(), "AA large");
+        assert_eq!(Color::rgb(0xaa, 0xaa, 0xaa).contrast(WHITE).verdict(), "fail");
+        assert_eq!(format!("{:.1}", BLACK.contrast(WHITE)), "21.0:1 AAA");
+        assert_eq!(Color::rgb(255, 255, 0).text_color(), BLACK);
+        assert_eq!(Color::rgb(0, 0, 128).text_color(), WHITE);
+    }
+
+    #[test]
+    fn generates_palettes() {
+        let red = Color::rgb(255, 0, 0);
+        assert_eq!(red.complementary(), Color::rgb(0, 255, 255));
+        assert_eq!(red.triadic(), [red, Color::rgb(0, 255, 0), Color::rgb(0, 0, 255)]);
+        assert_eq!(red.with_alpha(9).complementary().alpha, 9);
+
+        assert_eq!(gradient(BLACK, WHITE, 3), [BLACK, Color::rgb(128, 128, 128), WHITE]);
+        assert_eq!(gradient(BLACK, WHITE, 1), [BLACK]);
+        assert!(gradient(BLACK, WHITE, 0).is_empty());
+        let steps = gradient(red, Color::rgb(0, 0, 255), 6);
+        assert_eq!((steps[0], steps[5]), (red, Color::rgb(0, 0, 255)));
+        assert_eq!(steps[1], Color::rgb(204, 0, 51));
+    }
+}
@@ -7,6 +7,9 @@
 
 use std::fmt;
 
+use json::{FromJson, FromJsonError, Json, ToJson};
+use number_format::NumberFormat;
+
 struct Structure(i32);
 
 impl fmt::Display for Structure {
@@ -15,6 +18,19 @@ impl fmt::Display for Structure {
     }
 }
 
+// As JSON, `Structure(3)` is just `3`.
+impl ToJson for Structure {
+    fn to_json(&self) -> Json {
+        self.0.to_json()
+    }
+}
+
+impl FromJson<'_> for Structure {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        i32::from_json(json).map(Structure)
+    }
+}
+
 
 fn main() {
     // In general, the `{}` will be automatically replaced with any
@@ -39,6 +55,13 @@ fn main() {
     println!("Base 8 (octal):        {:o}", 69420); // 207454
     println!("Base 16 (hexadecimal): {:x}", 69420); // 10f2c
 
+    // Or written for people, with the `number_format` wrappers.
+    println!("Grouped:               {}", 69420.grouped()); // 69,420
+    println!("SI prefix:             {}", 69420.si()); // 69.4 k
+    println!("As a size:             {}", 69420.bytes()); // 67.8 KiB
+    println!("Engineering:           {}", 69420.eng()); // 69.4e3
+    println!("2 significant digits:  {}", 69420.sig(2)); // 69000
+
     // You can right-justify text with a specified width. This will
     // output "    1". (Four white spaces and a "1", for a total width of 5.)
     println!("{number:>5}", number=1);
@@ -59,6 +82,10 @@ fn main() {
     println!("This struct `{}` won't print... until you fix it", Structure(3));
     // TODO ^ Try uncommenting this line (done)
 
+    // It reads back from JSON too.
+    let structure: Structure = json::from_str(&json::to_string(&Structure(3))).unwrap();
+    println!("Structure from JSON: {}", structure);
+
     // For Rust 1.58 and above, you can directly capture the argument from a
     // surrounding variable. Just like the above, this will output
     // "    1", 4 white spaces and a "1".
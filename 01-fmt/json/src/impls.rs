@@ -0,0 +1,235 @@
+// `ToJson` / `FromJson` for the standard types the examples are built from.
+
+use crate::{FromJson, FromJsonError, Json, ToJson};
+
+impl ToJson for Json {
+    fn to_json(&self) -> Json {
+        self.clone()
+    }
+}
+
+impl<'a> FromJson<'a> for Json {
+    fn from_json(json: &'a Json) -> Result<Self, FromJsonError> {
+        Ok(json.clone())
+    }
+}
+
+impl ToJson for bool {
+    fn to_json(&self) -> Json {
+        Json::Bool(*self)
+    }
+}
+
+impl FromJson<'_> for bool {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        match json {
+            Json::Bool(b) => Ok(*b),
+            other => Err(FromJsonError::expected("a boolean", other)),
+        }
+    }
+}
+
+// Every integer goes through `i128`, which holds all of them exactly, with a
+// range check on the way back.
+macro_rules! integer {
+    ($($ty:ty),*) => {
+        $(
+            impl ToJson for $ty {
+                fn to_json(&self) -> Json {
+                    Json::Int(*self as i128)
+                }
+            }
+
+            impl FromJson<'_> for $ty {
+                fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+                    match json {
+                        Json::Int(n) => <$ty>::try_from(*n).map_err(|_| {
+                            FromJsonError::new(format!("{} does not fit in {}", n, stringify!($ty)))
+                        }),
+                        // `MAX + 1` is a power of two: exact for the small types,
+                        // and what `MAX as f64` already rounds to for 64 bits.
+                        // Either way the upper bound is strict.
+                        Json::Float(x)
+                            if x.fract() == 0.0 && *x >= <$ty>::MIN as f64 && *x < <$ty>::MAX as f64 + 1.0 =>
+                        {
+                            Ok(*x as $ty)
+                        }
+                        Json::Float(x) => {
+                            Err(FromJsonError::new(format!("{} is not a valid {}", x, stringify!($ty))))
+                        }
+                        other => Err(FromJsonError::expected("an integer", other)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl ToJson for f64 {
+    fn to_json(&self) -> Json {
+        Json::Float(*self)
+    }
+}
+
+impl FromJson<'_> for f64 {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        match json {
+            Json::Int(n) => Ok(*n as f64),
+            Json::Float(x) => Ok(*x),
+            other => Err(FromJsonError::expected("a number", other)),
+        }
+    }
+}
+
+impl ToJson for f32 {
+    fn to_json(&self) -> Json {
+        // Going through the shortest `f32` text keeps `0.1f32` as `0.1`
+        // instead of `0.10000000149011612`.
+        Json::Float(self.to_string().parse().unwrap_or(f64::NAN))
+    }
+}
+
+impl FromJson<'_> for f32 {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        f64::from_json(json).map(|x| x as f32)
+    }
+}
+
+impl ToJson for char {
+    fn to_json(&self) -> Json {
+        Json::String(self.to_string())
+    }
+}
+
+impl FromJson<'_> for char {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        let s = <&str>::from_json(json)?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(FromJsonError::new(format!("expected a single character, found {:?}", s))),
+        }
+    }
+}
+
+impl ToJson for str {
+    fn to_json(&self) -> Json {
+        Json::String(self.to_owned())
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> Json {
+        Json::String(self.clone())
+    }
+}
+
+impl<'a> FromJson<'a> for &'a str {
+    fn from_json(json: &'a Json) -> Result<Self, FromJsonError> {
+        match json {
+            Json::String(s) => Ok(s),
+            other => Err(FromJsonError::expected("a string", other)),
+        }
+    }
+}
+
+impl FromJson<'_> for String {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        <&str>::from_json(json).map(str::to_owned)
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn to_json(&self) -> Json {
+        (**self).to_json()
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for Box<T> {
+    fn to_json(&self) -> Json {
+        (**self).to_json()
+    }
+}
+
+impl<'a, T: FromJson<'a>> FromJson<'a> for Box<T> {
+    fn from_json(json: &'a Json) -> Result<Self, FromJsonError> {
+        T::from_json(json).map(Box::new)
+    }
+}
+
+// `None` is `null`.
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> Json {
+        match self {
+            Some(value) => value.to_json(),
+            None => Json::Null,
+        }
+    }
+}
+
+impl<'a, T: FromJson<'a>> FromJson<'a> for Option<T> {
+    fn from_json(json: &'a Json) -> Result<Self, FromJsonError> {
+        match json {
+            Json::Null => Ok(None),
+            other => T::from_json(other).map(Some),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for [T] {
+    fn to_json(&self) -> Json {
+        Json::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
+impl<T: ToJson, const N: usize> ToJson for [T; N] {
+    fn to_json(&self) -> Json {
+        self[..].to_json()
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> Json {
+        self[..].to_json()
+    }
+}
+
+impl<'a, T: FromJson<'a>> FromJson<'a> for Vec<T> {
+    fn from_json(json: &'a Json) -> Result<Self, FromJsonError> {
+        match json {
+            Json::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| T::from_json(item).map_err(|e| e.at(&format!("[{}]", i))))
+                .collect(),
+            other => Err(FromJsonError::expected("an array", other)),
+        }
+    }
+}
+
+// Tuples are fixed-length arrays.
+macro_rules! tuple {
+    ($len:literal: $($name:ident $index:tt),+) => {
+        impl<$($name: ToJson),+> ToJson for ($($name,)+) {
+            fn to_json(&self) -> Json {
+                Json::Array(vec![$(self.$index.to_json()),+])
+            }
+        }
+
+        impl<'a, $($name: FromJson<'a>),+> FromJson<'a> for ($($name,)+) {
+            fn from_json(json: &'a Json) -> Result<Self, FromJsonError> {
+                let items = json.tuple($len)?;
+                Ok(($(
+                    $name::from_json(&items[$index]).map_err(|e| e.at(concat!("[", $index, "]")))?,
+                )+))
+            }
+        }
+    };
+}
+
+tuple!(1: A 0);
+tuple!(2: A 0, B 1);
+tuple!(3: A 0, B 1, C 2);
+tuple!(4: A 0, B 1, C 2, D 3);
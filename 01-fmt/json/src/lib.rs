@@ -0,0 +1,302 @@
+// A small, dependency-free JSON library.
+//
+// `Json` is the document tree. It prints itself through `Display`: `{}` is
+// compact and `{:#}` is pretty, the same way `{:?}` and `{:#?}` work for
+// Debug. `Json::parse` reads a document back and reports where it went wrong.
+//
+// Types opt in with the `ToJson` / `FromJson` pair:
+//
+//     impl ToJson for Point2D {
+//         fn to_json(&self) -> Json {
+//             Json::object([("x", self.x.to_json()), ("y", self.y.to_json())])
+//         }
+//     }
+//
+//     impl FromJson<'_> for Point2D {
+//         fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+//             Ok(Point2D { x: json.field("x")?, y: json.field("y")? })
+//         }
+//     }
+//
+// `FromJson` has a lifetime so that borrowed fields like `name: &'a str` can
+// point into the parsed document instead of being copied.
+//
+// Enums follow the usual convention: a unit variant is a string
+// (`"PageLoad"`), any other variant is a one-entry object keyed by the
+// variant name (`{"KeyPress": "x"}`, `{"Click": {"x": 20, "y": 80}}`).
+
+mod impls;
+mod parse;
+mod write;
+
+use std::error::Error;
+use std::fmt;
+
+pub use parse::ParseError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    // Integers are kept apart from floats so that every Rust integer up to
+    // `u64` and `i64` survives a round trip exactly.
+    Int(i128),
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    // Keys stay in insertion order, so output is stable and readable.
+    Object(Vec<(String, Json)>),
+}
+
+pub trait ToJson {
+    fn to_json(&self) -> Json;
+}
+
+pub trait FromJson<'a>: Sized {
+    fn from_json(json: &'a Json) -> Result<Self, FromJsonError>;
+}
+
+// Why a document could not be turned into a value. `path` says where in the
+// document, e.g. `.top_left.x`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FromJsonError {
+    pub path: String,
+    pub message: String,
+}
+
+impl FromJsonError {
+    pub fn new(message: impl Into<String>) -> FromJsonError {
+        FromJsonError { path: String::new(), message: message.into() }
+    }
+
+    // "expected a number, found a string"
+    pub fn expected(what: &str, found: &Json) -> FromJsonError {
+        FromJsonError::new(format!("expected {}, found {}", what, found.kind()))
+    }
+
+    // Prefix the path with the field or index the error came from.
+    pub fn at(mut self, segment: &str) -> FromJsonError {
+        self.path.insert_str(0, segment);
+        self
+    }
+}
+
+impl fmt::Display for FromJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{} at `{}`", self.message, self.path)
+        }
+    }
+}
+
+impl Error for FromJsonError {}
+
+// Anything that can go wrong in `from_str`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonError {
+    Parse(ParseError),
+    Convert(FromJsonError),
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonError::Parse(e) => write!(f, "{}", e),
+            JsonError::Convert(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for JsonError {}
+
+impl From<ParseError> for JsonError {
+    fn from(e: ParseError) -> JsonError {
+        JsonError::Parse(e)
+    }
+}
+
+impl From<FromJsonError> for JsonError {
+    fn from(e: FromJsonError) -> JsonError {
+        JsonError::Convert(e)
+    }
+}
+
+impl Json {
+    pub fn parse(text: &str) -> Result<Json, ParseError> {
+        parse::parse(text)
+    }
+
+    // Build an object from `(key, value)` pairs.
+    pub fn object<'k>(entries: impl IntoIterator<Item = (&'k str, Json)>) -> Json {
+        Json::Object(entries.into_iter().map(|(k, v)| (k.to_owned(), v)).collect())
+    }
+
+    // The name of the JSON type, for error messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Json::Null => "null",
+            Json::Bool(_) => "a boolean",
+            Json::Int(_) | Json::Float(_) => "a number",
+            Json::String(_) => "a string",
+            Json::Array(_) => "an array",
+            Json::Object(_) => "an object",
+        }
+    }
+
+    // Look up a key in an object. `None` for missing keys and non-objects.
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    // Convert the value of a required field, adding the field to the path of
+    // any error.
+    pub fn field<'a, T: FromJson<'a>>(&'a self, key: &str) -> Result<T, FromJsonError> {
+        if !matches!(self, Json::Object(_)) {
+            return Err(FromJsonError::expected("an object", self));
+        }
+        let segment = format!(".{}", key);
+        match self.get(key) {
+            Some(value) => T::from_json(value).map_err(|e| e.at(&segment)),
+            None => Err(FromJsonError::new(format!("missing field `{}`", key))),
+        }
+    }
+
+    // The elements of an array that must have exactly `len` of them, as used
+    // for tuple structs.
+    pub fn tuple(&self, len: usize) -> Result<&[Json], FromJsonError> {
+        match self {
+            Json::Array(items) if items.len() == len => Ok(items),
+            Json::Array(items) => Err(FromJsonError::new(format!(
+                "expected an array of {} elements, found {}",
+                len,
+                items.len()
+            ))),
+            other => Err(FromJsonError::expected("an array", other)),
+        }
+    }
+
+    // Split an enum value into its variant name and payload: `"PageLoad"`
+    // gives `("PageLoad", None)`, `{"KeyPress": "x"}` gives
+    // `("KeyPress", Some("x"))`.
+    pub fn variant(&self) -> Result<(&str, Option<&Json>), FromJsonError> {
+        match self {
+            Json::String(name) => Ok((name, None)),
+            Json::Object(entries) if entries.len() == 1 => Ok((&entries[0].0, Some(&entries[0].1))),
+            other => Err(FromJsonError::expected("a variant name or a single-key object", other)),
+        }
+    }
+
+    // Build the value of an enum variant with a payload.
+    pub fn variant_of(name: &str, payload: Json) -> Json {
+        Json::Object(vec![(name.to_owned(), payload)])
+    }
+}
+
+// Serialize compactly, the counterpart of `Json::to_string`.
+pub fn to_string<T: ToJson + ?Sized>(value: &T) -> String {
+    value.to_json().to_string()
+}
+
+// Serialize with two-space indentation.
+pub fn to_string_pretty<T: ToJson + ?Sized>(value: &T) -> String {
+    format!("{:#}", value.to_json())
+}
+
+// Parse and convert in one step. Only for types that own their data; a type
+// that borrows from the document (`Person<'a>`) needs the document to outlive
+// it, so parse with `Json::parse` and call `from_json` on the result.
+pub fn from_str<T>(text: &str) -> Result<T, JsonError>
+where
+    T: for<'a> FromJson<'a>,
+{
+    let json = Json::parse(text)?;
+    Ok(T::from_json(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_compact_and_pretty() {
+        let doc = Json::object([
+            ("name", "Dub\"lin\n\u{1}".to_json()),
+            ("coords", vec![53.5f64, -6.25].to_json()),
+            ("empty", Json::Array(vec![])),
+            ("nested", Json::object([("ok", true.to_json()), ("none", Json::Null)])),
+        ]);
+        assert_eq!(
+            doc.to_string(),
+            r#"{"name":"Dub\"lin\n\u0001","coords":[53.5,-6.25],"empty":[],"nested":{"ok":true,"none":null}}"#
+        );
+        assert_eq!(
+            format!("{:#}", doc),
+            "{\n  \"name\": \"Dub\\\"lin\\n\\u0001\",\n  \"coords\": [\n    53.5,\n    -6.25\n  ],\n  \"empty\": [],\n  \"nested\": {\n    \"ok\": true,\n    \"none\": null\n  }\n}"
+        );
+        assert_eq!(Json::parse(&format!("{:#}", doc)), Ok(doc));
+        assert_eq!(Json::Float(f64::NAN).to_string(), "null");
+        assert_eq!(Json::Float(1.0).to_string(), "1.0");
+    }
+
+    #[test]
+    fn parses_numbers_strings_and_escapes() {
+        assert_eq!(Json::parse("-12"), Ok(Json::Int(-12)));
+        assert_eq!(Json::parse("1.5e3"), Ok(Json::Float(1500.0)));
+        assert_eq!(Json::parse("18446744073709551615"), Ok(Json::Int(u64::MAX.into())));
+        assert_eq!(Json::parse("200000000000000000000000000000000000000"), Ok(Json::Float(2e38)));
+        assert_eq!(
+            Json::parse(r#" "a\u00e9\ud83e\udd80\/\t" "#),
+            Ok(Json::String("aé🦀/\t".to_owned()))
+        );
+    }
+
+    #[test]
+    fn reports_error_positions() {
+        let err = Json::parse("{\n  \"a\": 1,\n  \"b\": tru\n}").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 8));
+        assert_eq!(err.to_string(), "expected `true` at line 3, column 8");
+
+        for bad in ["", "[1,]", "{\"a\" 1}", "01", "1.", "\"\\x\"", "\"\u{1}\"", "[1] 2", "\"\\ud800\""] {
+            assert!(Json::parse(bad).is_err(), "{:?} should not parse", bad);
+        }
+    }
+
+    #[test]
+    fn converts_std_types_with_paths_in_errors() {
+        let json = Json::parse(r#"{"xs": [1, 2, 300], "name": "x", "c": "é"}"#).unwrap();
+        assert_eq!(json.field::<Vec<u16>>("xs"), Ok(vec![1, 2, 300]));
+        assert_eq!(json.field::<&str>("name"), Ok("x"));
+        assert_eq!(json.field::<char>("c"), Ok('é'));
+        assert_eq!(json.field::<Option<i32>>("missing").unwrap_err().message, "missing field `missing`");
+        let err = json.field::<Vec<u8>>("xs").unwrap_err();
+        assert_eq!(err.to_string(), "300 does not fit in u8 at `.xs[2]`");
+        assert_eq!(from_str::<(i32, String)>(r#"[1, "a"]"#).unwrap(), (1, "a".to_owned()));
+    }
+
+    #[test]
+    fn integers_round_trip_exactly_at_the_edges() {
+        for n in [u64::MAX, u64::MAX - 1, i64::MAX as u64 + 1] {
+            assert_eq!(from_str::<u64>(&to_string(&n)), Ok(n));
+        }
+        assert_eq!(to_string(&u64::MAX), "18446744073709551615");
+        assert_eq!(from_str::<i64>(&to_string(&i64::MIN)), Ok(i64::MIN));
+        assert_eq!(from_str::<usize>(&to_string(&usize::MAX)), Ok(usize::MAX));
+
+        assert!(from_str::<u64>("18446744073709551616").is_err());
+        assert!(from_str::<i64>("9223372036854775808").is_err());
+        assert!(from_str::<i64>("-9223372036854775809").is_err());
+        // Floats are checked against 2^64 itself, which `u64::MAX as f64`
+        // would round up to.
+        assert!(from_str::<u64>("1.8446744073709552e19").is_err());
+        assert_eq!(from_str::<u64>("1.8446744073709550e19"), Ok(18446744073709549568));
+        assert!(from_str::<i64>("9.223372036854775808e18").is_err());
+        assert_eq!(from_str::<i64>("-9.223372036854775808e18"), Ok(i64::MIN));
+        assert!(from_str::<u8>("256.0").is_err());
+        assert_eq!(from_str::<u8>("255.0"), Ok(255));
+    }
+}
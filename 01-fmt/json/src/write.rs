@@ -0,0 +1,93 @@
+use std::fmt::{self, Write};
+
+use crate::Json;
+
+// `{}` prints compact JSON, `{:#}` prints it indented by two spaces.
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pretty = f.alternate();
+        write_value(f, self, pretty, 0)
+    }
+}
+
+fn newline(f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+    f.write_char('\n')?;
+    for _ in 0..depth {
+        f.write_str("  ")?;
+    }
+    Ok(())
+}
+
+fn write_value(f: &mut fmt::Formatter, json: &Json, pretty: bool, depth: usize) -> fmt::Result {
+    match json {
+        Json::Null => f.write_str("null"),
+        Json::Bool(b) => write!(f, "{}", b),
+        Json::Int(n) => write!(f, "{}", n),
+        // JSON has no NaN or infinity; like most encoders, write `null`.
+        Json::Float(x) if !x.is_finite() => f.write_str("null"),
+        // `{:?}` always keeps a `.0` or an exponent, so the number reads back
+        // as a float, and its output is valid JSON number syntax.
+        Json::Float(x) => write!(f, "{:?}", x),
+        Json::String(s) => write_string(f, s),
+        Json::Array(items) => {
+            if items.is_empty() {
+                return f.write_str("[]");
+            }
+            f.write_char('[')?;
+            for (i, item) in items.iter().enumerate() {
+                if i != 0 {
+                    f.write_char(',')?;
+                }
+                if pretty {
+                    newline(f, depth + 1)?;
+                }
+                write_value(f, item, pretty, depth + 1)?;
+            }
+            if pretty {
+                newline(f, depth)?;
+            }
+            f.write_char(']')
+        }
+        Json::Object(entries) => {
+            if entries.is_empty() {
+                return f.write_str("{}");
+            }
+            f.write_char('{')?;
+            for (i, (key, value)) in entries.iter().enumerate() {
+                if i != 0 {
+                    f.write_char(',')?;
+                }
+                if pretty {
+                    newline(f, depth + 1)?;
+                }
+                write_string(f, key)?;
+                f.write_str(if pretty { ": " } else { ":" })?;
+                write_value(f, value, pretty, depth + 1)?;
+            }
+            if pretty {
+                newline(f, depth)?;
+            }
+            f.write_char('}')
+        }
+    }
+}
+
+// RFC 8259 section 7: quote, backslash and control characters must be
+// escaped; everything else may be written as is.
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '\u{8}' => f.write_str("\\b")?,
+            '\u{c}' => f.write_str("\\f")?,
+            c if c < ' ' => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
@@ -1,3 +1,5 @@
+use decimal::{Decimal, Rounding};
+
 fn main() {
 
     // https://doc.rust-lang.org/std/fmt/
@@ -26,4 +28,22 @@ fn main() {
     //                          in arg "prec" (5)}
     println!("Hello {} is {number:.prec$}", "x", prec = 5, number = 0.01);
 
+    // Floats are binary, so 2.675 is stored as 2.67499999999999982236...
+    // and rounds down. A `Decimal` keeps the digits it was written with.
+    println!("2.675 as f64 to 2 places:     {:.2}", 2.675);
+    let price: Decimal = "2.675".parse().unwrap();
+    println!("2.675 as Decimal to 2 places: {:.2}", price);
+    println!("Hello x is {:.5}", Decimal::new(1, 2));
+
+    // Other ways to round than the half-to-even of `{:.2}`.
+    for mode in [Rounding::HalfEven, Rounding::HalfUp, Rounding::Floor, Rounding::Ceiling, Rounding::Truncate] {
+        println!("{:>8}: {:>6} {:>6}", format!("{:?}", mode), price.round(2, mode), (-&price).round(2, mode));
+    }
+
+    // 0.1 + 0.2 is exactly 0.3, and division is done to the scale asked for.
+    let sum = Decimal::new(1, 1) + Decimal::new(2, 1);
+    println!("0.1 + 0.2 = {} (f64 says {})", sum, 0.1 + 0.2);
+    let third = Decimal::from(1).checked_div(&Decimal::from(3), 10, Rounding::HalfEven).unwrap();
+    println!("1 / 3 to 10 places = {}", third);
+
 }
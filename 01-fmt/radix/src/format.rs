@@ -0,0 +1,111 @@
+use crate::{check_base, mask, prefix_for, to_twos_complement, RadixError};
+
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+// How to render an integer: the base plus the optional decorations. Built
+// with the usual chain of setters:
+//
+//     RadixFormat::new(2)?.prefix(true).group(4).min_digits(8)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RadixFormat {
+    base: u32,
+    prefix: bool,
+    group: Option<usize>,
+    uppercase: bool,
+    min_digits: usize,
+}
+
+impl RadixFormat {
+    pub fn new(base: u32) -> Result<RadixFormat, RadixError> {
+        Ok(RadixFormat { base: check_base(base)?, prefix: false, group: None, uppercase: false, min_digits: 0 })
+    }
+
+    pub fn base(&self) -> u32 {
+        self.base
+    }
+
+    // Write `0x`, `0o` or `0b` in front of the digits. Other bases have no
+    // prefix, so this has no effect on them.
+    pub fn prefix(self, prefix: bool) -> RadixFormat {
+        RadixFormat { prefix, ..self }
+    }
+
+    // Separate every `size` digits with `_`, counting from the right.
+    // `0` turns grouping off.
+    pub fn group(self, size: usize) -> RadixFormat {
+        RadixFormat { group: if size == 0 { None } else { Some(size) }, ..self }
+    }
+
+    pub fn uppercase(self, uppercase: bool) -> RadixFormat {
+        RadixFormat { uppercase, ..self }
+    }
+
+    // Pad with leading zeros to at least this many digits (not counting the
+    // prefix or the `_` separators).
+    pub fn min_digits(self, min_digits: usize) -> RadixFormat {
+        RadixFormat { min_digits, ..self }
+    }
+
+    pub fn unsigned(&self, value: u128) -> String {
+        self.render(false, value, self.min_digits)
+    }
+
+    // Negative values get a `-` in front of the prefix, like a Rust literal:
+    // `-0x1b`.
+    pub fn signed(&self, value: i128) -> String {
+        self.render(value < 0, value.unsigned_abs(), self.min_digits)
+    }
+
+    // The `bits`-wide two's complement pattern of `value`, padded to the
+    // number of digits the widest pattern needs, like `{:x}` does for a
+    // negative `i32`.
+    pub fn twos_complement(&self, value: i128, bits: u32) -> Result<String, RadixError> {
+        let raw = to_twos_complement(value, bits)?;
+        let width = self.digits(mask(bits)).len().max(self.min_digits);
+        Ok(self.render(false, raw, width))
+    }
+
+    // The bare digits, most significant first.
+    fn digits(&self, mut value: u128) -> Vec<u8> {
+        let base = self.base as u128;
+        let mut digits = Vec::new();
+        loop {
+            let digit = DIGITS[(value % base) as usize];
+            digits.push(if self.uppercase { digit.to_ascii_uppercase() } else { digit });
+            value /= base;
+            if value == 0 {
+                break;
+            }
+        }
+        digits.reverse();
+        digits
+    }
+
+    fn render(&self, negative: bool, value: u128, min_digits: usize) -> String {
+        let mut digits = self.digits(value);
+        if digits.len() < min_digits {
+            let padding = min_digits - digits.len();
+            digits.splice(0..0, std::iter::repeat_n(b'0', padding));
+        }
+
+        let mut out = String::new();
+        if negative {
+            out.push('-');
+        }
+        if self.prefix
+            && let Some(prefix) = prefix_for(self.base)
+        {
+            out.push_str(prefix);
+        }
+        for (i, &digit) in digits.iter().enumerate() {
+            if let Some(size) = self.group
+                && i > 0
+                && (digits.len() - i).is_multiple_of(size)
+            {
+                out.push('_');
+            }
+            out.push(digit as char);
+        }
+        out
+    }
+}
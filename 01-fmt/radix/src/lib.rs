@@ -0,0 +1,199 @@
+// Formatting and parsing integers in any base from 2 to 36.
+//
+// `{:#x}`, `{:#o}` and `{:#b}` cover the three bases Rust has literal
+// prefixes for. This crate goes further: any base, `_` digit grouping,
+// fixed-width two's complement views of negative numbers, and a parser that
+// accepts everything the formatter produces and says where a bad digit is.
+//
+//     let hex = RadixFormat::new(16)?.prefix(true).group(2);
+//     assert_eq!(hex.unsigned(0xdead_beef), "0xde_ad_be_ef");
+//     assert_eq!(parse_unsigned("0xde_ad_be_ef", None)?, 0xdead_beef);
+
+mod format;
+mod parse;
+
+use std::error::Error;
+use std::fmt;
+
+pub use format::RadixFormat;
+pub use parse::{parse_signed, parse_twos_complement, parse_unsigned};
+
+pub const MIN_BASE: u32 = 2;
+pub const MAX_BASE: u32 = 36;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RadixError {
+    // The base is outside `2..=36`.
+    InvalidBase(u32),
+    // A two's complement width outside `1..=128`.
+    InvalidBits(u32),
+    // There were no digits at all (e.g. `""`, `"-"` or `"0x"`).
+    Empty { position: usize },
+    // `found` is not a digit of `base`. `position` is a byte offset.
+    InvalidDigit { position: usize, found: char, base: u32 },
+    // The digits up to and including `position` no longer fit in the type.
+    Overflow { position: usize },
+    // The value does not fit in a two's complement number of `bits` bits.
+    OutOfRange { bits: u32 },
+}
+
+impl fmt::Display for RadixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RadixError::InvalidBase(base) => {
+                write!(f, "base {} is not between {} and {}", base, MIN_BASE, MAX_BASE)
+            }
+            RadixError::InvalidBits(bits) => write!(f, "bit width {} is not between 1 and 128", bits),
+            RadixError::Empty { position } => write!(f, "expected digits at position {}", position),
+            RadixError::InvalidDigit { position, found, base } => {
+                write!(f, "invalid digit {:?} for base {} at position {}", found, base, position)
+            }
+            RadixError::Overflow { position } => write!(f, "number too large at position {}", position),
+            RadixError::OutOfRange { bits } => {
+                write!(f, "value does not fit in {} bits of two's complement", bits)
+            }
+        }
+    }
+}
+
+impl Error for RadixError {}
+
+pub(crate) fn check_base(base: u32) -> Result<u32, RadixError> {
+    if (MIN_BASE..=MAX_BASE).contains(&base) {
+        Ok(base)
+    } else {
+        Err(RadixError::InvalidBase(base))
+    }
+}
+
+pub(crate) fn check_bits(bits: u32) -> Result<u32, RadixError> {
+    if (1..=128).contains(&bits) {
+        Ok(bits)
+    } else {
+        Err(RadixError::InvalidBits(bits))
+    }
+}
+
+// All ones in the lowest `bits` bits.
+pub(crate) fn mask(bits: u32) -> u128 {
+    if bits == 128 { u128::MAX } else { (1 << bits) - 1 }
+}
+
+// The literal prefix Rust uses for a base, if it has one.
+pub fn prefix_for(base: u32) -> Option<&'static str> {
+    match base {
+        2 => Some("0b"),
+        8 => Some("0o"),
+        16 => Some("0x"),
+        _ => None,
+    }
+}
+
+// The bit pattern of `value` as a `bits`-wide two's complement number, e.g.
+// `-1` at 8 bits is `0xff`.
+pub fn to_twos_complement(value: i128, bits: u32) -> Result<u128, RadixError> {
+    let bits = check_bits(bits)?;
+    let min = if bits == 128 { i128::MIN } else { -(1i128 << (bits - 1)) };
+    let max = if bits == 128 { i128::MAX } else { (1i128 << (bits - 1)) - 1 };
+    if value < min || value > max {
+        return Err(RadixError::OutOfRange { bits });
+    }
+    Ok(value as u128 & mask(bits))
+}
+
+// The inverse of `to_twos_complement`: sign-extend a `bits`-wide pattern.
+pub fn from_twos_complement(raw: u128, bits: u32) -> Result<i128, RadixError> {
+    let bits = check_bits(bits)?;
+    if raw & !mask(bits) != 0 {
+        return Err(RadixError::OutOfRange { bits });
+    }
+    let shift = 128 - bits;
+    Ok(((raw << shift) as i128) >> shift)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_like_std_for_the_usual_bases() {
+        let plain = |base| RadixFormat::new(base).unwrap().prefix(true);
+        assert_eq!(plain(16).unsigned(27), format!("{:#x}", 27));
+        assert_eq!(plain(8).unsigned(27), format!("{:#o}", 27));
+        assert_eq!(plain(2).unsigned(27), format!("{:#b}", 27));
+        assert_eq!(plain(16).min_digits(8).unsigned(27), format!("{:#010x}", 27));
+        assert_eq!(plain(10).signed(-69420), "-69420");
+        assert_eq!(RadixFormat::new(36).unwrap().unsigned(35), "z");
+        assert_eq!(RadixFormat::new(36).unwrap().uppercase(true).unsigned(u128::MAX), "F5LXX1ZZ5PNORYNQGLHZMSP33");
+    }
+
+    #[test]
+    fn groups_digits_from_the_right() {
+        let bin = RadixFormat::new(2).unwrap().prefix(true).group(4);
+        assert_eq!(bin.unsigned(69420), "0b1_0000_1111_0010_1100");
+        let dec = RadixFormat::new(10).unwrap().group(3);
+        assert_eq!(dec.signed(-1_000_000), "-1_000_000");
+        assert_eq!(dec.unsigned(999), "999");
+    }
+
+    #[test]
+    fn twos_complement_views() {
+        assert_eq!(to_twos_complement(-1, 8), Ok(0xff));
+        assert_eq!(to_twos_complement(-128, 8), Ok(0x80));
+        assert_eq!(to_twos_complement(128, 8), Err(RadixError::OutOfRange { bits: 8 }));
+        assert_eq!(from_twos_complement(0xe8, 8), Ok(-24));
+        assert_eq!(from_twos_complement(0x1ff, 8), Err(RadixError::OutOfRange { bits: 8 }));
+        assert_eq!(from_twos_complement(u128::MAX, 128), Ok(-1));
+
+        let hex = RadixFormat::new(16).unwrap().prefix(true);
+        assert_eq!(hex.twos_complement(-5, 32).unwrap(), "0xfffffffb");
+        assert_eq!(hex.twos_complement(5, 16).unwrap(), "0x0005");
+        assert_eq!(parse_twos_complement("0xfffffffb", None, 32), Ok(-5));
+    }
+
+    #[test]
+    fn parsing_round_trips_and_reports_positions() {
+        for base in MIN_BASE..=MAX_BASE {
+            let format = RadixFormat::new(base).unwrap().prefix(true).group(3);
+            for value in [0, 1, -1, 42, i128::MAX, i128::MIN, -123_456_789] {
+                let text = format.signed(value);
+                assert_eq!(parse_signed(&text, Some(base)), Ok(value), "{} in base {}", text, base);
+            }
+        }
+
+        assert_eq!(parse_unsigned("0x1B", None), Ok(27));
+        assert_eq!(parse_unsigned("0o33", None), Ok(27));
+        assert_eq!(parse_unsigned("+0b1_1011", None), Ok(27));
+        assert_eq!(
+            parse_unsigned("0b10201", None),
+            Err(RadixError::InvalidDigit { position: 4, found: '2', base: 2 })
+        );
+        assert_eq!(
+            parse_signed("12z4", Some(10)),
+            Err(RadixError::InvalidDigit { position: 2, found: 'z', base: 10 })
+        );
+        assert_eq!(parse_unsigned("0x", None), Err(RadixError::Empty { position: 2 }));
+        assert_eq!(
+            parse_unsigned("-1", None),
+            Err(RadixError::InvalidDigit { position: 0, found: '-', base: 10 })
+        );
+        assert_eq!(
+            parse_unsigned("0x1_0000_0000_0000_0000_0000_0000_0000_0000", None),
+            Err(RadixError::Overflow { position: 42 })
+        );
+        // The prefix check must not cut a multi-byte character in half.
+        assert_eq!(
+            parse_unsigned("€1", None),
+            Err(RadixError::InvalidDigit { position: 0, found: '€', base: 10 })
+        );
+        assert_eq!(
+            parse_signed("-é", Some(16)),
+            Err(RadixError::InvalidDigit { position: 1, found: 'é', base: 16 })
+        );
+        assert_eq!(
+            parse_unsigned("0x€", None),
+            Err(RadixError::InvalidDigit { position: 2, found: '€', base: 16 })
+        );
+        assert_eq!(parse_signed("-0x8000_0000_0000_0000_0000_0000_0000_0000", None), Ok(i128::MIN));
+    }
+}
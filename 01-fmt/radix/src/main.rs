@@ -0,0 +1,144 @@
+// radix: show integers in other bases.
+//
+//     $ radix 69420
+//     $ radix --to 16 --group 4 0b1_0000_1111_0010_1100
+//     $ radix --bits 8 -- -24 0xe8
+//
+// Run with `cargo run -- <args>`.
+
+use std::env;
+use std::process;
+
+use radix::{from_twos_complement, parse_signed, to_twos_complement, RadixError, RadixFormat};
+
+const USAGE: &str = "usage: radix [--from BASE] [--to BASE]... [--bits N] [--group N] [--upper] [--no-prefix] NUMBER...
+
+  --from BASE   base of the input (default: taken from the prefix, else 10)
+  --to BASE     base to print, may be repeated (default: 2, 8, 10, 16 and 36)
+  --bits N      treat numbers as N-bit two's complement
+  --group N     separate every N digits with `_`
+  --upper       print digits above 9 in uppercase
+  --no-prefix   leave out `0x`, `0o` and `0b`";
+
+struct Options {
+    from: Option<u32>,
+    to: Vec<u32>,
+    bits: Option<u32>,
+    group: usize,
+    upper: bool,
+    prefix: bool,
+    numbers: Vec<String>,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        from: None,
+        to: Vec::new(),
+        bits: None,
+        group: 0,
+        upper: false,
+        prefix: true,
+        numbers: Vec::new(),
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| -> Result<u32, String> {
+            let text = args.next().ok_or(format!("{} needs a value", name))?;
+            text.parse().map_err(|_| format!("{} expects a number, got {:?}", name, text))
+        };
+        match arg.as_str() {
+            "--from" => options.from = Some(value("--from")?),
+            "--to" => options.to.push(value("--to")?),
+            "--bits" => options.bits = Some(value("--bits")?),
+            "--group" => options.group = value("--group")? as usize,
+            "--upper" => options.upper = true,
+            "--no-prefix" => options.prefix = false,
+            "--help" | "-h" => return Err(USAGE.to_owned()),
+            "--" => options.numbers.extend(args.by_ref()),
+            _ => options.numbers.push(arg),
+        }
+    }
+
+    if options.numbers.is_empty() {
+        return Err(USAGE.to_owned());
+    }
+    if options.to.is_empty() {
+        options.to = vec![2, 8, 10, 16, 36];
+    }
+    Ok(options)
+}
+
+// Point at the offending character under the input.
+fn report(input: &str, error: &RadixError) {
+    eprintln!("error: {}", error);
+    let position = match *error {
+        RadixError::InvalidDigit { position, .. }
+        | RadixError::Overflow { position }
+        | RadixError::Empty { position } => position,
+        _ => return,
+    };
+    eprintln!("  {}", input);
+    eprintln!("  {}^", " ".repeat(input[..position].chars().count()));
+}
+
+// With a bit width, a number may be given either as a signed value (`-24`)
+// or as the raw pattern (`0xe8`).
+fn value_of(input: &str, options: &Options) -> Result<i128, RadixError> {
+    match options.bits {
+        None => parse_signed(input, options.from),
+        Some(bits) => {
+            let value = parse_signed(input, options.from)?;
+            match to_twos_complement(value, bits) {
+                Ok(_) => Ok(value),
+                Err(_) if value >= 0 => from_twos_complement(value as u128, bits),
+                Err(e) => Err(e),
+            }
+        }
+    }
+}
+
+fn main() {
+    let options = parse_args().unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        process::exit(2);
+    });
+
+    let mut failed = false;
+    for input in &options.numbers {
+        let value = match value_of(input, &options) {
+            Ok(value) => value,
+            Err(e) => {
+                report(input, &e);
+                failed = true;
+                continue;
+            }
+        };
+
+        println!("{}", input);
+        for &base in &options.to {
+            let format = match RadixFormat::new(base) {
+                Ok(format) => format.prefix(options.prefix).group(options.group).uppercase(options.upper),
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    process::exit(2);
+                }
+            };
+            let text = match options.bits {
+                Some(bits) => format.twos_complement(value, bits),
+                None => Ok(format.signed(value)),
+            };
+            match text {
+                Ok(text) => println!("  base {:>2}: {}", base, text),
+                Err(e) => eprintln!("  base {:>2}: error: {}", base, e),
+            }
+        }
+        if let Some(bits) = options.bits {
+            println!("  as i{}:  {}", bits, value);
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{check_base, from_twos_complement, prefix_for, RadixError};
+
+// Parse a non-negative integer. With `base: None` the base comes from the
+// prefix (`0x`, `0o`, `0b`) and defaults to 10; with `Some(base)` the prefix
+// for that base is optional. A leading `+` and `_` separators are accepted.
+pub fn parse_unsigned(text: &str, base: Option<u32>) -> Result<u128, RadixError> {
+    let (negative, base, start) = split(text, base)?;
+    if negative {
+        return Err(RadixError::InvalidDigit { position: 0, found: '-', base });
+    }
+    digits(text, start, base, u128::MAX)
+}
+
+// Parse an integer with an optional `-`, e.g. `-0x1b`.
+pub fn parse_signed(text: &str, base: Option<u32>) -> Result<i128, RadixError> {
+    let (negative, base, start) = split(text, base)?;
+    if negative {
+        let magnitude = digits(text, start, base, i128::MIN.unsigned_abs())?;
+        Ok((magnitude as i128).wrapping_neg())
+    } else {
+        Ok(digits(text, start, base, i128::MAX as u128)? as i128)
+    }
+}
+
+// Parse the digits as a `bits`-wide two's complement pattern and return the
+// value it stands for, so `"0xff"` at 8 bits is `-1`.
+pub fn parse_twos_complement(text: &str, base: Option<u32>, bits: u32) -> Result<i128, RadixError> {
+    from_twos_complement(parse_unsigned(text, base)?, bits)
+}
+
+// Split off the sign and the prefix. Returns the sign, the base and the byte
+// offset where the digits start.
+fn split(text: &str, base: Option<u32>) -> Result<(bool, u32, usize), RadixError> {
+    let mut start = 0;
+    let negative = text.starts_with('-');
+    if negative || text.starts_with('+') {
+        start += 1;
+    }
+
+    let rest = &text[start..];
+    let detected = [2, 8, 16]
+        .into_iter()
+        .find(|&b| prefix_for(b).is_some_and(|p| rest.get(..2).is_some_and(|r| r.eq_ignore_ascii_case(p))));
+    let base = match (base, detected) {
+        (Some(base), Some(found)) if base == found => {
+            start += 2;
+            base
+        }
+        (Some(base), _) => check_base(base)?,
+        (None, Some(found)) => {
+            start += 2;
+            found
+        }
+        (None, None) => 10,
+    };
+    Ok((negative, base, start))
+}
+
+fn digits(text: &str, start: usize, base: u32, limit: u128) -> Result<u128, RadixError> {
+    let prefixed = text[..start].trim_start_matches(['+', '-']).len() == 2;
+    let mut value: u128 = 0;
+    let mut seen = false;
+
+    for (i, c) in text[start..].char_indices() {
+        let position = start + i;
+        // Like a Rust literal, `_` may follow a prefix or a digit but may
+        // not start an unprefixed number.
+        if c == '_' && (seen || prefixed) {
+            continue;
+        }
+        let digit = c
+            .to_digit(base)
+            .ok_or(RadixError::InvalidDigit { position, found: c, base })?;
+        value = value
+            .checked_mul(base as u128)
+            .and_then(|v| v.checked_add(digit as u128))
+            .filter(|&v| v <= limit)
+            .ok_or(RadixError::Overflow { position })?;
+        seen = true;
+    }
+
+    if seen { Ok(value) } else { Err(RadixError::Empty { position: text.len() }) }
+}
@@ -0,0 +1,74 @@
+use std::error::Error;
+use std::fmt;
+
+// Everything that can go wrong while parsing or applying a template. The
+// messages mirror the ones `rustc` prints when a `println!` literal is wrong,
+// and `pos` is the byte offset of the offending part of the template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatError {
+    // A `{` that is never closed.
+    UnclosedBrace { pos: usize },
+    // A lone `}` that should have been written as `}}`.
+    UnmatchedBrace { pos: usize },
+    // Something inside `{...}` that is not valid format-spec syntax.
+    InvalidSpec { pos: usize, reason: String },
+    // A trait name such as `{:z}` that `std::fmt` does not know about.
+    UnknownTrait { pos: usize, name: String },
+    // `{3}` when only three arguments (0, 1, 2) were given.
+    MissingArgument { pos: usize, index: usize, count: usize },
+    // `{name}` when no argument called `name` was given.
+    MissingNamedArgument { pos: usize, name: String },
+    // A positional argument that no placeholder refers to.
+    UnusedArgument { index: usize },
+    // A named argument that no placeholder refers to.
+    UnusedNamedArgument { name: String },
+    // A width or precision argument that is not a non-negative integer.
+    InvalidCount { pos: usize, type_name: &'static str },
+    // E.g. `{:x}` applied to a string.
+    UnsupportedTrait { pos: usize, spec: &'static str, type_name: &'static str },
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormatError::UnclosedBrace { pos } => {
+                write!(f, "invalid format string: expected `}}` but string was terminated (at {})", pos)
+            }
+            FormatError::UnmatchedBrace { pos } => {
+                write!(f, "invalid format string: unmatched `}}` found (at {}); use `}}}}` to escape it", pos)
+            }
+            FormatError::InvalidSpec { pos, reason } => {
+                write!(f, "invalid format string: {} (at {})", reason, pos)
+            }
+            FormatError::UnknownTrait { pos, name } => {
+                write!(f, "unknown format trait `{}` (at {})", name, pos)
+            }
+            FormatError::MissingArgument { pos, index, count } => {
+                let verb = if *count == 1 { "is" } else { "are" };
+                let noun = if *count == 1 { "argument" } else { "arguments" };
+                write!(
+                    f,
+                    "invalid reference to positional argument {} (there {} {} {}) (at {})",
+                    index, verb, count, noun, pos
+                )
+            }
+            FormatError::MissingNamedArgument { pos, name } => {
+                write!(f, "there is no argument named `{}` (at {})", name, pos)
+            }
+            FormatError::UnusedArgument { index } => {
+                write!(f, "argument {} never used", index)
+            }
+            FormatError::UnusedNamedArgument { name } => {
+                write!(f, "named argument `{}` never used", name)
+            }
+            FormatError::InvalidCount { pos, type_name } => {
+                write!(f, "expected a non-negative integer for width or precision, found `{}` (at {})", type_name, pos)
+            }
+            FormatError::UnsupportedTrait { pos, spec, type_name } => {
+                write!(f, "the trait `{}` is not implemented for `{}` (at {})", spec, type_name, pos)
+            }
+        }
+    }
+}
+
+impl Error for FormatError {}
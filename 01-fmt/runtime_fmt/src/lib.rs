@@ -0,0 +1,99 @@
+// A runtime version of the `format!` machinery.
+//
+// `println!` checks and expands its format string at compile time, so it
+// cannot take a string built (or loaded) at runtime. This crate parses the
+// same `std::fmt` mini-language at runtime and applies it to a list of
+// dynamically typed `Value`s:
+//
+//     let text = runtime_fmt::format("{:#010x}!", &[27.into()])?;
+//     assert_eq!(text, "0x0000001b!");
+//
+// Errors that `rustc` would report for a bad literal (a typo in the spec, a
+// reference to an argument that was not passed, an argument nobody uses) are
+// returned as `FormatError`s instead.
+
+mod error;
+mod render;
+mod spec;
+mod value;
+
+pub use error::FormatError;
+pub use render::Args;
+pub use spec::{Align, ArgRef, Count, Piece, Placeholder, Sign, Spec, Template, Trait};
+pub use value::Value;
+
+// Parse `template` and apply it to positional arguments in one go.
+pub fn format(template: &str, args: &[Value]) -> Result<String, FormatError> {
+    Template::parse(template)?.render(args)
+}
+
+// Like `format`, but with named arguments as well, as in
+// `format!("{subject} {verb}", subject = .., verb = ..)`.
+pub fn format_named(
+    template: &str,
+    args: &[Value],
+    named: &[(&str, Value)],
+) -> Result<String, FormatError> {
+    Template::parse(template)?.render_with(&Args { positional: args, named })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Compare the runtime interpreter against the real `format!`.
+    macro_rules! same {
+        ($template:literal, $($arg:expr),*) => {
+            assert_eq!(
+                format($template, &[$(Value::from($arg)),*]).unwrap(),
+                std::format!($template, $($arg),*),
+                "template {:?}", $template
+            );
+        };
+    }
+
+    #[test]
+    fn matches_std_for_flags_and_traits() {
+        same!("{:#x} {:#o} {:#b} {:#010x} {:+}", 27, 27, 27, 27, 5);
+        same!("[{:^15}] [{:*<8}] [{:>5}] [{:0>5}] [{:0<5}]", "hi", "ab", 1, 1, 1);
+        same!("{:x} {:X} {:+x} {:#X?} {:x?}", -1i8, 255u8, -5i32, 27, 27);
+        same!("[{:08.3}] [{:+.2e}] [{:E}] [{:05}] [{:05}] [{:+}]", -1.5, 1234.5, 0.001f32, f64::INFINITY, -0.0, f64::NAN);
+        same!("[{:10?}] [{:5?}] [{:<6}] [{:05}] [{:.2}] [{:?}]", "ab", 'c', true, "ab", "abcdef", 1.0);
+        same!("{:e} {:.1E} {:#b} {}", 1500u32, 123456i64, u128::MAX, i128::MIN);
+    }
+
+    #[test]
+    fn positional_named_and_star_arguments() {
+        same!("{1} {} {0} {}", "a", "b");
+        same!("Hello {1} is {2:.0$}", 5, "x", 0.01);
+        same!("Hello {} is {:.*}", "x", 5, 0.01);
+        same!("Hello {} is {2:.*}", "x", 5, 0.01);
+        same!("{:1$} {{literal}} {:>1$}", 7, 4);
+        assert_eq!(
+            format_named("{number:0>width$}", &[], &[("number", 1.into()), ("width", 5.into())]).unwrap(),
+            "00001"
+        );
+    }
+
+    #[test]
+    fn reports_bad_specs_and_arguments() {
+        let err = format("My name is {0}, {1} {0}", &["Bond".into()]).unwrap_err();
+        assert_eq!(err, FormatError::MissingArgument { pos: 16, index: 1, count: 1 });
+        assert_eq!(
+            err.to_string(),
+            "invalid reference to positional argument 1 (there is 1 argument) (at 16)"
+        );
+
+        assert!(matches!(format("{", &[]), Err(FormatError::UnclosedBrace { pos: 0 })));
+        assert!(matches!(format("a } b", &[]), Err(FormatError::UnmatchedBrace { pos: 2 })));
+        assert!(matches!(format("{:q}", &[1.into()]), Err(FormatError::UnknownTrait { .. })));
+        assert!(matches!(format("{:.}", &[1.into()]), Err(FormatError::InvalidSpec { .. })));
+        assert!(matches!(format("{:x}", &["s".into()]), Err(FormatError::UnsupportedTrait { .. })));
+        assert!(matches!(format("{:.*}", &[(-1).into(), 1.into()]), Err(FormatError::InvalidCount { .. })));
+        assert!(matches!(format("{}", &[1.into(), 2.into()]), Err(FormatError::UnusedArgument { index: 1 })));
+        assert!(matches!(
+            format_named("{who}", &[], &[]),
+            Err(FormatError::MissingNamedArgument { .. })
+        ));
+    }
+}
@@ -0,0 +1,51 @@
+use runtime_fmt::{format, format_named, Args, Template, Value};
+
+fn main() {
+    // The examples from `Sign-hashtag-0`, this time with the format string
+    // only known at runtime.
+    let templates = ["{:#x}!", "{:#o}!", "{:#b}!", "{:#010x}!", "Hello {:+}!"];
+    for template in templates {
+        let value = if template.contains('+') { 5 } else { 27 };
+        println!("{:<12} => {}", template, format(template, &[value.into()]).unwrap());
+    }
+
+    // The examples from `precision`, including `.*` and `N$`.
+    let x: Value = "x".into();
+    let hundredth: Value = 0.01.into();
+    let five: Value = 5.into();
+    let examples = [
+        ("Hello {0} is {1:.5}", vec![x.clone(), hundredth.clone()]),
+        ("Hello {1} is {2:.0$}", vec![five.clone(), x.clone(), hundredth.clone()]),
+        ("Hello {0} is {2:.1$}", vec![x.clone(), five.clone(), hundredth.clone()]),
+        ("Hello {} is {:.*}", vec![x.clone(), five.clone(), hundredth.clone()]),
+        ("Hello {1} is {2:.*}", vec![five.clone(), x.clone(), hundredth.clone()]),
+        ("Hello {} is {2:.*}", vec![x.clone(), five.clone(), hundredth.clone()]),
+    ];
+    for (template, args) in &examples {
+        println!("{:<22} => {}", template, format(template, args).unwrap());
+    }
+    println!(
+        "{}",
+        format_named("Hello {} is {number:.prec$}", &[x], &[("prec", five), ("number", hundredth)])
+            .unwrap()
+    );
+
+    // A template can be parsed once and rendered many times.
+    let row = Template::parse("{name:>10}|{score:^7.2}|").unwrap();
+    for (name, score) in [("Alice", 9.5), ("Bob", 7.25)] {
+        let named = [("name", name.into()), ("score", score.into())];
+        println!("{}", row.render_with(&Args { positional: &[], named: &named }).unwrap());
+    }
+
+    // What the FIXME in `formatter_print` is about: `rustc` rejects
+    // `println!("My name is {0}, {1} {0}", "Bond")`. Here the same mistake is
+    // reported at runtime.
+    match format("My name is {0}, {1} {0}", &["Bond".into()]) {
+        Ok(text) => println!("{}", text),
+        Err(e) => println!("error: {}", e),
+    }
+    match format("{:q}", &[1.into()]) {
+        Ok(text) => println!("{}", text),
+        Err(e) => println!("error: {}", e),
+    }
+}
@@ -0,0 +1,267 @@
+// Applies a parsed `Template` to a list of values.
+//
+// Only the "body" of each value (the digits, or the text of a string) is
+// produced by the standard library, through a format string that carries the
+// precision and the trait. Sign, `#` prefix, fill, alignment, width and
+// zero-padding are applied here, following the rules of
+// `Formatter::pad_integral` and `Formatter::pad`.
+
+use crate::error::FormatError;
+use crate::spec::{Align, ArgRef, Count, Piece, Sign, Spec, Template, Trait};
+use crate::value::Value;
+
+// The formatted pieces of a numeric value before padding.
+struct Numeric {
+    nonnegative: bool,
+    // NaN is printed without any sign, even with `+`.
+    signless: bool,
+    prefix: &'static str,
+    body: String,
+}
+
+// The arguments a template is applied to.
+pub struct Args<'a> {
+    pub positional: &'a [Value],
+    pub named: &'a [(&'a str, Value)],
+}
+
+impl Template {
+    pub fn render(&self, args: &[Value]) -> Result<String, FormatError> {
+        self.render_with(&Args { positional: args, named: &[] })
+    }
+
+    pub fn render_with(&self, args: &Args) -> Result<String, FormatError> {
+        let mut used = vec![false; args.positional.len()];
+        let mut used_named = vec![false; args.named.len()];
+        let mut out = String::new();
+
+        let mut lookup = |arg: &ArgRef, pos: usize| -> Result<&Value, FormatError> {
+            match arg {
+                ArgRef::Index(index) => match args.positional.get(*index) {
+                    Some(value) => {
+                        used[*index] = true;
+                        Ok(value)
+                    }
+                    None => Err(FormatError::MissingArgument {
+                        pos,
+                        index: *index,
+                        count: args.positional.len(),
+                    }),
+                },
+                ArgRef::Name(name) => match args.named.iter().position(|(n, _)| n == name) {
+                    Some(i) => {
+                        used_named[i] = true;
+                        Ok(&args.named[i].1)
+                    }
+                    None => Err(FormatError::MissingNamedArgument { pos, name: name.clone() }),
+                },
+            }
+        };
+
+        for piece in self.pieces() {
+            match piece {
+                Piece::Literal(text) => out.push_str(text),
+                Piece::Placeholder(placeholder) => {
+                    let pos = placeholder.pos;
+                    let mut count = |count: &Option<Count>| -> Result<Option<usize>, FormatError> {
+                        match count {
+                            None => Ok(None),
+                            Some(Count::Literal(n)) => Ok(Some(*n)),
+                            Some(Count::Arg(arg)) => {
+                                let value = lookup(arg, pos)?;
+                                match value.as_count() {
+                                    Some(n) => Ok(Some(n)),
+                                    None => Err(FormatError::InvalidCount { pos, type_name: value.type_name() }),
+                                }
+                            }
+                        }
+                    };
+                    let width = count(&placeholder.spec.width)?;
+                    let precision = count(&placeholder.spec.precision)?;
+                    let value = lookup(&placeholder.arg, pos)?;
+                    write_value(&mut out, value, &placeholder.spec, width, precision, pos)?;
+                }
+            }
+        }
+
+        if let Some(index) = used.iter().position(|used| !used) {
+            return Err(FormatError::UnusedArgument { index });
+        }
+        if let Some(i) = used_named.iter().position(|used| !used) {
+            return Err(FormatError::UnusedNamedArgument { name: args.named[i].0.to_owned() });
+        }
+        Ok(out)
+    }
+}
+
+fn write_value(
+    out: &mut String,
+    value: &Value,
+    spec: &Spec,
+    width: Option<usize>,
+    precision: Option<usize>,
+    pos: usize,
+) -> Result<(), FormatError> {
+    let unsupported = || FormatError::UnsupportedTrait {
+        pos,
+        spec: spec.format_trait.name(),
+        type_name: value.type_name(),
+    };
+
+    match value {
+        Value::Str(s) => match spec.format_trait {
+            Trait::Display => {
+                let s = match precision {
+                    Some(p) => s.chars().take(p).collect(),
+                    None => s.clone(),
+                };
+                pad(out, &s, spec, width, Align::Left);
+            }
+            // `Debug` for `str` and `char` writes straight to the output and
+            // ignores width and fill, so we do too.
+            Trait::Debug | Trait::DebugLowerHex | Trait::DebugUpperHex => out.push_str(&format!("{:?}", s)),
+            _ => return Err(unsupported()),
+        },
+        Value::Char(c) => match spec.format_trait {
+            Trait::Display => pad(out, &c.to_string(), spec, width, Align::Left),
+            Trait::Debug | Trait::DebugLowerHex | Trait::DebugUpperHex => out.push_str(&format!("{:?}", c)),
+            _ => return Err(unsupported()),
+        },
+        Value::Bool(b) => match spec.format_trait {
+            Trait::Display | Trait::Debug | Trait::DebugLowerHex | Trait::DebugUpperHex => {
+                pad(out, &b.to_string(), spec, width, Align::Left)
+            }
+            _ => return Err(unsupported()),
+        },
+        _ => {
+            let numeric = numeric(value, spec, precision).ok_or_else(unsupported)?;
+            pad_numeric(out, numeric, spec, width);
+        }
+    }
+    Ok(())
+}
+
+macro_rules! integer {
+    ($v:expr, $trait:expr, $alternate:expr, $precision:expr) => {{
+        let v = $v;
+        let (nonnegative, prefix, body) = match $trait {
+            Trait::Display | Trait::Debug => (v >= 0 as _, "", v.unsigned_abs().to_string()),
+            // The radix traits show the two's complement bit pattern, so a
+            // negative value never gets a `-`.
+            Trait::LowerHex | Trait::DebugLowerHex => (true, "0x", format!("{:x}", v)),
+            Trait::UpperHex | Trait::DebugUpperHex => (true, "0x", format!("{:X}", v)),
+            Trait::Octal => (true, "0o", format!("{:o}", v)),
+            Trait::Binary => (true, "0b", format!("{:b}", v)),
+            Trait::LowerExp => (v >= 0 as _, "", match $precision {
+                Some(p) => format!("{:.*e}", p, v.unsigned_abs()),
+                None => format!("{:e}", v.unsigned_abs()),
+            }),
+            Trait::UpperExp => (v >= 0 as _, "", match $precision {
+                Some(p) => format!("{:.*E}", p, v.unsigned_abs()),
+                None => format!("{:E}", v.unsigned_abs()),
+            }),
+        };
+        let prefix = if $alternate { prefix } else { "" };
+        Some(Numeric { nonnegative, signless: false, prefix, body })
+    }};
+}
+
+macro_rules! float {
+    ($v:expr, $trait:expr, $precision:expr) => {{
+        let v = $v;
+        let abs = v.abs();
+        let body = match ($trait, $precision) {
+            (Trait::Display, Some(p)) => format!("{:.*}", p, abs),
+            (Trait::Display, None) => format!("{}", abs),
+            (Trait::Debug | Trait::DebugLowerHex | Trait::DebugUpperHex, Some(p)) => format!("{:.*?}", p, abs),
+            (Trait::Debug | Trait::DebugLowerHex | Trait::DebugUpperHex, None) => format!("{:?}", abs),
+            (Trait::LowerExp, Some(p)) => format!("{:.*e}", p, abs),
+            (Trait::LowerExp, None) => format!("{:e}", abs),
+            (Trait::UpperExp, Some(p)) => format!("{:.*E}", p, abs),
+            (Trait::UpperExp, None) => format!("{:E}", abs),
+            _ => return None,
+        };
+        Some(Numeric { nonnegative: !v.is_sign_negative(), signless: v.is_nan(), prefix: "", body })
+    }};
+}
+
+fn numeric(value: &Value, spec: &Spec, precision: Option<usize>) -> Option<Numeric> {
+    let t = spec.format_trait;
+    let alt = spec.alternate;
+    match *value {
+        Value::I8(v) => integer!(v, t, alt, precision),
+        Value::I16(v) => integer!(v, t, alt, precision),
+        Value::I32(v) => integer!(v, t, alt, precision),
+        Value::I64(v) => integer!(v, t, alt, precision),
+        Value::I128(v) => integer!(v, t, alt, precision),
+        Value::Isize(v) => integer!(v, t, alt, precision),
+        // Smaller unsigned types are widened to a signed type that holds every
+        // value, which keeps the digits (and the bit pattern) unchanged.
+        Value::U8(v) => integer!(v as i16, t, alt, precision),
+        Value::U16(v) => integer!(v as i32, t, alt, precision),
+        Value::U32(v) => integer!(v as i64, t, alt, precision),
+        Value::U64(v) => integer!(v as i128, t, alt, precision),
+        Value::U128(v) => Some(unsigned128(v, t, alt, precision)),
+        Value::Usize(v) => integer!(v as i128, t, alt, precision),
+        Value::F32(v) => float!(v, t, precision),
+        Value::F64(v) => float!(v, t, precision),
+        Value::Bool(_) | Value::Char(_) | Value::Str(_) => None,
+    }
+}
+
+// `u128` has no wider signed type to borrow the `integer!` arm from.
+fn unsigned128(v: u128, t: Trait, alternate: bool, precision: Option<usize>) -> Numeric {
+    let (prefix, body) = match t {
+        Trait::Display | Trait::Debug => ("", v.to_string()),
+        Trait::LowerHex | Trait::DebugLowerHex => ("0x", format!("{:x}", v)),
+        Trait::UpperHex | Trait::DebugUpperHex => ("0x", format!("{:X}", v)),
+        Trait::Octal => ("0o", format!("{:o}", v)),
+        Trait::Binary => ("0b", format!("{:b}", v)),
+        Trait::LowerExp => ("", match precision {
+            Some(p) => format!("{:.*e}", p, v),
+            None => format!("{:e}", v),
+        }),
+        Trait::UpperExp => ("", match precision {
+            Some(p) => format!("{:.*E}", p, v),
+            None => format!("{:E}", v),
+        }),
+    };
+    Numeric { nonnegative: true, signless: false, prefix: if alternate { prefix } else { "" }, body }
+}
+
+fn pad_numeric(out: &mut String, numeric: Numeric, spec: &Spec, width: Option<usize>) {
+    let mut head = String::new();
+    if !numeric.nonnegative && !numeric.signless {
+        head.push('-');
+    } else if spec.sign == Some(Sign::Plus) && !numeric.signless {
+        head.push('+');
+    }
+    head.push_str(numeric.prefix);
+
+    // Sign-aware zero padding: the zeros go between the sign/prefix and the
+    // digits, and fill and alignment are ignored.
+    if spec.zero_pad {
+        let len = head.chars().count() + numeric.body.chars().count();
+        out.push_str(&head);
+        for _ in len..width.unwrap_or(0) {
+            out.push('0');
+        }
+        out.push_str(&numeric.body);
+    } else {
+        head.push_str(&numeric.body);
+        pad(out, &head, spec, width, Align::Right);
+    }
+}
+
+fn pad(out: &mut String, s: &str, spec: &Spec, width: Option<usize>, default: Align) {
+    let len = s.chars().count();
+    let padding = width.unwrap_or(0).saturating_sub(len);
+    let (before, after) = match spec.align.unwrap_or(default) {
+        Align::Left => (0, padding),
+        Align::Right => (padding, 0),
+        Align::Center => (padding / 2, padding - padding / 2),
+    };
+    out.extend(std::iter::repeat_n(spec.fill, before));
+    out.push_str(s);
+    out.extend(std::iter::repeat_n(spec.fill, after));
+}
@@ -0,0 +1,364 @@
+// Parser for the `std::fmt` grammar:
+//
+//   format_string := text [ maybe_format text ] *
+//   maybe_format := '{' '{' | '}' '}' | format
+//   format := '{' [ argument ] [ ':' format_spec ] [ ws ] * '}'
+//   argument := integer | identifier
+//   format_spec := [[fill]align][sign]['#']['0'][width]['.' precision]type
+//   width := count
+//   precision := count | '*'
+//   count := parameter | integer
+//   parameter := argument '$'
+//
+// https://doc.rust-lang.org/std/fmt/#syntax
+
+use crate::error::FormatError;
+
+// Which argument a placeholder (or its width/precision) refers to. Implicit
+// `{}` references are numbered while parsing, exactly like the compiler does,
+// so only explicit indices and names remain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgRef {
+    Index(usize),
+    Name(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sign {
+    Plus,
+    Minus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Count {
+    Literal(usize),
+    Arg(ArgRef),
+}
+
+// The formatting trait selected by the type character at the end of a spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trait {
+    Display,
+    Debug,
+    DebugLowerHex,
+    DebugUpperHex,
+    LowerHex,
+    UpperHex,
+    Octal,
+    Binary,
+    LowerExp,
+    UpperExp,
+}
+
+impl Trait {
+    // The spelling used in error messages.
+    pub fn name(self) -> &'static str {
+        match self {
+            Trait::Display => "std::fmt::Display",
+            Trait::Debug | Trait::DebugLowerHex | Trait::DebugUpperHex => "std::fmt::Debug",
+            Trait::LowerHex => "std::fmt::LowerHex",
+            Trait::UpperHex => "std::fmt::UpperHex",
+            Trait::Octal => "std::fmt::Octal",
+            Trait::Binary => "std::fmt::Binary",
+            Trait::LowerExp => "std::fmt::LowerExp",
+            Trait::UpperExp => "std::fmt::UpperExp",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spec {
+    pub fill: char,
+    pub align: Option<Align>,
+    pub sign: Option<Sign>,
+    pub alternate: bool,
+    pub zero_pad: bool,
+    pub width: Option<Count>,
+    pub precision: Option<Count>,
+    pub format_trait: Trait,
+}
+
+impl Default for Spec {
+    fn default() -> Self {
+        Spec {
+            fill: ' ',
+            align: None,
+            sign: None,
+            alternate: false,
+            zero_pad: false,
+            width: None,
+            precision: None,
+            format_trait: Trait::Display,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholder {
+    // Byte offset of the opening `{`, kept for error messages.
+    pub pos: usize,
+    pub arg: ArgRef,
+    pub spec: Spec,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Piece {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+// A parsed format string. Parse once, then render it with as many argument
+// lists as you like.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    pieces: Vec<Piece>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Template, FormatError> {
+        Parser { src: source, pos: 0, next_arg: 0 }.parse()
+    }
+
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    // The counter behind implicit `{}` and `.*` references.
+    next_arg: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        self.src[self.pos..].chars().nth(1)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn invalid(&self, reason: &str) -> FormatError {
+        FormatError::InvalidSpec { pos: self.pos, reason: reason.to_owned() }
+    }
+
+    fn parse(mut self) -> Result<Template, FormatError> {
+        let mut pieces = Vec::new();
+        let mut text = String::new();
+
+        while let Some(c) = self.peek() {
+            match c {
+                '{' if self.peek_second() == Some('{') => {
+                    self.pos += 2;
+                    text.push('{');
+                }
+                '}' if self.peek_second() == Some('}') => {
+                    self.pos += 2;
+                    text.push('}');
+                }
+                '}' => return Err(FormatError::UnmatchedBrace { pos: self.pos }),
+                '{' => {
+                    if !text.is_empty() {
+                        pieces.push(Piece::Literal(std::mem::take(&mut text)));
+                    }
+                    pieces.push(Piece::Placeholder(self.placeholder()?));
+                }
+                _ => {
+                    text.push(c);
+                    self.pos += c.len_utf8();
+                }
+            }
+        }
+        if !text.is_empty() {
+            pieces.push(Piece::Literal(text));
+        }
+
+        Ok(Template { pieces })
+    }
+
+    fn placeholder(&mut self) -> Result<Placeholder, FormatError> {
+        let start = self.pos;
+        self.bump(); // the `{`
+
+        let explicit = self.argument();
+        let mut spec = Spec::default();
+        // `.*` takes its precision from the next implicit argument *before*
+        // the value itself does, so the value reference is resolved last.
+        if self.eat(':') {
+            self.spec(&mut spec)?;
+        }
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+        match self.peek() {
+            Some('}') => {
+                self.bump();
+            }
+            None => return Err(FormatError::UnclosedBrace { pos: start }),
+            Some(_) => return Err(self.invalid("expected `}`")),
+        }
+
+        let arg = explicit.unwrap_or_else(|| self.implicit());
+        Ok(Placeholder { pos: start, arg, spec })
+    }
+
+    fn implicit(&mut self) -> ArgRef {
+        let index = self.next_arg;
+        self.next_arg += 1;
+        ArgRef::Index(index)
+    }
+
+    // `integer | identifier`, or nothing at all.
+    fn argument(&mut self) -> Option<ArgRef> {
+        let rest = &self.src[self.pos..];
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if digits > 0 {
+            let index = rest[..digits].parse().ok()?;
+            self.pos += digits;
+            return Some(ArgRef::Index(index));
+        }
+        let ident = identifier(rest)?;
+        self.pos += ident.len();
+        Some(ArgRef::Name(ident.to_owned()))
+    }
+
+    fn spec(&mut self, spec: &mut Spec) -> Result<(), FormatError> {
+        // [[fill]align]
+        if let Some(align) = self.peek_second().and_then(align_of) {
+            spec.fill = self.bump().unwrap_or(' ');
+            self.bump();
+            spec.align = Some(align);
+        } else if let Some(align) = self.peek().and_then(align_of) {
+            self.bump();
+            spec.align = Some(align);
+        }
+
+        // [sign]['#']['0']
+        if self.eat('+') {
+            spec.sign = Some(Sign::Plus);
+        } else if self.eat('-') {
+            spec.sign = Some(Sign::Minus);
+        }
+        spec.alternate = self.eat('#');
+        // `{:0$}` means "width from argument 0", not the zero flag.
+        if self.peek() == Some('0') && self.peek_second() != Some('$') {
+            self.bump();
+            spec.zero_pad = true;
+        }
+
+        // [width]
+        spec.width = self.count()?;
+
+        // ['.' precision]
+        if self.eat('.') {
+            if self.eat('*') {
+                spec.precision = Some(Count::Arg(self.implicit()));
+            } else {
+                match self.count()? {
+                    Some(count) => spec.precision = Some(count),
+                    None => return Err(self.invalid("expected a precision after `.`")),
+                }
+            }
+        }
+
+        // type
+        let start = self.pos;
+        spec.format_trait = match identifier(self.src.get(self.pos..).unwrap_or("")) {
+            Some(name) => {
+                self.pos += name.len();
+                match name {
+                    "x" if self.eat('?') => Trait::DebugLowerHex,
+                    "X" if self.eat('?') => Trait::DebugUpperHex,
+                    "x" => Trait::LowerHex,
+                    "X" => Trait::UpperHex,
+                    "o" => Trait::Octal,
+                    "b" => Trait::Binary,
+                    "e" => Trait::LowerExp,
+                    "E" => Trait::UpperExp,
+                    _ => return Err(FormatError::UnknownTrait { pos: start, name: name.to_owned() }),
+                }
+            }
+            None if self.eat('?') => Trait::Debug,
+            None => Trait::Display,
+        };
+
+        Ok(())
+    }
+
+    // `integer`, `integer '$'` or `identifier '$'`. An identifier without the
+    // `$` is the trait name, so the parser backs off and leaves it alone.
+    fn count(&mut self) -> Result<Option<Count>, FormatError> {
+        let start = self.pos;
+        match self.argument() {
+            Some(ArgRef::Index(n)) => {
+                if self.eat('$') {
+                    Ok(Some(Count::Arg(ArgRef::Index(n))))
+                } else {
+                    Ok(Some(Count::Literal(n)))
+                }
+            }
+            Some(ArgRef::Name(name)) => {
+                if self.eat('$') {
+                    Ok(Some(Count::Arg(ArgRef::Name(name))))
+                } else {
+                    self.pos = start;
+                    Ok(None)
+                }
+            }
+            None => {
+                let rest = &self.src[self.pos..];
+                if rest.starts_with(|c: char| c.is_ascii_digit()) {
+                    Err(self.invalid("count is too large"))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    }
+}
+
+fn align_of(c: char) -> Option<Align> {
+    match c {
+        '<' => Some(Align::Left),
+        '^' => Some(Align::Center),
+        '>' => Some(Align::Right),
+        _ => None,
+    }
+}
+
+// The longest identifier at the start of `s`, if there is one.
+fn identifier(s: &str) -> Option<&str> {
+    let mut chars = s.char_indices();
+    match chars.next() {
+        Some((_, c)) if c == '_' || c.is_alphabetic() => {}
+        _ => return None,
+    }
+    let end = chars
+        .find(|&(_, c)| !(c == '_' || c.is_alphanumeric()))
+        .map_or(s.len(), |(i, _)| i);
+    Some(&s[..end])
+}
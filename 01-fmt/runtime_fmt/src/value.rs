@@ -0,0 +1,93 @@
+// A dynamically typed argument. `println!` knows the type of every argument
+// at compile time; here the type travels with the value instead, so that the
+// renderer can pick the right `fmt` trait when the template is applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    Isize(isize),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Usize(usize),
+    F32(f32),
+    F64(f64),
+    Bool(bool),
+    Char(char),
+    Str(String),
+}
+
+impl Value {
+    // The name of the Rust type behind the value, used in error messages.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::I8(_) => "i8",
+            Value::I16(_) => "i16",
+            Value::I32(_) => "i32",
+            Value::I64(_) => "i64",
+            Value::I128(_) => "i128",
+            Value::Isize(_) => "isize",
+            Value::U8(_) => "u8",
+            Value::U16(_) => "u16",
+            Value::U32(_) => "u32",
+            Value::U64(_) => "u64",
+            Value::U128(_) => "u128",
+            Value::Usize(_) => "usize",
+            Value::F32(_) => "f32",
+            Value::F64(_) => "f64",
+            Value::Bool(_) => "bool",
+            Value::Char(_) => "char",
+            Value::Str(_) => "str",
+        }
+    }
+
+    // Width and precision taken from an argument (`{:1$}`, `{:.*}`) must be
+    // a non-negative integer. `println!` insists on `usize`; we accept any
+    // integer that fits.
+    pub fn as_count(&self) -> Option<usize> {
+        match *self {
+            Value::I8(v) => usize::try_from(v).ok(),
+            Value::I16(v) => usize::try_from(v).ok(),
+            Value::I32(v) => usize::try_from(v).ok(),
+            Value::I64(v) => usize::try_from(v).ok(),
+            Value::I128(v) => usize::try_from(v).ok(),
+            Value::Isize(v) => usize::try_from(v).ok(),
+            Value::U8(v) => Some(v as usize),
+            Value::U16(v) => Some(v as usize),
+            Value::U32(v) => usize::try_from(v).ok(),
+            Value::U64(v) => usize::try_from(v).ok(),
+            Value::U128(v) => usize::try_from(v).ok(),
+            Value::Usize(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(v: $ty) -> Self {
+                    Value::$variant(v)
+                }
+            }
+        )*
+    };
+}
+
+impl_from! {
+    i8 => I8, i16 => I16, i32 => I32, i64 => I64, i128 => I128, isize => Isize,
+    u8 => U8, u16 => U16, u32 => U32, u64 => U64, u128 => U128, usize => Usize,
+    f32 => F32, f64 => F64, bool => Bool, char => Char, String => Str,
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        Value::Str(v.to_owned())
+    }
+}
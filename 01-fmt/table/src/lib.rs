@@ -0,0 +1,294 @@
+// Plain-text tables whose columns line up even with CJK text and emoji.
+//
+//     let mut table = Table::new(["City", "Lat", "Lon"]);
+//     table.align(1, Align::Right).align(2, Align::Right);
+//     table.row(["Dublin", "53.348°N", "6.260°W"]);
+//     println!("{}", table);
+//
+//     +--------+----------+---------+
+//     | City   |      Lat |     Lon |
+//     +--------+----------+---------+
+//     | Dublin | 53.348°N | 6.260°W |
+//     +--------+----------+---------+
+//
+// Column widths are measured in terminal columns (see `display_width`), not
+// in `char`s as `{:>5}` does, so "ようこそ" counts as 8 and the borders stay
+// straight.
+
+mod width;
+
+use std::fmt;
+
+pub use width::{char_width, display_width, truncate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+// The characters a table is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub vertical: char,
+    pub horizontal: char,
+    // Corners and joints, each as [left, middle, right].
+    pub top: [char; 3],
+    pub middle: [char; 3],
+    pub bottom: [char; 3],
+    // Whether to draw the outer frame and the lines between columns.
+    pub frame: bool,
+}
+
+impl Style {
+    pub const ASCII: Style = Style {
+        vertical: '|',
+        horizontal: '-',
+        top: ['+', '+', '+'],
+        middle: ['+', '+', '+'],
+        bottom: ['+', '+', '+'],
+        frame: true,
+    };
+
+    pub const BOX: Style = Style {
+        vertical: '│',
+        horizontal: '─',
+        top: ['┌', '┬', '┐'],
+        middle: ['├', '┼', '┤'],
+        bottom: ['└', '┴', '┘'],
+        frame: true,
+    };
+
+    // No frame: columns are separated by spaces and the header is
+    // underlined with dashes.
+    pub const PLAIN: Style = Style {
+        vertical: ' ',
+        horizontal: '-',
+        top: [' ', ' ', ' '],
+        middle: [' ', ' ', ' '],
+        bottom: [' ', ' ', ' '],
+        frame: false,
+    };
+}
+
+#[derive(Debug, Clone)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    aligns: Vec<Align>,
+    max_widths: Vec<Option<usize>>,
+    style: Style,
+}
+
+impl Table {
+    pub fn new<I>(headers: I) -> Table
+    where
+        I: IntoIterator,
+        I::Item: fmt::Display,
+    {
+        let headers: Vec<String> = headers.into_iter().map(|h| cell(&h)).collect();
+        let columns = headers.len();
+        Table {
+            headers,
+            rows: Vec::new(),
+            aligns: vec![Align::Left; columns],
+            max_widths: vec![None; columns],
+            style: Style::ASCII,
+        }
+    }
+
+    // Add a row. Short rows are padded with empty cells; extra cells are
+    // ignored.
+    pub fn row<I>(&mut self, cells: I) -> &mut Table
+    where
+        I: IntoIterator,
+        I::Item: fmt::Display,
+    {
+        let mut row: Vec<String> = cells.into_iter().take(self.headers.len()).map(|c| cell(&c)).collect();
+        row.resize(self.headers.len(), String::new());
+        self.rows.push(row);
+        self
+    }
+
+    pub fn align(&mut self, column: usize, align: Align) -> &mut Table {
+        if let Some(a) = self.aligns.get_mut(column) {
+            *a = align;
+        }
+        self
+    }
+
+    // Cells (and the header) wider than `width` columns are cut short with
+    // an ellipsis.
+    pub fn max_width(&mut self, column: usize, width: usize) -> &mut Table {
+        if let Some(w) = self.max_widths.get_mut(column) {
+            *w = Some(width);
+        }
+        self
+    }
+
+    pub fn style(&mut self, style: Style) -> &mut Table {
+        self.style = style;
+        self
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        (0..self.headers.len())
+            .map(|column| {
+                let widest = std::iter::once(&self.headers)
+                    .chain(&self.rows)
+                    .map(|row| display_width(&row[column]))
+                    .max()
+                    .unwrap_or(0);
+                match self.max_widths[column] {
+                    Some(max) => widest.min(max),
+                    None => widest,
+                }
+            })
+            .collect()
+    }
+
+    fn rule(&self, f: &mut fmt::Formatter, widths: &[usize], joints: [char; 3]) -> fmt::Result {
+        let style = &self.style;
+        let mut line = String::new();
+        if style.frame {
+            line.push(joints[0]);
+        }
+        for (i, &width) in widths.iter().enumerate() {
+            if i != 0 {
+                if style.frame {
+                    line.push(joints[1]);
+                } else {
+                    line.push_str("  ");
+                }
+            }
+            let padding = if style.frame { 2 } else { 0 };
+            line.extend(std::iter::repeat_n(style.horizontal, width + padding));
+        }
+        if style.frame {
+            line.push(joints[2]);
+        }
+        writeln!(f, "{}", line.trim_end())
+    }
+
+    fn line(&self, f: &mut fmt::Formatter, widths: &[usize], cells: &[String]) -> fmt::Result {
+        let style = &self.style;
+        let mut line = String::new();
+        if style.frame {
+            line.push(style.vertical);
+            line.push(' ');
+        }
+        for (i, (text, &width)) in cells.iter().zip(widths).enumerate() {
+            if i != 0 {
+                if style.frame {
+                    line.push(' ');
+                    line.push(style.vertical);
+                    line.push(' ');
+                } else {
+                    line.push_str("  ");
+                }
+            }
+            // Headers follow the column alignment too, so numbers and their
+            // heading line up on the same side.
+            pad(&mut line, &truncate(text, width), width, self.aligns[i]);
+        }
+        if style.frame {
+            line.push(' ');
+            line.push(style.vertical);
+        }
+        writeln!(f, "{}", if style.frame { &line } else { line.trim_end() })
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let widths = self.widths();
+        let style = self.style;
+        if style.frame {
+            self.rule(f, &widths, style.top)?;
+        }
+        self.line(f, &widths, &self.headers)?;
+        self.rule(f, &widths, style.middle)?;
+        for row in &self.rows {
+            self.line(f, &widths, row)?;
+        }
+        if style.frame {
+            self.rule(f, &widths, style.bottom)?;
+        }
+        Ok(())
+    }
+}
+
+// A cell is one line of text.
+fn cell(value: &dyn fmt::Display) -> String {
+    value.to_string().replace(['\n', '\r'], " ")
+}
+
+// Pad `text` to `width` columns.
+fn pad(out: &mut String, text: &str, width: usize, align: Align) {
+    let padding = width.saturating_sub(display_width(text));
+    let (before, after) = match align {
+        Align::Left => (0, padding),
+        Align::Right => (padding, 0),
+        Align::Center => (padding / 2, padding - padding / 2),
+    };
+    out.extend(std::iter::repeat_n(' ', before));
+    out.push_str(text);
+    out.extend(std::iter::repeat_n(' ', after));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_wide_and_zero_width_characters() {
+        assert_eq!(display_width("Oslo"), 4);
+        assert_eq!(display_width("ようこそ"), 8);
+        assert_eq!(display_width("서울"), 4);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(display_width("🦀"), 2);
+        assert_eq!(truncate("Vancouver", 5), "Vanc…");
+        assert_eq!(truncate("ようこそ", 6), "よう…");
+        assert_eq!(truncate("ようこそ", 8), "ようこそ");
+    }
+
+    #[test]
+    fn renders_aligned_ascii_table() {
+        let mut table = Table::new(["Name", "Value"]);
+        table.align(1, Align::Right);
+        table.row(["ようこそ", "1"]).row(["Rust", "1000"]);
+        assert_eq!(
+            table.to_string(),
+            "\
++----------+-------+
+| Name     | Value |
++----------+-------+
+| ようこそ |     1 |
+| Rust     |  1000 |
++----------+-------+
+"
+        );
+    }
+
+    #[test]
+    fn renders_box_and_plain_styles_with_truncation() {
+        let mut table = Table::new(["City", "Note"]);
+        table.style(Style::BOX).max_width(1, 6).align(0, Align::Center);
+        table.row(["Oslo", "a very long note"]).row(["東京"]);
+        assert_eq!(
+            table.to_string(),
+            "\
+┌──────┬────────┐
+│ City │ Note   │
+├──────┼────────┤
+│ Oslo │ a ver… │
+│ 東京 │        │
+└──────┴────────┘
+"
+        );
+
+        table.style(Style::PLAIN);
+        assert_eq!(table.to_string(), "City  Note\n----  ------\nOslo  a ver…\n東京\n");
+    }
+}
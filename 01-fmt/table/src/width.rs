@@ -0,0 +1,139 @@
+// How many terminal columns a string takes up.
+//
+// `{:>5}` counts `char`s, but a terminal gives CJK ideographs, kana, hangul
+// and most emoji two columns, and combining accents none at all. The ranges
+// below are the wide and zero-width blocks from Unicode's East Asian Width
+// (UAX #11) and general category data that show up in practice.
+
+const ZERO_WIDTH: &[(u32, u32)] = &[
+    (0x0300, 0x036F), // combining diacritical marks
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x0E31, 0x0E31),
+    (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F), // zero width space, joiners, direction marks
+    (0x2028, 0x202E),
+    (0x2060, 0x2064),
+    (0x20D0, 0x20FF), // combining marks for symbols
+    (0x3099, 0x309A), // combining kana voiced sound marks
+    (0xFE00, 0xFE0F), // variation selectors
+    (0xFE20, 0xFE2F),
+    (0xFEFF, 0xFEFF),
+    (0xE0100, 0xE01EF),
+];
+
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F), // hangul jamo
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F5),
+    (0x26FA, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x303E), // CJK radicals, punctuation
+    (0x3041, 0x3096), // hiragana
+    (0x309B, 0x33FF), // katakana, bopomofo, CJK compatibility
+    (0x3400, 0x4DBF), // CJK extension A
+    (0x4E00, 0x9FFF), // CJK unified ideographs
+    (0xA000, 0xA4CF), // yi
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3), // hangul syllables
+    (0xF900, 0xFAFF), // CJK compatibility ideographs
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60), // fullwidth forms
+    (0xFFE0, 0xFFE6),
+    (0x16FE0, 0x16FE4),
+    (0x17000, 0x18CFF), // tangut
+    (0x1B000, 0x1B2FF), // kana supplement
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F200, 0x1F251),
+    (0x1F300, 0x1F64F), // emoji: symbols, pictographs, emoticons
+    (0x1F680, 0x1F6FF), // transport and map symbols
+    (0x1F7E0, 0x1F7EB),
+    (0x1F90C, 0x1F9FF), // supplemental symbols and pictographs
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x2FFFD), // CJK extensions B..F
+    (0x30000, 0x3FFFD),
+];
+
+fn in_table(table: &[(u32, u32)], c: char) -> bool {
+    let c = c as u32;
+    table
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                std::cmp::Ordering::Less
+            } else if start > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+// 0, 1 or 2 columns.
+pub fn char_width(c: char) -> usize {
+    if c.is_control() || in_table(ZERO_WIDTH, c) {
+        0
+    } else if in_table(WIDE, c) {
+        2
+    } else {
+        1
+    }
+}
+
+pub fn display_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+// Cut `s` down to at most `width` columns, ending in `…` if anything was
+// removed. A wide character that would only half fit is dropped, so the
+// result can be one column short; padding takes care of that.
+pub fn truncate(s: &str, width: usize) -> String {
+    if display_width(s) <= width {
+        return s.to_owned();
+    }
+    if width == 0 {
+        return String::new();
+    }
+
+    let mut out = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = char_width(c);
+        if used + w > width - 1 {
+            break;
+        }
+        used += w;
+        out.push(c);
+    }
+    out.push('…');
+    out
+}
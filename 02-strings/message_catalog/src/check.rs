@@ -0,0 +1,57 @@
+// Consistency checks across the locales of a catalog.
+
+use std::fmt;
+
+use crate::plural::Category;
+
+// Something a translator should look at. The default locale is the
+// reference: every key it has should be in the others, and keys it does not
+// have are left over from an earlier version.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Problem {
+    // A key of the default locale that `locale` does not translate.
+    MissingKey { locale: String, key: String },
+    // A key of `locale` that the default locale does not have.
+    UnusedKey { locale: String, key: String },
+    // A plural form the language needs but the message lacks.
+    MissingForm { locale: String, key: String, category: Category },
+    // A plural form the language never uses, like `[few]` in English.
+    UnusedForm { locale: String, key: String, category: Category },
+}
+
+impl Problem {
+    pub fn locale(&self) -> &str {
+        match self {
+            Problem::MissingKey { locale, .. }
+            | Problem::UnusedKey { locale, .. }
+            | Problem::MissingForm { locale, .. }
+            | Problem::UnusedForm { locale, .. } => locale,
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        match self {
+            Problem::MissingKey { key, .. }
+            | Problem::UnusedKey { key, .. }
+            | Problem::MissingForm { key, .. }
+            | Problem::UnusedForm { key, .. } => key,
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::MissingKey { locale, key } => write!(f, "{}: `{}` is not translated", locale, key),
+            Problem::UnusedKey { locale, key } => {
+                write!(f, "{}: `{}` is not in the default locale", locale, key)
+            }
+            Problem::MissingForm { locale, key, category } => {
+                write!(f, "{}: `{}` has no `{}` form", locale, key, category)
+            }
+            Problem::UnusedForm { locale, key, category } => {
+                write!(f, "{}: `{}[{}]` is never used", locale, key, category)
+            }
+        }
+    }
+}
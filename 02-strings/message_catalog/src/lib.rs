@@ -0,0 +1,303 @@
+// Translated messages, looked up by key.
+//
+// Each locale has a text file of messages (see `parse` for the format):
+//
+//     # fr.txt
+//     greeting = Bonjour, {name} !
+//     inbox[one] = Vous avez {count} message
+//     inbox[other] = Vous avez {count} messages
+//
+// and the program asks for a message by key:
+//
+//     let catalog = Catalog::load_dir("locales", "en")?;
+//     catalog.text("fr", "greeting", &[("name", "Alice".into())])?;   // Bonjour, Alice !
+//     catalog.plural("fr", "inbox", 0, &[])?;                          // Vous avez 0 message
+//
+// A message missing from `fr-CA` is looked for in `fr` and then in the
+// default locale. `plural` picks the form by the CLDR rules of the language
+// the message was found in, and passes the number on as `{count}`.
+//
+// `check` lists what translators still have to do: missing keys and plural
+// forms, and ones that are no longer used.
+
+mod check;
+mod parse;
+mod plural;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use template_engine::{Map, Template, TemplateError};
+
+pub use check::Problem;
+pub use parse::LoadError;
+pub use plural::{categories, category, Category};
+pub use template_engine::Value;
+
+use parse::Message;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Catalog {
+    default_locale: String,
+    locales: BTreeMap<String, BTreeMap<String, Message>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageError {
+    // Not in the locale, its language or the default locale.
+    UnknownKey { locale: String, key: String },
+    // A plural message asked for with `text`, which has no number to go by.
+    NeedsCount { key: String },
+    // A plural message with neither the form asked for nor `other`.
+    MissingForm { locale: String, key: String, category: Category },
+    // The message did not get the values it uses.
+    Template { key: String, error: TemplateError },
+}
+
+impl fmt::Display for MessageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MessageError::UnknownKey { locale, key } => write!(f, "no message `{}` for `{}`", key, locale),
+            MessageError::NeedsCount { key } => write!(f, "`{}` is a plural message and needs a count", key),
+            MessageError::MissingForm { locale, key, category } => {
+                write!(f, "`{}` has no `{}` or `other` form in `{}`", key, category, locale)
+            }
+            MessageError::Template { key, error } => write!(f, "in `{}`: {}", key, error),
+        }
+    }
+}
+
+impl Error for MessageError {}
+
+impl Catalog {
+    // An empty catalog whose fallback is `default_locale`.
+    pub fn new(default_locale: &str) -> Catalog {
+        Catalog { default_locale: default_locale.to_owned(), locales: BTreeMap::new() }
+    }
+
+    // Every `<locale>.txt` file in `dir`, e.g. `en.txt` and `pt-BR.txt`.
+    pub fn load_dir(dir: impl AsRef<Path>, default_locale: &str) -> Result<Catalog, LoadError> {
+        let dir = dir.as_ref();
+        let io_error = |path: &Path, e: std::io::Error| LoadError::Io { path: path.to_owned(), message: e.to_string() };
+        let mut catalog = Catalog::new(default_locale);
+        for entry in fs::read_dir(dir).map_err(|e| io_error(dir, e))? {
+            let path = entry.map_err(|e| io_error(dir, e))?.path();
+            let locale = match (path.extension(), path.file_stem()) {
+                (Some(ext), Some(stem)) if ext == "txt" => stem.to_string_lossy().into_owned(),
+                _ => continue,
+            };
+            let text = fs::read_to_string(&path).map_err(|e| io_error(&path, e))?;
+            catalog.add(&locale, &text)?;
+        }
+        Ok(catalog)
+    }
+
+    // Adds the messages of `locale`, read from the text of its file. A
+    // locale that was already there is replaced.
+    pub fn add(&mut self, locale: &str, text: &str) -> Result<(), LoadError> {
+        self.locales.insert(locale.to_owned(), parse::parse(locale, text)?);
+        Ok(())
+    }
+
+    pub fn default_locale(&self) -> &str {
+        &self.default_locale
+    }
+
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        self.locales.keys().map(String::as_str)
+    }
+
+    // A plain message with its placeholders filled in from `args`.
+    pub fn text(&self, locale: &str, key: &str, args: &[(&str, Value)]) -> Result<String, MessageError> {
+        match self.find(locale, key)? {
+            (_, Message::Text(template)) => render(key, template, values(args)),
+            (_, Message::Plural(_)) => Err(MessageError::NeedsCount { key: key.to_owned() }),
+        }
+    }
+
+    // The form of a plural message for `count`, which `args` can use as
+    // `{count}`. A plain message is fine too and is just filled in.
+    pub fn plural(&self, locale: &str, key: &str, count: i64, args: &[(&str, Value)]) -> Result<String, MessageError> {
+        let mut values = values(args);
+        values.entry(String::from("count")).or_insert(Value::Int(count));
+        match self.find(locale, key)? {
+            (_, Message::Text(template)) => render(key, template, values),
+            (found_in, Message::Plural(forms)) => {
+                let category = category(language(found_in), count.unsigned_abs());
+                let template = forms.get(&category).or_else(|| forms.get(&Category::Other)).ok_or_else(|| {
+                    MessageError::MissingForm { locale: found_in.to_owned(), key: key.to_owned(), category }
+                })?;
+                render(key, template, values)
+            }
+        }
+    }
+
+    // Everything that does not line up between the default locale and the
+    // others, sorted by locale and key.
+    pub fn check(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        let empty = BTreeMap::new();
+        let reference = self.locales.get(&self.default_locale).unwrap_or(&empty);
+        for (locale, messages) in &self.locales {
+            let problem_key = |key: &str| (locale.clone(), key.to_owned());
+            for key in reference.keys().filter(|key| !messages.contains_key(*key)) {
+                let (locale, key) = problem_key(key);
+                problems.push(Problem::MissingKey { locale, key });
+            }
+            for key in messages.keys().filter(|key| !reference.contains_key(*key)) {
+                let (locale, key) = problem_key(key);
+                problems.push(Problem::UnusedKey { locale, key });
+            }
+
+            let needed = categories(language(locale));
+            for (key, message) in messages {
+                let Message::Plural(forms) = message else {
+                    continue;
+                };
+                for &category in needed.iter().filter(|c| !forms.contains_key(c)) {
+                    let (locale, key) = problem_key(key);
+                    problems.push(Problem::MissingForm { locale, key, category });
+                }
+                // `other` is the fallback for every language, so it is
+                // never out of place.
+                for &category in forms.keys().filter(|c| !needed.contains(c) && **c != Category::Other) {
+                    let (locale, key) = problem_key(key);
+                    problems.push(Problem::UnusedForm { locale, key, category });
+                }
+            }
+        }
+        problems.sort_by(|a, b| (a.locale(), a.key()).cmp(&(b.locale(), b.key())).then_with(|| a.cmp(b)));
+        problems
+    }
+
+    // The message and the locale it was found in: `locale` itself, then its
+    // language (`fr` for `fr-CA`), then the default locale.
+    fn find<'a>(&'a self, locale: &'a str, key: &str) -> Result<(&'a str, &'a Message), MessageError> {
+        [locale, language(locale), &self.default_locale]
+            .into_iter()
+            .find_map(|candidate| {
+                let (found_in, messages) = self.locales.get_key_value(candidate)?;
+                Some((found_in.as_str(), messages.get(key)?))
+            })
+            .ok_or_else(|| MessageError::UnknownKey { locale: locale.to_owned(), key: key.to_owned() })
+    }
+}
+
+// `pt` for `pt-BR` or `pt_BR`.
+fn language(locale: &str) -> &str {
+    locale.split(['-', '_']).next().unwrap_or(locale)
+}
+
+fn values(args: &[(&str, Value)]) -> Map {
+    args.iter().map(|(name, value)| (name.to_string(), value.clone())).collect()
+}
+
+fn render(key: &str, template: &Template, values: Map) -> Result<String, MessageError> {
+    template.render(&values).map_err(|error| MessageError::Template { key: key.to_owned(), error })
+}
+
+// The user's locale from the environment (`LC_ALL`, `LC_MESSAGES` or
+// `LANG`), as `fr-CA` for `fr_CA.UTF-8`. `C` and `POSIX` mean none.
+pub fn system_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"].into_iter().find_map(|name| {
+        let value = std::env::var(name).ok()?;
+        let locale = value.split(['.', '@']).next()?.replace('_', "-");
+        (!locale.is_empty() && locale != "C" && locale != "POSIX").then_some(locale)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EN: &str = "
+# English, the default.
+greeting = Hello, {name}!
+inbox[one] = You have {count} message
+inbox[other] = You have {count} messages
+farewell = Bye
+";
+
+    const RU: &str = "
+greeting = Привет, {name}!
+inbox[one] = У вас {count} сообщение
+inbox[few] = У вас {count} сообщения
+inbox[other] = У вас {count} сообщений
+inbox[two] = ?
+legacy = old
+";
+
+    fn catalog() -> Catalog {
+        let mut catalog = Catalog::new("en");
+        catalog.add("en", EN).unwrap();
+        catalog.add("ru", RU).unwrap();
+        catalog.add("fr", "greeting = Bonjour, {name} !\ninbox[one] = {count} message\ninbox[other] = {count} messages").unwrap();
+        catalog
+    }
+
+    #[test]
+    fn looks_up_with_fallback() {
+        let catalog = catalog();
+        let name = [("name", Value::from("Alice"))];
+        assert_eq!(catalog.text("en", "greeting", &name).unwrap(), "Hello, Alice!");
+        assert_eq!(catalog.text("fr-CA", "greeting", &name).unwrap(), "Bonjour, Alice !");
+        assert_eq!(catalog.text("fr", "farewell", &[]).unwrap(), "Bye");
+        assert_eq!(catalog.text("de", "farewell", &[]).unwrap(), "Bye");
+        assert_eq!(
+            catalog.text("fr", "nothing", &[]),
+            Err(MessageError::UnknownKey { locale: "fr".to_owned(), key: "nothing".to_owned() })
+        );
+        assert_eq!(catalog.text("en", "inbox", &[]).unwrap_err().to_string(), "`inbox` is a plural message and needs a count");
+        let error = catalog.text("en", "greeting", &[]).unwrap_err();
+        assert_eq!(error.to_string(), "in `greeting`: no value named `name` at line 1, column 9");
+    }
+
+    #[test]
+    fn picks_plural_forms() {
+        let catalog = catalog();
+        let inbox = |locale, n| catalog.plural(locale, "inbox", n, &[]).unwrap();
+        assert_eq!(inbox("en", 1), "You have 1 message");
+        assert_eq!(inbox("en", 0), "You have 0 messages");
+        assert_eq!(inbox("fr", 0), "0 message");
+        assert_eq!(inbox("ru", 21), "У вас 21 сообщение");
+        assert_eq!(inbox("ru", 3), "У вас 3 сообщения");
+        // `many` is missing in Russian, so `other` stands in.
+        assert_eq!(inbox("ru", 5), "У вас 5 сообщений");
+        assert_eq!(inbox("ja", 1), "You have 1 message");
+        assert_eq!(catalog.plural("en", "farewell", 2, &[]).unwrap(), "Bye");
+    }
+
+    #[test]
+    fn checks_locales_against_the_default() {
+        let problems: Vec<String> = catalog().check().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            problems,
+            [
+                "fr: `farewell` is not translated",
+                "ru: `farewell` is not translated",
+                "ru: `inbox` has no `many` form",
+                "ru: `inbox[two]` is never used",
+                "ru: `legacy` is not in the default locale",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_mistakes_in_catalogs() {
+        let error = |text| Catalog::new("en").add("en", text).unwrap_err().to_string();
+        assert_eq!(error("a = 1\nb"), "expected `key = message` at line 2, column 1 of the `en` catalog");
+        assert_eq!(error("  a[several] = x"), "unknown plural category `several` at line 1, column 5 of the `en` catalog");
+        assert_eq!(error("a b = x"), "invalid key `a b` at line 1, column 1 of the `en` catalog");
+        assert_eq!(error("a = x\na = y"), "`a` is defined twice at line 2, column 1 of the `en` catalog");
+        assert_eq!(error("a = x\na[one] = y"), "`a` is both a plain and a plural message at line 2, column 1 of the `en` catalog");
+        assert_eq!(error("a = Hi {name"), "`{` is never closed; use `{{` to write one at line 1, column 8 of the `en` catalog");
+        // Columns are counted in the line as written, escapes and all.
+        assert_eq!(error("a = One\\nTwo {name"), "`{` is never closed; use `{{` to write one at line 1, column 14 of the `en` catalog");
+        assert_eq!(error("a = é {#if}"), "expected a name at line 1, column 11 of the `en` catalog");
+        assert_eq!(error("a = \\n\\né {#if}"), "expected a name at line 1, column 15 of the `en` catalog");
+        assert!(Catalog::new("en").add("en", "a = two\\nlines").is_ok());
+    }
+}
@@ -0,0 +1,48 @@
+// Checks a directory of catalogs and lists what is missing or left over:
+//
+//     $ cargo run -- ../../18-std_library_types/channel/locales
+//     $ cargo run -- locales --default fr
+//
+// The default locale (`en` unless `--default` says otherwise) is the one
+// the others are compared with. Exits with 1 when there are problems.
+
+use std::process;
+
+use message_catalog::Catalog;
+
+fn fail(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(2);
+}
+
+fn main() {
+    let mut default_locale = String::from("en");
+    let mut dir = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--default" => default_locale = args.next().unwrap_or_else(|| fail("`--default` needs a locale")),
+            _ if dir.is_none() => dir = Some(arg),
+            _ => fail(&format!("unexpected argument `{}`", arg)),
+        }
+    }
+    let dir = dir.unwrap_or_else(|| fail("usage: message_catalog <dir> [--default <locale>]"));
+
+    let catalog = Catalog::load_dir(&dir, &default_locale).unwrap_or_else(|e| fail(&e.to_string()));
+    let locales: Vec<&str> = catalog.locales().collect();
+    if !locales.contains(&default_locale.as_str()) {
+        fail(&format!("there is no catalog for the default locale `{}` in {}", default_locale, dir));
+    }
+    println!("{} locales: {}", locales.len(), locales.join(", "));
+
+    let problems = catalog.check();
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    if problems.is_empty() {
+        println!("no problems found");
+    } else {
+        println!("{} problem{}", problems.len(), if problems.len() == 1 { "" } else { "s" });
+        process::exit(1);
+    }
+}
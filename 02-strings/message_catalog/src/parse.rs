@@ -0,0 +1,115 @@
+// Reads the messages of one locale from its text file:
+//
+//     # Lines starting with `#` are comments.
+//     greeting = Hello, {name}!
+//     inbox[one] = You have {count} message
+//     inbox[other] = You have {count} messages
+//
+// A key is made of letters, digits, `.`, `_` and `-`. A message is a
+// template, so `{name}`, `{name|default}`, `{#if}` and `{{` all work; `\n`
+// in a message is a line break.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+
+use template_engine::Template;
+
+use crate::plural::Category;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Message {
+    Text(Template),
+    Plural(BTreeMap<Category, Template>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    // A catalog file that could not be read.
+    Io { path: PathBuf, message: String },
+    // A mistake in a catalog, at a 1-based line and column.
+    Syntax { locale: String, line: usize, column: usize, message: String },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io { path, message } => write!(f, "cannot read {}: {}", path.display(), message),
+            LoadError::Syntax { locale, line, column, message } => {
+                write!(f, "{} at line {}, column {} of the `{}` catalog", message, line, column, locale)
+            }
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+pub(crate) fn parse(locale: &str, text: &str) -> Result<BTreeMap<String, Message>, LoadError> {
+    let mut messages = BTreeMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let error = |column: usize, message: String| LoadError::Syntax {
+            locale: locale.to_owned(),
+            line: index + 1,
+            column,
+            message,
+        };
+        // Columns count characters, from 1.
+        let column_of = |part: &str| line[..part.as_ptr() as usize - line.as_ptr() as usize].chars().count() + 1;
+
+        let content = line.trim();
+        if content.is_empty() || content.starts_with('#') {
+            continue;
+        }
+        let Some((name, value)) = content.split_once('=') else {
+            return Err(error(column_of(content), String::from("expected `key = message`")));
+        };
+        let (name, value) = (name.trim_end(), value.trim_start());
+
+        let (key, category) = match name.strip_suffix(']').and_then(|name| name.split_once('[')) {
+            Some((key, form)) => match Category::from_name(form) {
+                Some(category) => (key, Some(category)),
+                None => return Err(error(column_of(form), format!("unknown plural category `{}`", form))),
+            },
+            None => (name, None),
+        };
+        let valid = |c: char| c.is_alphanumeric() || matches!(c, '.' | '_' | '-');
+        if key.is_empty() || !key.chars().all(valid) {
+            return Err(error(column_of(name), format!("invalid key `{}`", name)));
+        }
+
+        let template = Template::parse(&value.replace("\\n", "\n"))
+            .map_err(|e| error(column_of(&value[source_position(value, e.position)..]), e.message))?;
+        let duplicate = || error(column_of(name), format!("`{}` is defined twice", name));
+        let mixed = || error(column_of(name), format!("`{}` is both a plain and a plural message", key));
+        match category {
+            None => match messages.get(key) {
+                Some(Message::Text(_)) => return Err(duplicate()),
+                Some(Message::Plural(_)) => return Err(mixed()),
+                None => {
+                    messages.insert(key.to_owned(), Message::Text(template));
+                }
+            },
+            Some(category) => match messages.entry(key.to_owned()).or_insert_with(|| Message::Plural(BTreeMap::new())) {
+                Message::Text(_) => return Err(mixed()),
+                Message::Plural(forms) => {
+                    if forms.insert(category, template).is_some() {
+                        return Err(duplicate());
+                    }
+                }
+            },
+        }
+    }
+    Ok(messages)
+}
+
+// Where byte `position` of a message with its `\n` escapes decoded was in
+// the message as written: one byte further for every escape before it.
+fn source_position(written: &str, position: usize) -> usize {
+    let escapes = written
+        .match_indices("\\n")
+        .enumerate()
+        .take_while(|&(count, (at, _))| at - count < position)
+        .count();
+    position + escapes
+}
@@ -0,0 +1,132 @@
+// Plural categories, after the CLDR plural rules:
+// https://www.unicode.org/cldr/charts/latest/supplemental/language_plural_rules.html
+//
+// Only whole numbers are handled, which is what counts of things are. A
+// language is known by the first part of its locale (`pt` for `pt-BR`);
+// languages not listed below get the English rule.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl Category {
+    pub const ALL: [Category; 6] =
+        [Category::Zero, Category::One, Category::Two, Category::Few, Category::Many, Category::Other];
+
+    // The name used in catalogs: `one` in `messages[one] = ...`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::Zero => "zero",
+            Category::One => "one",
+            Category::Two => "two",
+            Category::Few => "few",
+            Category::Many => "many",
+            Category::Other => "other",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Category> {
+        Category::ALL.into_iter().find(|c| c.name() == name)
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+// The category `n` falls in for `language`.
+pub fn category(language: &str, n: u64) -> Category {
+    let (last, last_two) = (n % 10, n % 100);
+    // 2, 3 and 4, but not 12, 13 and 14: the Slavic "few".
+    let slavic_few = (2..=4).contains(&last) && !(12..=14).contains(&last_two);
+    match language {
+        // No plural forms at all.
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" => Category::Other,
+        // Zero counts as singular.
+        "fr" | "pt" | "hi" | "bn" => {
+            if n <= 1 { Category::One } else { Category::Other }
+        }
+        "ru" | "uk" | "be" => {
+            if last == 1 && last_two != 11 {
+                Category::One
+            } else if slavic_few {
+                Category::Few
+            } else {
+                Category::Many
+            }
+        }
+        "pl" => {
+            if n == 1 {
+                Category::One
+            } else if slavic_few {
+                Category::Few
+            } else {
+                Category::Many
+            }
+        }
+        "cs" | "sk" => match n {
+            1 => Category::One,
+            2..=4 => Category::Few,
+            _ => Category::Other,
+        },
+        "ar" => match (n, last_two) {
+            (0, _) => Category::Zero,
+            (1, _) => Category::One,
+            (2, _) => Category::Two,
+            (_, 3..=10) => Category::Few,
+            (_, 11..=99) => Category::Many,
+            _ => Category::Other,
+        },
+        // English, German, Spanish, Italian, Dutch, the Nordic languages...
+        _ => {
+            if n == 1 { Category::One } else { Category::Other }
+        }
+    }
+}
+
+// The categories `language` uses for whole numbers, i.e. the plural forms a
+// catalog for it must have. The rules above only look at the last two
+// digits, so trying 0 to 199 finds them all.
+pub fn categories(language: &str) -> BTreeSet<Category> {
+    (0..200).map(|n| category(language, n)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(language: &str, numbers: &[u64]) -> Vec<&'static str> {
+        numbers.iter().map(|&n| category(language, n).name()).collect()
+    }
+
+    #[test]
+    fn follows_cldr() {
+        assert_eq!(names("en", &[0, 1, 2, 11, 21]), ["other", "one", "other", "other", "other"]);
+        assert_eq!(names("fr", &[0, 1, 2]), ["one", "one", "other"]);
+        assert_eq!(names("ru", &[1, 2, 5, 11, 12, 21, 22, 25, 111]), ["one", "few", "many", "many", "many", "one", "few", "many", "many"]);
+        assert_eq!(names("pl", &[1, 2, 5, 21, 22]), ["one", "few", "many", "many", "few"]);
+        assert_eq!(names("cs", &[1, 3, 5]), ["one", "few", "other"]);
+        assert_eq!(names("ar", &[0, 1, 2, 3, 11, 100, 103]), ["zero", "one", "two", "few", "many", "other", "few"]);
+        assert_eq!(names("ja", &[1, 2]), ["other", "other"]);
+    }
+
+    #[test]
+    fn lists_the_forms_a_language_needs() {
+        assert_eq!(categories("en"), BTreeSet::from([Category::One, Category::Other]));
+        assert_eq!(categories("ru"), BTreeSet::from([Category::One, Category::Few, Category::Many]));
+        assert_eq!(categories("ar").len(), 6);
+        assert_eq!(Category::from_name("few"), Some(Category::Few));
+        assert_eq!(Category::from_name("several"), None);
+    }
+}
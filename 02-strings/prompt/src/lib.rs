@@ -0,0 +1,255 @@
+// Interactive prompts that read typed answers.
+//
+// `stdin().read_line` hands back whatever was typed, newline included, and
+// leaves the rest (trimming, parsing, asking again) to the caller. A `Prompt`
+// does that part:
+//
+//     let mut prompt = Prompt::stdio();
+//     let age: u32 = prompt.ask("How old are you?")?;
+//     let name = prompt.ask_or("Name", String::from("anonymous"))?;
+//     if prompt.confirm("Save?", Some(true))? { ... }
+//
+// An answer that does not parse gets an error message and the question
+// again, until a valid one comes or the input runs out.
+//
+// The reader and writer are type parameters, so tests can script the input
+// with a `&[u8]` and check the transcript left in a `Vec<u8>`.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io::{self, BufRead, StdinLock, Stdout, Write};
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub enum PromptError {
+    // The input ended before a valid answer was given.
+    Eof,
+    Io(io::Error),
+}
+
+impl fmt::Display for PromptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PromptError::Eof => write!(f, "input ended before an answer was given"),
+            PromptError::Io(e) => write!(f, "could not read an answer: {}", e),
+        }
+    }
+}
+
+impl Error for PromptError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PromptError::Eof => None,
+            PromptError::Io(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for PromptError {
+    fn from(e: io::Error) -> PromptError {
+        PromptError::Io(e)
+    }
+}
+
+pub struct Prompt<R, W> {
+    input: R,
+    output: W,
+}
+
+impl Prompt<StdinLock<'static>, Stdout> {
+    // Questions on standard output, answers from standard input.
+    pub fn stdio() -> Self {
+        Prompt::new(io::stdin().lock(), io::stdout())
+    }
+}
+
+impl<R: BufRead, W: Write> Prompt<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Prompt { input, output }
+    }
+
+    // Gives back the reader and the writer, e.g. to look at what was written.
+    pub fn into_inner(self) -> (R, W) {
+        (self.input, self.output)
+    }
+
+    // Ask until the answer parses as a `T`.
+    pub fn ask<T>(&mut self, question: &str) -> Result<T, PromptError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.ask_until(question, None, |answer| answer.parse().map_err(|e: T::Err| e.to_string()))
+    }
+
+    // Like `ask`, but an empty answer picks `default`, which the question
+    // shows in brackets: `Name [anonymous]: `.
+    pub fn ask_or<T>(&mut self, question: &str, default: T) -> Result<T, PromptError>
+    where
+        T: FromStr + Display,
+        T::Err: Display,
+    {
+        let shown = default.to_string();
+        let mut default = Some(default);
+        self.ask_until(question, Some(&shown), |answer| {
+            if answer.is_empty()
+                && let Some(default) = default.take()
+            {
+                return Ok(default);
+            }
+            answer.parse().map_err(|e: T::Err| e.to_string())
+        })
+    }
+
+    // Like `ask`, with a check on the parsed value as well. `check` returns
+    // the message to show when the value is not acceptable.
+    pub fn ask_valid<T, F>(&mut self, question: &str, check: F) -> Result<T, PromptError>
+    where
+        T: FromStr,
+        T::Err: Display,
+        F: Fn(&T) -> Result<(), String>,
+    {
+        self.ask_until(question, None, |answer| {
+            let value = answer.parse().map_err(|e: T::Err| e.to_string())?;
+            check(&value).map(|()| value)
+        })
+    }
+
+    // A yes/no question: `y`, `yes`, `n` or `no` in any case. With a
+    // default, an empty answer picks it and the hint shows which one it is:
+    // `[Y/n]` or `[y/N]`.
+    pub fn confirm(&mut self, question: &str, default: Option<bool>) -> Result<bool, PromptError> {
+        let hint = match default {
+            Some(true) => "Y/n",
+            Some(false) => "y/N",
+            None => "y/n",
+        };
+        self.ask_until(question, Some(hint), |answer| match answer.to_lowercase().as_str() {
+            "y" | "yes" => Ok(true),
+            "n" | "no" => Ok(false),
+            "" => default.ok_or_else(|| String::from("please answer yes or no")),
+            _ => Err(String::from("please answer yes or no")),
+        })
+    }
+
+    // A numbered menu. The answer is the number of an option or its text
+    // (in any case); the index of the chosen option is returned.
+    pub fn choose<S: Display>(&mut self, question: &str, options: &[S]) -> Result<usize, PromptError> {
+        writeln!(self.output, "{}", question)?;
+        for (i, option) in options.iter().enumerate() {
+            writeln!(self.output, "  {}) {}", i + 1, option)?;
+        }
+        let range = format!("1-{}", options.len());
+        self.ask_until("Choice", Some(&range), |answer| {
+            if let Ok(n) = answer.parse::<usize>() {
+                return n.checked_sub(1).filter(|&i| i < options.len()).ok_or_else(|| {
+                    format!("{} is not one of the options {}", n, range)
+                });
+            }
+            options
+                .iter()
+                .position(|option| option.to_string().eq_ignore_ascii_case(answer))
+                .ok_or_else(|| format!("`{}` is not one of the options", answer))
+        })
+    }
+
+    // The loop behind every question: print it, read a line, and hand the
+    // trimmed answer to `parse` until it accepts one. Its error message is
+    // printed before asking again.
+    fn ask_until<T>(
+        &mut self,
+        question: &str,
+        hint: Option<&str>,
+        mut parse: impl FnMut(&str) -> Result<T, String>,
+    ) -> Result<T, PromptError> {
+        loop {
+            match hint {
+                Some(hint) => write!(self.output, "{} [{}]: ", question, hint)?,
+                None => write!(self.output, "{} ", question)?,
+            }
+            self.output.flush()?;
+
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                return Err(PromptError::Eof);
+            }
+            match parse(line.trim()) {
+                Ok(value) => return Ok(value),
+                Err(message) => writeln!(self.output, "{}; try again.", message)?,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Answer `script`, one line per answer, and return the result and what
+    // was written.
+    fn scripted<T>(
+        script: &str,
+        ask: impl FnOnce(&mut Prompt<&[u8], Vec<u8>>) -> Result<T, PromptError>,
+    ) -> (Result<T, PromptError>, String) {
+        let mut prompt = Prompt::new(script.as_bytes(), Vec::new());
+        let result = ask(&mut prompt);
+        let (_, output) = prompt.into_inner();
+        (result, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn asks_until_the_answer_parses() {
+        let (age, transcript) = scripted("forty\n-3\n  42 \n", |p| p.ask::<u32>("Age?"));
+        assert_eq!(age.unwrap(), 42);
+        assert_eq!(
+            transcript,
+            "Age? invalid digit found in string; try again.\nAge? invalid digit found in string; try again.\nAge? "
+        );
+    }
+
+    #[test]
+    fn uses_defaults_and_checks() {
+        let (name, transcript) = scripted("\n", |p| p.ask_or("Name", String::from("anonymous")));
+        assert_eq!(name.unwrap(), "anonymous");
+        assert_eq!(transcript, "Name [anonymous]: ");
+        let (port, _) = scripted("8080\n", |p| p.ask_or("Port", 80u16));
+        assert_eq!(port.unwrap(), 8080);
+
+        let even = |n: &i32| if n % 2 == 0 { Ok(()) } else { Err(format!("{} is odd", n)) };
+        let (n, transcript) = scripted("3\n4\n", |p| p.ask_valid("Even number:", even));
+        assert_eq!(n.unwrap(), 4);
+        assert_eq!(transcript, "Even number: 3 is odd; try again.\nEven number: ");
+    }
+
+    #[test]
+    fn confirms() {
+        assert!(scripted("YES\n", |p| p.confirm("Save?", None)).0.unwrap());
+        assert!(!scripted("\n", |p| p.confirm("Save?", Some(false))).0.unwrap());
+        let (answer, transcript) = scripted("maybe\n\nn\n", |p| p.confirm("Save?", None));
+        assert!(!answer.unwrap());
+        assert_eq!(
+            transcript,
+            "Save? [y/n]: please answer yes or no; try again.\nSave? [y/n]: please answer yes or no; try again.\nSave? [y/n]: "
+        );
+    }
+
+    #[test]
+    fn picks_from_a_menu() {
+        let colors = ["Red", "Green", "Blue"];
+        let (choice, transcript) = scripted("4\nblue\n", |p| p.choose("Pick a color", &colors));
+        assert_eq!(choice.unwrap(), 2);
+        assert_eq!(
+            transcript,
+            "Pick a color\n  1) Red\n  2) Green\n  3) Blue\nChoice [1-3]: 4 is not one of the options 1-3; try again.\nChoice [1-3]: "
+        );
+        assert_eq!(scripted("1\n", |p| p.choose("Pick", &colors)).0.unwrap(), 0);
+        assert!(scripted("0\n", |p| p.choose("Pick", &colors)).0.is_err());
+    }
+
+    #[test]
+    fn stops_at_the_end_of_input() {
+        let (age, _) = scripted("x\n", |p| p.ask::<u8>("Age?"));
+        assert!(matches!(age, Err(PromptError::Eof)));
+        assert_eq!(PromptError::Eof.to_string(), "input ended before an answer was given");
+    }
+}
@@ -1,4 +1,9 @@
+use prompt::Prompt;
+use template_engine::{Map, Template, Value};
+
 const FORMAT: &str = "Value: {}";
+// A template can name its placeholders and be filled in at runtime.
+const TEMPLATE: &str = "Value: {value}{#if unit} {unit}{/if} ({source|unknown})";
 
 
 fn main() {
@@ -28,6 +33,26 @@ fn main() {
     println!("{}", message);
     // Output: Value: 123
 
+    // Or parse a template once and render it as often as needed.
+    let template = Template::parse(TEMPLATE).unwrap();
+    let mut values = Map::new();
+    for (value, unit) in [(123, ""), (42, "km")] {
+        values.insert("value".to_owned(), value.into());
+        values.insert("unit".to_owned(), unit.into());
+        println!("{}", template.render(&values).unwrap());
+    }
+    // Output: Value: 123 (unknown)
+    //         Value: 42 km (unknown)
+
+    let report = Template::parse("{#for line in lines}{loop.index}. {line}\n{/for}").unwrap();
+    values.insert("lines".to_owned(), Value::from(vec!["static text", "runtime text"]));
+    print!("{}", report.render(&values).unwrap());
+
+    // Mistakes in a template are reported with where they are.
+    if let Err(e) = Template::parse("Value: {value") {
+        println!("{:#}", e);
+    }
+
 
     // =====================================
     // String Objects - Runtime
@@ -43,7 +68,13 @@ fn main() {
     println!("Type your name: ");  
     let mut input = String::new();
     let user_input = std::io::stdin().read_line(&mut input).expect("Failed to read line");
-    println!("You entered: {}", input);
+    println!("You entered: {} ({} bytes, newline included)", input.trim_end(), user_input);
+
+    // `Prompt` does the trimming, parsing and asking again.
+    let mut prompt = Prompt::stdio();
+    let name = prompt.ask_or("And your nickname?", String::from("none")).expect("Failed to read line");
+    let age: u32 = prompt.ask("How old are you?").expect("Failed to read line");
+    println!("Nickname: {}, age: {}", name, age);
 
     // =====================================
     // Coversion 
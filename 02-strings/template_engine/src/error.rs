@@ -0,0 +1,53 @@
+use std::error::Error;
+use std::fmt;
+
+// A mistake in a template, found either while parsing it (an unclosed `{`, an
+// `{/if}` without an `{#if}`) or while rendering it (a value that is missing
+// or of the wrong kind).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateError {
+    // Byte offset of the tag at fault, and the same place as a 1-based line
+    // and column (in characters) for humans.
+    pub position: usize,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    // The whole line the error is on, for `{:#}`.
+    pub source_line: String,
+}
+
+impl TemplateError {
+    pub(crate) fn new(source: &str, position: usize, message: impl Into<String>) -> TemplateError {
+        let before = &source[..position];
+        let line = before.matches('\n').count() + 1;
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        let end = source[position..].find('\n').map_or(source.len(), |i| position + i);
+        TemplateError {
+            position,
+            line,
+            column: source[start..position].chars().count() + 1,
+            message: message.into(),
+            source_line: source[start..end].to_owned(),
+        }
+    }
+}
+
+// `{}` is one line; `{:#}` adds the line of the template with a caret under
+// the offending column:
+//
+//     no value named `nmae` at line 1, column 7
+//       |
+//     1 | Hello {nmae}!
+//       |       ^
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at line {}, column {}", self.message, self.line, self.column)?;
+        if f.alternate() {
+            let gutter = " ".repeat(self.line.to_string().len());
+            write!(f, "\n{} |\n{} | {}\n{} | {:>col$}", gutter, self.line, self.source_line, gutter, "^", col = self.column)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for TemplateError {}
@@ -0,0 +1,179 @@
+// A string template engine for text that is only known at runtime.
+//
+// `println!` needs its format string at compile time, so a `const FORMAT`
+// or a template read from a file cannot be used with it. A `Template` is
+// parsed once and can then be rendered any number of times:
+//
+//     let template = Template::parse("Hello {name|anonymous}!")?;
+//     template.render(&values)?;
+//
+// The syntax:
+//
+//     {name}                       the value called `name`
+//     {user.name}                  the `name` entry of the map `user`
+//     {name|anonymous}             `anonymous` if there is no `name`
+//     {{ and }}                    a literal `{` and `}`
+//     {#if admin}..{#else}..{/if}  the first part if `admin` is set and not
+//                                  false, zero or empty; `{#if !admin}` too
+//     {#for tag in tags}..{/for}   the middle once per item of the list
+//                                  `tags`, with `tag` set to the item and
+//                                  `loop.index`, `loop.first`, `loop.last`
+//
+// Blocks nest up to `MAX_DEPTH` deep.
+//
+// Errors, in the template itself or in the values it gets, say where the
+// template went wrong: `{}` gives the line and column and `{:#}` also shows
+// the line with a caret under the spot.
+
+mod error;
+mod parse;
+mod render;
+mod value;
+
+use std::str::FromStr;
+
+pub use error::TemplateError;
+pub use parse::MAX_DEPTH;
+pub use value::{Map, Value};
+
+use parse::Node;
+use render::Renderer;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    source: String,
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Template, TemplateError> {
+        Ok(Template { source: source.to_owned(), nodes: parse::parse(source)? })
+    }
+
+    // The text the template was parsed from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn render(&self, values: &Map) -> Result<String, TemplateError> {
+        let mut out = String::new();
+        let mut renderer = Renderer { source: &self.source, root: values, scopes: Vec::new() };
+        renderer.render(&self.nodes, &mut out)?;
+        Ok(out)
+    }
+}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    fn from_str(s: &str) -> Result<Template, TemplateError> {
+        Template::parse(s)
+    }
+}
+
+// Parse `template` and render it in one go.
+pub fn render(template: &str, values: &Map) -> Result<String, TemplateError> {
+    Template::parse(template)?.render(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> Map {
+        let Value::Map(map) = Value::map([
+            ("name", "Alice".into()),
+            ("count", 3.into()),
+            ("admin", true.into()),
+            ("tags", vec!["rust", "fmt"].into()),
+            ("empty", Vec::<Value>::new().into()),
+            ("user", Value::map([("name", "Bob".into()), ("score", 9.5.into())])),
+            ("people", vec![
+                Value::map([("name", "Ann".into()), ("age", 31.into())]),
+                Value::map([("name", "Ben".into()), ("age", 27.into())]),
+            ].into()),
+        ]) else {
+            unreachable!()
+        };
+        map
+    }
+
+    fn ok(template: &str) -> String {
+        render(template, &values()).unwrap()
+    }
+
+    fn err(template: &str) -> TemplateError {
+        render(template, &values()).unwrap_err()
+    }
+
+    #[test]
+    fn fills_in_values() {
+        assert_eq!(ok("Hello {name}, you have {count} messages"), "Hello Alice, you have 3 messages");
+        assert_eq!(ok("{user.name} scored {user.score}"), "Bob scored 9.5");
+        assert_eq!(ok("{ name }"), "Alice");
+        assert_eq!(ok("{nobody|anonymous} and {name|anonymous}"), "anonymous and Alice");
+        assert_eq!(ok("{user.email|no email}"), "no email");
+        assert_eq!(ok("{{name}} is {{{name}}}"), "{name} is {Alice}");
+        assert_eq!(ok("no tags at all ✓"), "no tags at all ✓");
+    }
+
+    #[test]
+    fn branches_and_loops() {
+        assert_eq!(ok("{#if admin}admin{#else}user{/if}"), "admin");
+        assert_eq!(ok("{#if !admin}user{/if}"), "");
+        assert_eq!(ok("{#if empty}some{#else}none{/if} {#if nobody}?{/if}"), "none ");
+        assert_eq!(ok("{#for tag in tags}#{tag}{#if !loop.last}, {/if}{/for}"), "#rust, #fmt");
+        assert_eq!(ok("{#for p in people}{loop.index}:{p.name}({p.age}) {/for}"), "0:Ann(31) 1:Ben(27) ");
+        assert_eq!(ok("[{#for x in empty}{x}{/for}]"), "[]");
+        assert_eq!(
+            ok("{#for a in tags}{#for b in tags}{#if loop.first}{a}{/if}{b} {/for}{/for}"),
+            "rustrust fmt fmtrust fmt "
+        );
+    }
+
+    #[test]
+    fn compiles_once_renders_many() {
+        let template: Template = "{name} has {count}".parse().unwrap();
+        for (name, count) in [("Ann", 1), ("Ben", 2)] {
+            let values = Map::from([("name".to_owned(), name.into()), ("count".to_owned(), count.into())]);
+            assert_eq!(template.render(&values).unwrap(), format!("{} has {}", name, count));
+        }
+        assert_eq!(template.source(), "{name} has {count}");
+    }
+
+    #[test]
+    fn points_at_parse_errors() {
+        let e = err("Hello {name");
+        assert_eq!((e.line, e.column), (1, 7));
+        assert_eq!(e.to_string(), "`{` is never closed; use `{{` to write one at line 1, column 7");
+        assert_eq!(err("a }").column, 3);
+        assert_eq!(err("{#if admin}yes").message, "`{#if}` is never closed with `{/if}`");
+        assert_eq!(err("{#for t in tags}{/if}").message, "`{/if}` without an `{#if}`");
+        assert_eq!(err("{#if a}{#else}{#else}{/if}").column, 15);
+        assert_eq!(err("{#for t of tags}").message, "expected `{#for name in list}`");
+        assert_eq!(err("{#each tags}").message, "unknown tag `#each`");
+        assert_eq!(err("{user..name}").message, "invalid name `user..name`");
+        assert_eq!(err("{}").message, "expected a name");
+
+        let nested = |n: usize| format!("{}yes{}", "{#if admin}".repeat(n), "{/if}".repeat(n));
+        assert_eq!(ok(&nested(MAX_DEPTH)), "yes");
+        let e = err(&nested(200_000));
+        assert_eq!((e.message.as_str(), e.position), ("blocks are nested too deeply", MAX_DEPTH * "{#if admin}".len()));
+        let e = err(&"{#for t in tags}".repeat(MAX_DEPTH + 1));
+        assert_eq!((e.message.as_str(), e.column), ("blocks are nested too deeply", MAX_DEPTH * 16 + 1));
+    }
+
+    #[test]
+    fn points_at_render_errors() {
+        let e = err("line one\n  Hi {nmae}!");
+        assert_eq!((e.line, e.column, e.position), (2, 7, 15));
+        assert_eq!(
+            format!("{:#}", e),
+            "no value named `nmae` at line 2, column 7\n  |\n2 |   Hi {nmae}!\n  |       ^"
+        );
+        assert_eq!(err("{#if x}{/if}{tags}").message, "`tags` is a list and cannot be printed");
+        assert_eq!(err("{#for c in   name}{/for}").message, "`name` is a string, not a list");
+        assert_eq!(err("{#for c in   name}{/for}").column, 14);
+        assert_eq!(err("{#for c in nothing}{/for}").message, "no value named `nothing`");
+    }
+}
@@ -0,0 +1,234 @@
+// Parser for the template syntax:
+//
+//   template := ( text | '{{' | '}}' | tag ) *
+//   tag      := '{' path [ '|' default ] '}'
+//             | '{#if' [ '!' ] path '}' template [ '{#else}' template ] '{/if}'
+//             | '{#for' name 'in' path '}' template '{/for}'
+//   path     := name ( '.' name ) *
+//   name     := ( letter | digit | '_' ) +
+//
+// Spaces inside a tag are ignored, so `{ name | anonymous }` is the same as
+// `{name|anonymous}`.
+
+use crate::error::TemplateError;
+
+// Blocks nested deeper than this are rejected instead of overflowing the
+// stack, here or while rendering.
+pub const MAX_DEPTH: usize = 128;
+
+// A name like `user.name`, split at the dots, with where it was written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Path {
+    pub(crate) names: Vec<String>,
+    pub(crate) position: usize,
+}
+
+impl Path {
+    pub(crate) fn dotted(&self) -> String {
+        self.names.join(".")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Node {
+    Text(String),
+    Value { path: Path, default: Option<String> },
+    If { negated: bool, path: Path, then: Vec<Node>, otherwise: Vec<Node> },
+    For { name: String, list: Path, body: Vec<Node> },
+}
+
+// A tag between braces, classified but not yet nested.
+enum Tag<'a> {
+    Value { path: Path, default: Option<&'a str> },
+    If { negated: bool, path: Path },
+    Else,
+    EndIf,
+    For { name: String, list: Path },
+    EndFor,
+}
+
+// How a run of nodes came to an end.
+enum Stop {
+    Eof,
+    Else(usize),
+    EndIf(usize),
+    EndFor(usize),
+}
+
+pub(crate) fn parse(source: &str) -> Result<Vec<Node>, TemplateError> {
+    let mut parser = Parser { src: source, pos: 0, depth: 0 };
+    match parser.nodes()? {
+        (nodes, Stop::Eof) => Ok(nodes),
+        (_, stray) => Err(parser.stray(stray)),
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    // `{#if}` and `{#for}` blocks open around `pos`.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, position: usize, message: &str) -> TemplateError {
+        TemplateError::new(self.src, position, message)
+    }
+
+    // A closing tag that does not belong where it is.
+    fn stray(&self, end: Stop) -> TemplateError {
+        match end {
+            Stop::Else(pos) => self.error(pos, "`{#else}` outside of an `{#if}`"),
+            Stop::EndIf(pos) => self.error(pos, "`{/if}` without an `{#if}`"),
+            Stop::EndFor(pos) => self.error(pos, "`{/for}` without a `{#for}`"),
+            Stop::Eof => self.error(self.src.len(), "unexpected end of template"),
+        }
+    }
+
+    // Where `part`, a slice of the template, starts in it.
+    fn offset(&self, part: &str) -> usize {
+        part.as_ptr() as usize - self.src.as_ptr() as usize
+    }
+
+    // Nodes up to the end of the template or the next `{#else}`, `{/if}` or
+    // `{/for}`, which the caller checks is the one it expects.
+    fn nodes(&mut self) -> Result<(Vec<Node>, Stop), TemplateError> {
+        let mut nodes = Vec::new();
+        let mut text = String::new();
+        let end = loop {
+            let rest = &self.src[self.pos..];
+            let Some(c) = rest.chars().next() else {
+                break Stop::Eof;
+            };
+            if rest.starts_with("{{") || rest.starts_with("}}") {
+                text.push(c);
+                self.pos += 2;
+                continue;
+            }
+            match c {
+                '}' => return Err(self.error(self.pos, "unmatched `}`; use `}}` to write one")),
+                '{' => {}
+                _ => {
+                    text.push(c);
+                    self.pos += c.len_utf8();
+                    continue;
+                }
+            }
+
+            let start = self.pos;
+            let tag = self.tag()?;
+            if !text.is_empty() {
+                nodes.push(Node::Text(std::mem::take(&mut text)));
+            }
+            match tag {
+                Tag::Value { path, default } => {
+                    nodes.push(Node::Value { path, default: default.map(str::to_owned) });
+                }
+                Tag::If { .. } | Tag::For { .. } if self.depth == MAX_DEPTH => {
+                    return Err(self.error(start, "blocks are nested too deeply"));
+                }
+                Tag::If { negated, path } => {
+                    self.depth += 1;
+                    let block = self.if_block(start, negated, path);
+                    self.depth -= 1;
+                    nodes.push(block?);
+                }
+                Tag::For { name, list } => {
+                    self.depth += 1;
+                    let body = self.nodes();
+                    self.depth -= 1;
+                    let (body, end) = body?;
+                    match end {
+                        Stop::EndFor(_) => nodes.push(Node::For { name, list, body }),
+                        Stop::Eof => return Err(self.error(start, "`{#for}` is never closed with `{/for}`")),
+                        stray => return Err(self.stray(stray)),
+                    }
+                }
+                Tag::Else => break Stop::Else(start),
+                Tag::EndIf => break Stop::EndIf(start),
+                Tag::EndFor => break Stop::EndFor(start),
+            }
+        };
+        if !text.is_empty() {
+            nodes.push(Node::Text(text));
+        }
+        Ok((nodes, end))
+    }
+
+    // The rest of an `{#if}` after its opening tag at `start`.
+    fn if_block(&mut self, start: usize, negated: bool, path: Path) -> Result<Node, TemplateError> {
+        let unclosed = |parser: &Parser| parser.error(start, "`{#if}` is never closed with `{/if}`");
+        let (then, end) = self.nodes()?;
+        let otherwise = match end {
+            Stop::EndIf(_) => Vec::new(),
+            Stop::Else(_) => match self.nodes()? {
+                (otherwise, Stop::EndIf(_)) => otherwise,
+                (_, Stop::Else(pos)) => return Err(self.error(pos, "a second `{#else}` in the same `{#if}`")),
+                (_, Stop::Eof) => return Err(unclosed(self)),
+                (_, stray) => return Err(self.stray(stray)),
+            },
+            Stop::Eof => return Err(unclosed(self)),
+            stray => return Err(self.stray(stray)),
+        };
+        Ok(Node::If { negated, path, then, otherwise })
+    }
+
+    // The tag starting at the `{` under `self.pos`.
+    fn tag(&mut self) -> Result<Tag<'a>, TemplateError> {
+        let start = self.pos;
+        let src = self.src;
+        let Some(len) = src[start + 1..].find(['{', '}']).filter(|&i| src[start + 1 + i..].starts_with('}')) else {
+            return Err(self.error(start, "`{` is never closed; use `{{` to write one"));
+        };
+        let inner = &src[start + 1..start + 1 + len];
+        self.pos = start + len + 2;
+        let content = inner.trim();
+        let at = self.offset(content);
+
+        let words: Vec<&str> = content.split_whitespace().collect();
+        match words.as_slice() {
+            ["#else"] => Ok(Tag::Else),
+            ["/if"] => Ok(Tag::EndIf),
+            ["/for"] => Ok(Tag::EndFor),
+            ["#if", ..] => {
+                let condition = content["#if".len()..].trim_start();
+                let (negated, path) = match condition.strip_prefix('!') {
+                    Some(path) => (true, path.trim_start()),
+                    None => (false, condition),
+                };
+                Ok(Tag::If { negated, path: self.path(path)? })
+            }
+            ["#for", name, "in", list] => {
+                let name = self.path(name)?;
+                if name.names.len() > 1 {
+                    return Err(self.error(name.position, "the loop variable must be a plain name"));
+                }
+                Ok(Tag::For { name: name.dotted(), list: self.path(list)? })
+            }
+            ["#for", ..] => Err(self.error(at, "expected `{#for name in list}`")),
+            [first, ..] if first.starts_with(['#', '/']) => {
+                Err(self.error(at, &format!("unknown tag `{}`", first)))
+            }
+            _ => {
+                let (path, default) = match content.split_once('|') {
+                    Some((path, default)) => (path.trim_end(), Some(default.trim_start())),
+                    None => (content, None),
+                };
+                Ok(Tag::Value { path: self.path(path)?, default })
+            }
+        }
+    }
+
+    fn path(&self, text: &str) -> Result<Path, TemplateError> {
+        let position = self.offset(text);
+        let valid = |name: &str| !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if text.is_empty() {
+            return Err(self.error(position, "expected a name"));
+        }
+        let names: Vec<String> = text.split('.').map(str::to_owned).collect();
+        if !names.iter().all(|name| valid(name)) {
+            return Err(self.error(position, &format!("invalid name `{}`", text)));
+        }
+        Ok(Path { names, position })
+    }
+}
@@ -0,0 +1,96 @@
+// Applies a parsed template to a map of values.
+
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use crate::error::TemplateError;
+use crate::parse::{Node, Path};
+use crate::value::{Map, Value};
+
+pub(crate) struct Renderer<'a> {
+    pub(crate) source: &'a str,
+    pub(crate) root: &'a Map,
+    // The names a `{#for}` binds, innermost last: the item itself, and
+    // `loop` with `index`, `first` and `last`.
+    pub(crate) scopes: Vec<(&'a str, Scoped<'a>)>,
+}
+
+pub(crate) enum Scoped<'a> {
+    Item(&'a Value),
+    Loop { index: usize, len: usize },
+}
+
+impl<'a> Renderer<'a> {
+    pub(crate) fn render(&mut self, nodes: &'a [Node], out: &mut String) -> Result<(), TemplateError> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => out.push_str(text),
+                Node::Value { path, default } => match (self.lookup(path).as_deref(), default) {
+                    (Some(value @ (Value::List(_) | Value::Map(_))), _) => {
+                        let message = format!("`{}` is {} and cannot be printed", path.dotted(), value.kind());
+                        return Err(self.error(path, &message));
+                    }
+                    (Some(value), _) => write!(out, "{}", value).unwrap(),
+                    (None, Some(default)) => out.push_str(default),
+                    (None, None) => return Err(self.missing(path)),
+                },
+                Node::If { negated, path, then, otherwise } => {
+                    let truthy = self.lookup(path).is_some_and(|value| value.is_truthy());
+                    self.render(if truthy != *negated { then } else { otherwise }, out)?;
+                }
+                Node::For { name, list, body } => {
+                    let items = match self.lookup(list) {
+                        Some(Cow::Borrowed(Value::List(items))) => items,
+                        Some(value) => {
+                            let message = format!("`{}` is {}, not a list", list.dotted(), value.kind());
+                            return Err(self.error(list, &message));
+                        }
+                        None => return Err(self.missing(list)),
+                    };
+                    for (index, item) in items.iter().enumerate() {
+                        self.scopes.push(("loop", Scoped::Loop { index, len: items.len() }));
+                        self.scopes.push((name, Scoped::Item(item)));
+                        let rendered = self.render(body, out);
+                        self.scopes.truncate(self.scopes.len() - 2);
+                        rendered?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // The value behind `user.name`: `user` from the innermost scope that has
+    // it, or else from the root map, then `name` inside that. The fields of
+    // `loop` are made up on the spot; everything else is borrowed.
+    fn lookup(&self, path: &Path) -> Option<Cow<'a, Value>> {
+        let (first, rest) = path.names.split_first()?;
+        let mut value = match self.scopes.iter().rev().find(|(name, _)| name == first) {
+            Some((_, Scoped::Item(value))) => *value,
+            Some((_, Scoped::Loop { index, len })) => {
+                return match rest {
+                    [field] if field == "index" => Some(Cow::Owned(Value::Int(*index as i64))),
+                    [field] if field == "first" => Some(Cow::Owned(Value::Bool(*index == 0))),
+                    [field] if field == "last" => Some(Cow::Owned(Value::Bool(index + 1 == *len))),
+                    _ => None,
+                };
+            }
+            None => self.root.get(first)?,
+        };
+        for name in rest {
+            match value {
+                Value::Map(entries) => value = entries.get(name)?,
+                _ => return None,
+            }
+        }
+        Some(Cow::Borrowed(value))
+    }
+
+    fn error(&self, path: &Path, message: &str) -> TemplateError {
+        TemplateError::new(self.source, path.position, message)
+    }
+
+    fn missing(&self, path: &Path) -> TemplateError {
+        self.error(path, &format!("no value named `{}`", path.dotted()))
+    }
+}
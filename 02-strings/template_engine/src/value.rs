@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+// The values a template is rendered against, by name.
+pub type Map = BTreeMap<String, Value>;
+
+// What a placeholder can refer to. Text and numbers print as themselves;
+// lists are for `{#for}` and maps for dotted names like `{user.name}`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    List(Vec<Value>),
+    Map(Map),
+}
+
+impl Value {
+    // `Value::map([("name", "Alice".into()), ("admin", true.into())])`.
+    pub fn map<'a>(entries: impl IntoIterator<Item = (&'a str, Value)>) -> Value {
+        Value::Map(entries.into_iter().map(|(k, v)| (k.to_owned(), v)).collect())
+    }
+
+    // Whether `{#if}` takes its first branch: `false`, zero and anything
+    // empty do not.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Bool(b) => *b,
+            Value::Int(n) => *n != 0,
+            Value::Float(x) => *x != 0.0,
+            Value::Str(s) => !s.is_empty(),
+            Value::List(items) => !items.is_empty(),
+            Value::Map(entries) => !entries.is_empty(),
+        }
+    }
+
+    // For error messages: "`tags` is a list".
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Value::Bool(_) => "a bool",
+            Value::Int(_) | Value::Float(_) => "a number",
+            Value::Str(_) => "a string",
+            Value::List(_) => "a list",
+            Value::Map(_) => "a map",
+        }
+    }
+}
+
+// How a value prints inside a template. Lists and maps have no text of their
+// own; the renderer reports them as an error before getting here, and they
+// print as nothing.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Bool(b) => b.fmt(f),
+            Value::Int(n) => n.fmt(f),
+            Value::Float(x) => x.fmt(f),
+            Value::Str(s) => s.fmt(f),
+            Value::List(_) | Value::Map(_) => Ok(()),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(v: $ty) -> Self {
+                    Value::$variant(v.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from! {
+    bool => Bool, i8 => Int, i16 => Int, i32 => Int, i64 => Int, u8 => Int, u16 => Int, u32 => Int,
+    f32 => Float, f64 => Float, String => Str, &str => Str, Map => Map,
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(items: Vec<T>) -> Self {
+        Value::List(items.into_iter().map(Into::into).collect())
+    }
+}
@@ -0,0 +1,417 @@
+// A two-dimensional grid stored as one flat `Vec`, row after row.
+//
+// Cell `(row, col)` lives at index `row * cols + col`, so a row is a plain
+// contiguous slice and a column is every `cols`-th element. Views into the
+// grid (a column, a rectangle, a sliding window) borrow those cells rather
+// than copying them.
+//
+//     let grid = Grid::from_rows([[1, 2, 3], [4, 5, 6]])?;
+//     grid[(1, 2)]                      6
+//     grid.row(0)                       [1, 2, 3]
+//     grid.column(1).sum::<i32>()       7
+//     grid.view(0..2, 1..3)             2 3 / 5 6
+//     grid.transpose()                  1 4 / 2 5 / 3 6
+
+use std::error::Error;
+use std::fmt;
+use std::ops::{Index, IndexMut, Range};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    // The number of cells given does not match `rows * cols`.
+    WrongLength { rows: usize, cols: usize, len: usize },
+    // A row of a different length from the first.
+    Ragged { row: usize, expected: usize, found: usize },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::WrongLength { rows, cols, len } => {
+                write!(f, "a {}x{} grid needs {} cells, got {}", rows, cols, rows * cols, len)
+            }
+            GridError::Ragged { row, expected, found } => {
+                write!(f, "row {} has {} cells, expected {}", row, found, expected)
+            }
+        }
+    }
+}
+
+impl Error for GridError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    // `rows` by `cols` copies of `value`.
+    pub fn new(rows: usize, cols: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid { cells: vec![value; rows * cols], rows, cols }
+    }
+
+    // Cells given row after row.
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Result<Grid<T>, GridError> {
+        if cells.len() != rows * cols {
+            return Err(GridError::WrongLength { rows, cols, len: cells.len() });
+        }
+        Ok(Grid { cells, rows, cols })
+    }
+
+    // Each cell computed from its position.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Grid<T> {
+        let cells = (0..rows * cols).map(|i| f(i / cols, i % cols)).collect();
+        Grid { cells, rows, cols }
+    }
+
+    // One item per row, all of the same length.
+    pub fn from_rows<R>(rows: impl IntoIterator<Item = R>) -> Result<Grid<T>, GridError>
+    where
+        R: IntoIterator<Item = T>,
+    {
+        let mut cells = Vec::new();
+        let (mut count, mut cols) = (0, 0);
+        for row in rows {
+            let before = cells.len();
+            cells.extend(row);
+            let found = cells.len() - before;
+            if count == 0 {
+                cols = found;
+            } else if found != cols {
+                return Err(GridError::Ragged { row: count, expected: cols, found });
+            }
+            count += 1;
+        }
+        Ok(Grid { cells, rows: count, cols })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // `(rows, cols)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    fn index_of(&self, (row, col): (usize, usize)) -> Option<usize> {
+        (row < self.rows && col < self.cols).then_some(row * self.cols + col)
+    }
+
+    // The cell at `(row, col)`, or `None` outside the grid. Note that a
+    // flat index check alone would not do: `(0, cols)` is in bounds of the
+    // `Vec` but is really `(1, 0)`.
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: (usize, usize)) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    // All the cells, row after row.
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.cells
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.rows, "row {} out of range for a grid with {} rows", row, self.rows);
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(row < self.rows, "row {} out of range for a grid with {} rows", row, self.rows);
+        &mut self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn iter_rows(&self) -> impl ExactSizeIterator<Item = &[T]> + DoubleEndedIterator {
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    // The cells of a column, top to bottom.
+    pub fn column(&self, col: usize) -> impl ExactSizeIterator<Item = &T> + DoubleEndedIterator {
+        assert!(col < self.cols, "column {} out of range for a grid with {} columns", col, self.cols);
+        // A grid with no rows has no cells to start from.
+        self.cells.get(col..).unwrap_or_default().iter().step_by(self.cols)
+    }
+
+    // The rectangle of rows `rows` and columns `cols`. Like slicing, this
+    // panics when the ranges go past the edge of the grid.
+    pub fn view(&self, rows: Range<usize>, cols: Range<usize>) -> View<'_, T> {
+        assert!(
+            rows.start <= rows.end && rows.end <= self.rows && cols.start <= cols.end && cols.end <= self.cols,
+            "view {:?} x {:?} out of range for a {}x{} grid",
+            rows,
+            cols,
+            self.rows,
+            self.cols
+        );
+        View { grid: self, rows, cols }
+    }
+
+    // The whole grid as a view.
+    pub fn as_view(&self) -> View<'_, T> {
+        View { grid: self, rows: 0..self.rows, cols: 0..self.cols }
+    }
+
+    // Every `height` by `width` rectangle, row by row, like
+    // `slice::windows` in two dimensions. None when the window is larger
+    // than the grid.
+    pub fn windows(&self, height: usize, width: usize) -> impl Iterator<Item = View<'_, T>> {
+        assert!(height > 0 && width > 0, "window size must be non-zero");
+        let across = (self.cols + 1).saturating_sub(width);
+        let down = (self.rows + 1).saturating_sub(height);
+        (0..down * across).map(move |i| {
+            let (top, left) = (i / across, i % across);
+            self.view(top..top + height, left..left + width)
+        })
+    }
+
+    // The positions up, left, right and down of `position` that are inside
+    // the grid.
+    pub fn neighbours4(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(position, &[(-1, 0), (0, -1), (0, 1), (1, 0)])
+    }
+
+    // The same with the four diagonals, in reading order.
+    pub fn neighbours8(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(position, &[(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)])
+    }
+
+    fn neighbours(&self, (row, col): (usize, usize), steps: &'static [(isize, isize)]) -> impl Iterator<Item = (usize, usize)> {
+        let (rows, cols) = self.shape();
+        steps.iter().filter_map(move |&(dr, dc)| {
+            let row = row.checked_add_signed(dr).filter(|&r| r < rows)?;
+            let col = col.checked_add_signed(dc).filter(|&c| c < cols)?;
+            Some((row, col))
+        })
+    }
+
+    // Rows become columns: cell `(r, c)` moves to `(c, r)`.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.cols, self.rows, |r, c| self[(c, r)].clone())
+    }
+
+    // A quarter turn clockwise: the first column, read bottom up, becomes
+    // the first row.
+    pub fn rotate_cw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.cols, self.rows, |r, c| self[(self.rows - 1 - c, r)].clone())
+    }
+
+    pub fn rotate_ccw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.cols, self.rows, |r, c| self[(c, self.cols - 1 - r)].clone())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        match self.get(position) {
+            Some(cell) => cell,
+            None => panic!("position {:?} out of range for a {}x{} grid", position, self.rows, self.cols),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        let (rows, cols) = self.shape();
+        match self.get_mut(position) {
+            Some(cell) => cell,
+            None => panic!("position {:?} out of range for a {}x{} grid", position, rows, cols),
+        }
+    }
+}
+
+// A rectangle of a grid. Positions are relative to its top left corner.
+#[derive(Debug, Clone)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows.len(), self.cols.len())
+    }
+
+    // Where the view's `(0, 0)` is in the grid.
+    pub fn origin(&self) -> (usize, usize) {
+        (self.rows.start, self.cols.start)
+    }
+
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<&'a T> {
+        if row < self.rows.len() && col < self.cols.len() {
+            self.grid.get((self.rows.start + row, self.cols.start + col))
+        } else {
+            None
+        }
+    }
+
+    // Each row of the view is a contiguous part of a row of the grid.
+    pub fn iter_rows(&self) -> impl ExactSizeIterator<Item = &'a [T]> + DoubleEndedIterator {
+        let (grid, cols) = (self.grid, self.cols.clone());
+        self.rows.clone().map(move |row| &grid.row(row)[cols.clone()])
+    }
+
+    // All the cells, row after row.
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> {
+        self.iter_rows().flatten()
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let (rows, cols) = self.shape();
+        Grid { cells: self.iter().cloned().collect(), rows, cols }
+    }
+}
+
+impl<T> Index<(usize, usize)> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        match self.get(position) {
+            Some(cell) => cell,
+            None => panic!("position {:?} out of range for a {}x{} view", position, self.rows.len(), self.cols.len()),
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for View<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.shape() == other.shape() && self.iter().eq(other.iter())
+    }
+}
+
+// Rows on separate lines, each column right-aligned to its widest cell.
+// A precision is passed on to the cells: `{:.2}` for floats.
+fn write_matrix<'a, T: fmt::Display + 'a>(
+    f: &mut fmt::Formatter,
+    rows: impl Iterator<Item = &'a [T]>,
+) -> fmt::Result {
+    let cells: Vec<Vec<String>> = rows
+        .map(|row| {
+            row.iter()
+                .map(|cell| match f.precision() {
+                    Some(precision) => format!("{:.*}", precision, cell),
+                    None => cell.to_string(),
+                })
+                .collect()
+        })
+        .collect();
+    let cols = cells.first().map_or(0, Vec::len);
+    let widths: Vec<usize> = (0..cols).map(|c| cells.iter().map(|row| row[c].chars().count()).max().unwrap_or(0)).collect();
+    for (i, row) in cells.iter().enumerate() {
+        if i > 0 {
+            writeln!(f)?;
+        }
+        let line: Vec<String> = row.iter().zip(&widths).map(|(cell, &width)| format!("{:>w$}", cell, w = width)).collect();
+        write!(f, "{}", line.join(" "))?;
+    }
+    Ok(())
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_matrix(f, self.iter_rows())
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for View<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_matrix(f, self.iter_rows())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<i32> {
+        Grid::from_rows([[1, 2, 3], [4, 5, 6]]).unwrap()
+    }
+
+    #[test]
+    fn indexes_by_row_and_column() {
+        let mut grid = sample();
+        assert_eq!(grid.shape(), (2, 3));
+        assert_eq!(grid[(1, 2)], 6);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        grid[(0, 0)] = 10;
+        grid.row_mut(1)[0] = 40;
+        assert_eq!(grid.as_slice(), [10, 2, 3, 40, 5, 6]);
+
+        assert_eq!(Grid::from_vec(2, 2, vec![1, 2, 3]), Err(GridError::WrongLength { rows: 2, cols: 2, len: 3 }));
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2], vec![3]]).unwrap_err().to_string(),
+            "row 1 has 1 cells, expected 2"
+        );
+    }
+
+    #[test]
+    fn views_without_copying() {
+        let grid = Grid::from_fn(4, 4, |r, c| r * 4 + c);
+        let view = grid.view(1..3, 1..4);
+        assert_eq!(view.shape(), (2, 3));
+        assert_eq!(view[(0, 0)], 5);
+        assert_eq!(view.get((2, 0)), None);
+        assert!(std::ptr::eq(&view[(1, 2)], &grid[(2, 3)]));
+        assert_eq!(view.to_string(), "5  6  7\n9 10 11");
+
+        let sums: Vec<usize> = grid.windows(3, 3).map(|window| window.iter().sum()).collect();
+        assert_eq!(sums, [45, 54, 81, 90]);
+        assert_eq!(grid.windows(5, 1).count(), 0);
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), [(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8((2, 2)).collect::<Vec<_>>(), [(1, 1), (1, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn turns_around() {
+        let grid = sample();
+        assert_eq!(grid.transpose(), Grid::from_rows([[1, 4], [2, 5], [3, 6]]).unwrap());
+        assert_eq!(grid.rotate_cw(), Grid::from_rows([[4, 1], [5, 2], [6, 3]]).unwrap());
+        assert_eq!(grid.rotate_ccw(), Grid::from_rows([[3, 6], [2, 5], [1, 4]]).unwrap());
+        assert_eq!(grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), grid);
+        assert_eq!(format!("{:.1}", Grid::from_rows([[1.0, -2.5], [10.25, 0.0]]).unwrap()), " 1.0 -2.5\n10.2  0.0");
+    }
+}
@@ -0,0 +1,4 @@
+// The slice helpers the example in `main.rs` uses.
+
+pub mod grid;
+pub mod stats;
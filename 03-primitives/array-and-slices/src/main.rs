@@ -1,9 +1,19 @@
 use std::mem;
 
+use array_and_slices::grid::Grid;
+use array_and_slices::stats;
+
 // This function borrows a slice.
+// `slice[0]` would panic on an empty slice; `first` returns an `Option`.
 fn analyze_slice(slice: &[i32]) {
-    println!("First element of the slice: {}", slice[0]);
+    match slice.first() {
+        Some(first) => println!("First element of the slice: {}", first),
+        None => println!("The slice is empty"),
+    }
     println!("The slice has {} elements", slice.len());
+    if let Some(summary) = stats::summary(slice) {
+        println!("Statistics: {}", summary);
+    }
 }
 
 fn main() {
@@ -39,6 +49,19 @@ fn main() {
     assert_eq!(&empty_array, &[]);
     assert_eq!(&empty_array, &[][..]); // Same but more verbose
 
+    // An empty slice has no first element, and no mean either.
+    println!("Borrow an empty slice.");
+    analyze_slice(&[]);
+    println!("The mean of nothing is {:?}", stats::mean(&empty_array));
+
+    // The statistics work on slices of any number type.
+    let temperatures = [21.5, 23.0, 19.5, 24.5, 22.0, 23.0, 18.0, 25.5];
+    println!("The 90th percentile of {:?} is {:?}", temperatures, stats::percentile(&temperatures, 90.0));
+    println!("The most common value is {:?}", stats::mode(&temperatures));
+    if let Ok(histogram) = stats::histogram(&temperatures, 4) {
+        print!("{}", histogram);
+    }
+
     // Arrays can be safely accessed using `.get`, which returns an
     // `Option`. This can be matched as shown below, or used with
     // `.expect()` if you would like the program to exit with a nice
@@ -50,6 +73,22 @@ fn main() {
         }
     }
 
+    // A two-dimensional grid keeps its cells in one flat array, row after
+    // row, so each row is an ordinary slice.
+    let grid = Grid::from_fn(3, 4, |row, col| row * 4 + col);
+    println!("A 3x4 grid:\n{}", grid);
+    println!("Its second row is the slice {:?}", grid.row(1));
+    println!("Its last column is {:?}", grid.column(3).collect::<Vec<_>>());
+    println!("Its middle 2x2 block is:\n{}", grid.view(1..3, 1..3));
+    println!("Transposed:\n{}", grid.transpose());
+    println!("The neighbours of (0, 0) are {:?}", grid.neighbours8((0, 0)).collect::<Vec<_>>());
+    // Like `get` on a slice, `get` on a grid checks both coordinates.
+    println!("(0, 4) is {:?}, not the first cell of the next row", grid.get((0, 4)));
+    // `windows` slides a rectangle over the grid, as `slice::windows` does
+    // along a slice.
+    let sums: Vec<usize> = grid.windows(2, 2).map(|window| window.iter().sum()).collect();
+    println!("The sums of each 2x2 window are {:?}", sums);
+
     // Out of bound indexing on array with constant value causes compile time error.
     //println!("{}", xs[5]);
     // Out of bound indexing on slice causes runtime error.
@@ -0,0 +1,342 @@
+// Descriptive statistics over slices of numbers.
+//
+// Nothing here panics on an empty slice: what has no answer for no values
+// (the mean, the median, ...) returns `None` or `Err(StatsError::Empty)`,
+// and the sum of nothing is 0.
+//
+// Every function takes a slice of any primitive number type; the results
+// that can be fractional are `f64`. Sums use Neumaier's variant of Kahan
+// summation, which carries the low-order bits that plain `+` rounds away,
+// and the variance is computed in two passes, so that neither loses
+// precision on long or badly scaled inputs.
+//
+// NaN has no place in an ordering, so a slice containing it gives
+// meaningless minimums, medians and histograms.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatsError {
+    // The slice has no values.
+    Empty,
+    // A percentile outside 0..=100.
+    PercentileOutOfRange(f64),
+    // A histogram with zero bins.
+    NoBins,
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StatsError::Empty => write!(f, "no values to compute statistics of"),
+            StatsError::PercentileOutOfRange(p) => write!(f, "percentile {} is not between 0 and 100", p),
+            StatsError::NoBins => write!(f, "a histogram needs at least one bin"),
+        }
+    }
+}
+
+impl Error for StatsError {}
+
+// The primitive numbers, which all convert to `f64` (the 64- and 128-bit
+// integers only approximately).
+pub trait Number: Copy + PartialOrd {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! number {
+    ($($ty:ty),*) => {
+        $(
+            impl Number for $ty {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+pub fn min<T: Number>(xs: &[T]) -> Option<T> {
+    xs.iter().copied().reduce(|min, x| if x < min { x } else { min })
+}
+
+pub fn max<T: Number>(xs: &[T]) -> Option<T> {
+    xs.iter().copied().reduce(|max, x| if x > max { x } else { max })
+}
+
+// Neumaier summation: `compensation` collects what each addition rounded
+// off, and is added back at the end. Summing 0.1 ten times gives exactly
+// 1.0, and [1.0, 1e100, 1.0, -1e100] gives 2.0 rather than 0.0.
+pub fn sum<T: Number>(xs: &[T]) -> f64 {
+    let mut total = 0.0f64;
+    let mut compensation = 0.0;
+    for x in xs.iter().map(|x| x.to_f64()) {
+        let next = total + x;
+        compensation += if total.abs() >= x.abs() { (total - next) + x } else { (x - next) + total };
+        total = next;
+    }
+    total + compensation
+}
+
+pub fn mean<T: Number>(xs: &[T]) -> Option<f64> {
+    (!xs.is_empty()).then(|| sum(xs) / xs.len() as f64)
+}
+
+// Two passes: first the mean, then the squared distances from it, with
+// the small correction for the rounding error in the mean. Unlike the mean
+// of the squares minus the square of the mean, this does not cancel
+// catastrophically when the values are large and close together.
+fn squared_deviations<T: Number>(xs: &[T]) -> f64 {
+    let Some(mean) = mean(xs) else { return 0.0 };
+    let deviations: Vec<f64> = xs.iter().map(|x| x.to_f64() - mean).collect();
+    let squares: Vec<f64> = deviations.iter().map(|d| d * d).collect();
+    let drift = sum(&deviations);
+    sum(&squares) - drift * drift / xs.len() as f64
+}
+
+// The population variance: the mean squared distance from the mean.
+pub fn variance<T: Number>(xs: &[T]) -> Option<f64> {
+    (!xs.is_empty()).then(|| squared_deviations(xs) / xs.len() as f64)
+}
+
+// The sample variance, divided by `n - 1`; it needs at least two values.
+pub fn sample_variance<T: Number>(xs: &[T]) -> Option<f64> {
+    (xs.len() >= 2).then(|| squared_deviations(xs) / (xs.len() - 1) as f64)
+}
+
+pub fn stddev<T: Number>(xs: &[T]) -> Option<f64> {
+    variance(xs).map(f64::sqrt)
+}
+
+pub fn sample_stddev<T: Number>(xs: &[T]) -> Option<f64> {
+    sample_variance(xs).map(f64::sqrt)
+}
+
+fn sorted<T: Number>(xs: &[T]) -> Vec<f64> {
+    let mut sorted: Vec<f64> = xs.iter().map(|x| x.to_f64()).collect();
+    sorted.sort_by(f64::total_cmp);
+    sorted
+}
+
+// The value below which `p` percent of the values fall, interpolating
+// linearly between the two nearest ones (as spreadsheets and NumPy do by
+// default): the 0th percentile is the minimum and the 100th the maximum.
+pub fn percentile<T: Number>(xs: &[T], p: f64) -> Result<f64, StatsError> {
+    if !(0.0..=100.0).contains(&p) {
+        return Err(StatsError::PercentileOutOfRange(p));
+    }
+    if xs.is_empty() {
+        return Err(StatsError::Empty);
+    }
+    Ok(interpolate(&sorted(xs), p))
+}
+
+// Several percentiles with a single sort.
+pub fn percentiles<T: Number>(xs: &[T], ps: &[f64]) -> Result<Vec<f64>, StatsError> {
+    if let Some(&p) = ps.iter().find(|p| !(0.0..=100.0).contains(*p)) {
+        return Err(StatsError::PercentileOutOfRange(p));
+    }
+    if xs.is_empty() {
+        return Err(StatsError::Empty);
+    }
+    let sorted = sorted(xs);
+    Ok(ps.iter().map(|&p| interpolate(&sorted, p)).collect())
+}
+
+fn interpolate(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (below, fraction) = (rank.floor() as usize, rank.fract());
+    match sorted.get(below + 1) {
+        Some(above) if fraction > 0.0 => sorted[below] + (above - sorted[below]) * fraction,
+        _ => sorted[below],
+    }
+}
+
+// The middle value, or the mean of the two middle ones.
+pub fn median<T: Number>(xs: &[T]) -> Option<f64> {
+    percentile(xs, 50.0).ok()
+}
+
+// The most common value; the smallest of them when several are equally
+// common.
+pub fn mode<T: Number>(xs: &[T]) -> Option<T> {
+    let mut sorted = xs.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mut best: Option<(T, usize)> = None;
+    for run in sorted.chunk_by(|a, b| a == b) {
+        if best.is_none_or(|(_, count)| run.len() > count) {
+            best = Some((run[0], run.len()));
+        }
+    }
+    best.map(|(value, _)| value)
+}
+
+// Counts of values in `counts.len()` equal-width bins from the minimum to
+// the maximum. Each bin includes its lower edge; the last one includes
+// the maximum too.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub start: f64,
+    pub width: f64,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    // The range each bin covers, with its count.
+    pub fn bins(&self) -> impl Iterator<Item = (Range<f64>, usize)> + '_ {
+        self.counts.iter().enumerate().map(|(i, &count)| {
+            let low = self.start + self.width * i as f64;
+            (low..low + self.width, count)
+        })
+    }
+}
+
+// One row per bin, with a bar as long as its count:
+//
+//     [1, 2)  ###  3
+//     [2, 3]  #    1
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // `counts` is public, so it may have been emptied: then no rows.
+        let Some(last) = self.counts.len().checked_sub(1) else {
+            return Ok(());
+        };
+        let ranges: Vec<String> = self
+            .bins()
+            .enumerate()
+            .map(|(i, (range, _))| format!("[{}, {}{}", range.start, range.end, if i == last { ']' } else { ')' }))
+            .collect();
+        let range_width = ranges.iter().map(String::len).max().unwrap_or(0);
+        let bar_width = self.counts.iter().copied().max().unwrap_or(0);
+        for (range, count) in ranges.iter().zip(&self.counts) {
+            writeln!(f, "{:<rw$}  {:<bw$}  {}", range, "#".repeat(*count), count, rw = range_width, bw = bar_width)?;
+        }
+        Ok(())
+    }
+}
+
+// `bins` equal-width bins spanning the values. When all the values are
+// the same, the bins span one unit starting at that value.
+pub fn histogram<T: Number>(xs: &[T], bins: usize) -> Result<Histogram, StatsError> {
+    if bins == 0 {
+        return Err(StatsError::NoBins);
+    }
+    let (Some(low), Some(high)) = (min(xs), max(xs)) else {
+        return Err(StatsError::Empty);
+    };
+    let (start, end) = (low.to_f64(), high.to_f64());
+    let width = if end > start { (end - start) / bins as f64 } else { 1.0 / bins as f64 };
+    let mut counts = vec![0; bins];
+    for x in xs {
+        let bin = ((x.to_f64() - start) / width) as usize;
+        counts[bin.min(bins - 1)] += 1;
+    }
+    Ok(Histogram { start, width, counts })
+}
+
+// The usual numbers at a glance, for a non-empty slice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary<T> {
+    pub count: usize,
+    pub min: T,
+    pub max: T,
+    pub mean: f64,
+    pub stddev: f64,
+    pub median: f64,
+}
+
+pub fn summary<T: Number>(xs: &[T]) -> Option<Summary<T>> {
+    Some(Summary {
+        count: xs.len(),
+        min: min(xs)?,
+        max: max(xs)?,
+        mean: mean(xs)?,
+        stddev: stddev(xs)?,
+        median: median(xs)?,
+    })
+}
+
+// `{:.2}` sets the precision of the fractional numbers.
+impl<T: Number + fmt::Display> fmt::Display for Summary<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        write!(
+            f,
+            "n = {}, min = {}, max = {}, mean = {:.p$}, stddev = {:.p$}, median = {:.p$}",
+            self.count,
+            self.min,
+            self.max,
+            self.mean,
+            self.stddev,
+            self.median,
+            p = precision
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMPTY: [f64; 0] = [];
+
+    #[test]
+    fn handles_empty_slices() {
+        assert_eq!(min(&EMPTY), None);
+        assert_eq!(max(&EMPTY), None);
+        assert_eq!(sum(&EMPTY), 0.0);
+        assert_eq!(mean(&EMPTY), None);
+        assert_eq!(variance(&EMPTY), None);
+        assert_eq!(sample_variance(&[1.0]), None);
+        assert_eq!(median(&EMPTY), None);
+        assert_eq!(mode(&EMPTY), None);
+        assert_eq!(percentile(&EMPTY, 90.0), Err(StatsError::Empty));
+        assert_eq!(histogram(&EMPTY, 3), Err(StatsError::Empty));
+        assert_eq!(summary(&EMPTY), None);
+    }
+
+    #[test]
+    fn sums_without_losing_bits() {
+        assert_eq!(sum(&[0.1; 10]), 1.0);
+        assert_eq!(sum(&[1.0, 1e100, 1.0, -1e100]), 2.0);
+        assert_eq!(sum(&[1u8, 2, 3]), 6.0);
+    }
+
+    #[test]
+    fn describes_the_spread() {
+        let xs = [2, 4, 4, 4, 5, 5, 7, 9];
+        assert_eq!(mean(&xs), Some(5.0));
+        assert_eq!(variance(&xs), Some(4.0));
+        assert_eq!(stddev(&xs), Some(2.0));
+        assert_eq!(sample_variance(&xs), Some(32.0 / 7.0));
+        assert_eq!(mode(&xs), Some(4));
+        assert_eq!(mode(&[3, 1, 3, 1]), Some(1));
+        // Large values close together, where the naive formula gives
+        // nonsense.
+        let shifted: Vec<f64> = xs.iter().map(|&x| 1e9 + f64::from(x)).collect();
+        assert_eq!(variance(&shifted), Some(4.0));
+    }
+
+    #[test]
+    fn interpolates_percentiles() {
+        let xs = [15, 20, 35, 40, 50];
+        assert_eq!(median(&xs), Some(35.0));
+        assert_eq!(median(&[4.0, 1.0, 3.0, 2.0]), Some(2.5));
+        assert_eq!(percentiles(&xs, &[0.0, 25.0, 40.0, 100.0]), Ok(vec![15.0, 20.0, 29.0, 50.0]));
+        assert_eq!(percentile(&xs, 101.0), Err(StatsError::PercentileOutOfRange(101.0)));
+    }
+
+    #[test]
+    fn bins_values() {
+        let h = histogram(&[1, 2, 2, 3, 3, 3, 5], 4).unwrap();
+        assert_eq!(h.counts, [1, 2, 3, 1]);
+        assert_eq!(h.bins().last(), Some((4.0..5.0, 1)));
+        assert_eq!(h.to_string(), "[1, 2)  #    1\n[2, 3)  ##   2\n[3, 4)  ###  3\n[4, 5]  #    1\n");
+        assert_eq!(histogram(&[7, 7], 2).unwrap().counts, [2, 0]);
+        assert_eq!(Histogram { start: 0.0, width: 1.0, counts: vec![] }.to_string(), "");
+        assert_eq!(histogram(&[7], 0), Err(StatsError::NoBins));
+    }
+}
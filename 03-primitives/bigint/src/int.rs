@@ -0,0 +1,221 @@
+// `BigInt`: a sign and a `BigUint` magnitude.
+//
+// It behaves like the primitive signed integers would if they never ran
+// out of bits: division truncates towards zero and the remainder takes the
+// sign of the dividend, `>>` rounds towards negative infinity, and the
+// bitwise operators act as if negative numbers were written in two's
+// complement with infinitely many leading ones.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::uint::{self, BigUint};
+
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    // Never set for zero, so that there is only one zero.
+    negative: bool,
+    magnitude: BigUint,
+}
+
+impl BigInt {
+    pub fn zero() -> BigInt {
+        BigInt::default()
+    }
+
+    pub fn one() -> BigInt {
+        BigInt::from(1)
+    }
+
+    pub fn from_biguint(negative: bool, magnitude: BigUint) -> BigInt {
+        BigInt { negative: negative && !magnitude.is_zero(), magnitude }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    // The absolute value.
+    pub fn magnitude(&self) -> &BigUint {
+        &self.magnitude
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt::from_biguint(false, self.magnitude.clone())
+    }
+
+    // -1, 0 or 1.
+    pub fn signum(&self) -> BigInt {
+        match (self.is_zero(), self.negative) {
+            (true, _) => BigInt::zero(),
+            (false, negative) => BigInt::from_biguint(negative, BigUint::one()),
+        }
+    }
+
+    pub fn pow(&self, exponent: u32) -> BigInt {
+        BigInt::from_biguint(self.negative && exponent % 2 == 1, self.magnitude.pow(exponent))
+    }
+
+    // Quotient (towards zero) and remainder in one go. Panics when
+    // `divisor` is zero.
+    pub fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        let (quotient, remainder) = self.magnitude.div_rem(&divisor.magnitude);
+        (
+            BigInt::from_biguint(self.negative != divisor.negative, quotient),
+            BigInt::from_biguint(self.negative, remainder),
+        )
+    }
+
+    // The magnitude, if the number is not negative.
+    pub fn to_biguint(&self) -> Option<BigUint> {
+        (!self.negative).then(|| self.magnitude.clone())
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        self.to_i128().and_then(|n| i64::try_from(n).ok())
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        let magnitude = self.magnitude.to_u128()?;
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    // The lowest `len` limbs of the two's complement form.
+    pub(crate) fn twos_complement(&self, len: usize) -> Vec<u32> {
+        let mut limbs = self.magnitude.limbs().to_vec();
+        limbs.resize(len, 0);
+        if self.negative {
+            // -x is !x + 1, or equivalently !(x - 1).
+            let mut borrow = true;
+            for limb in &mut limbs {
+                let (d, under) = limb.overflowing_sub(u32::from(borrow));
+                *limb = !d;
+                borrow = under;
+            }
+        }
+        limbs
+    }
+
+    // The inverse of `twos_complement`: the top bit is the sign.
+    pub(crate) fn from_twos_complement(mut limbs: Vec<u32>) -> BigInt {
+        let negative = limbs.last().is_some_and(|top| top >> 31 == 1);
+        if negative {
+            // x is !(-x) + 1.
+            let mut carry = true;
+            for limb in &mut limbs {
+                let (d, over) = (!*limb).overflowing_add(u32::from(carry));
+                *limb = d;
+                carry = over;
+            }
+        }
+        BigInt::from_biguint(negative, BigUint::from_limbs(limbs))
+    }
+
+    // `op` applied limb by limb to both numbers in two's complement, one
+    // limb wider than the wider of them so that the sign survives.
+    pub(crate) fn bitwise(&self, other: &BigInt, op: impl Fn(u32, u32) -> u32) -> BigInt {
+        let len = self.magnitude.limbs().len().max(other.magnitude.limbs().len()) + 1;
+        let (a, b) = (self.twos_complement(len), other.twos_complement(len));
+        BigInt::from_twos_complement(a.iter().zip(&b).map(|(&x, &y)| op(x, y)).collect())
+    }
+}
+
+impl From<BigUint> for BigInt {
+    fn from(magnitude: BigUint) -> BigInt {
+        BigInt::from_biguint(false, magnitude)
+    }
+}
+
+macro_rules! from_signed {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for BigInt {
+                fn from(n: $ty) -> BigInt {
+                    BigInt::from_biguint(n < 0, BigUint::from(n.unsigned_abs()))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! from_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for BigInt {
+                fn from(n: $ty) -> BigInt {
+                    BigInt::from_biguint(false, BigUint::from(n))
+                }
+            }
+        )*
+    };
+}
+
+from_signed!(i8, i16, i32, i64, i128, isize);
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+        }
+    }
+}
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+// `a + b` for magnitudes with signs, the core of both `+` and `-`.
+pub(crate) fn add_signed(a_negative: bool, a: &BigUint, b_negative: bool, b: &BigUint) -> BigInt {
+    if a_negative == b_negative {
+        return BigInt::from_biguint(a_negative, BigUint::from_limbs(uint::add(a.limbs(), b.limbs())));
+    }
+    // Opposite signs: the larger magnitude wins.
+    match a.cmp(b) {
+        Ordering::Less => BigInt::from_biguint(b_negative, BigUint::from_limbs(uint::sub(b.limbs(), a.limbs()))),
+        _ => BigInt::from_biguint(a_negative, BigUint::from_limbs(uint::sub(a.limbs(), b.limbs()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_twos_complement() {
+        for n in [0i64, 1, -1, 255, -256, i64::MIN, i64::MAX, -4_294_967_296] {
+            let big = BigInt::from(n);
+            assert_eq!(BigInt::from_twos_complement(big.twos_complement(3)), big, "{}", n);
+        }
+        assert_eq!(BigInt::from(-1).twos_complement(2), [u32::MAX, u32::MAX]);
+    }
+
+    #[test]
+    fn converts_back() {
+        assert_eq!(BigInt::from(i128::MIN).to_i128(), Some(i128::MIN));
+        assert_eq!(BigInt::from(u128::MAX).to_i128(), None);
+        assert_eq!(BigInt::from(-5).to_i64(), Some(-5));
+        assert_eq!(BigInt::from(-5).to_biguint(), None);
+        assert_eq!(BigInt::from(-5).signum(), BigInt::from(-1));
+        assert_eq!(BigInt::from(-2).pow(3), BigInt::from(-8));
+    }
+}
@@ -0,0 +1,121 @@
+// Integers without an upper limit.
+//
+// `BigUint` is an unsigned integer and `BigInt` a signed one. Both behave
+// like the primitive integers as far as they can: the usual operators work
+// on values and references (`&a * &b` does not consume either side), they
+// print with `{}`, `{:x}`, `{:#b}` and friends, and they parse with
+// `str::parse` or `from_str_radix`.
+//
+//     let big = BigUint::from(2u32).pow(100);
+//     println!("{}", big);           // 1267650600228229401496703205376
+//     println!("{:#x}", big);        // 0x10000000000000000000000000
+//
+// Multiplying two large numbers uses Karatsuba's method, which splits each
+// number in half and gets away with three half-size products instead of
+// four; short numbers fall back to the schoolbook method.
+
+mod int;
+mod ops;
+mod radix;
+mod uint;
+
+pub use int::BigInt;
+pub use radix::ParseBigIntError;
+pub use uint::BigUint;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(n: u32) -> BigUint {
+        let (mut a, mut b) = (BigUint::zero(), BigUint::one());
+        for _ in 0..n {
+            let next = &a + &b;
+            a = std::mem::replace(&mut b, next);
+        }
+        a
+    }
+
+    // F(2k) = F(k)(2F(k+1) - F(k)) and F(2k+1) = F(k)^2 + F(k+1)^2, which
+    // multiplies numbers big enough for Karatsuba.
+    fn fast_fibonacci(n: u32) -> (BigUint, BigUint) {
+        if n == 0 {
+            return (BigUint::zero(), BigUint::one());
+        }
+        let (a, b) = fast_fibonacci(n / 2);
+        let even = &a * &(&(&b << 1) - &a);
+        let odd = &a * &a + &b * &b;
+        if n.is_multiple_of(2) { (even, odd) } else { (odd.clone(), even + odd) }
+    }
+
+    #[test]
+    fn computes_fibonacci() {
+        let f = fibonacci(10_000);
+        assert_eq!(f, fast_fibonacci(10_000).0);
+        let digits = f.to_string();
+        assert_eq!(digits.len(), 2090);
+        assert!(digits.starts_with("33644764876431783266"));
+        assert!(digits.ends_with("66073310059947366875"));
+    }
+
+    #[test]
+    fn matches_primitive_arithmetic() {
+        let values = [0i64, 1, -1, 7, -7, 12345, -98765, i32::MAX as i64, i32::MIN as i64];
+        for &a in &values {
+            for &b in &values {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+                assert_eq!(&x + &y, BigInt::from(a + b));
+                assert_eq!(&x - &y, BigInt::from(a - b));
+                assert_eq!(&x * &y, BigInt::from(a * b));
+                assert_eq!(&x & &y, BigInt::from(a & b), "{} & {}", a, b);
+                assert_eq!(&x | &y, BigInt::from(a | b), "{} | {}", a, b);
+                assert_eq!(&x ^ &y, BigInt::from(a ^ b), "{} ^ {}", a, b);
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+                if b != 0 {
+                    assert_eq!(&x / &y, BigInt::from(a / b), "{} / {}", a, b);
+                    assert_eq!(&x % &y, BigInt::from(a % b), "{} % {}", a, b);
+                }
+            }
+            assert_eq!(!BigInt::from(a), BigInt::from(!a));
+            assert_eq!(BigInt::from(a) >> 3, BigInt::from(a >> 3));
+            assert_eq!(BigInt::from(a) << 3, BigInt::from(a << 3));
+        }
+    }
+
+    #[test]
+    fn formats_and_parses() {
+        let n = BigUint::from(3u32).pow(200);
+        assert_eq!(format!("{:#x}", n)[..30], *"0x1fd5863c3eb0469ec21a937a76f3");
+        for radix in [2, 3, 8, 10, 16, 36] {
+            assert_eq!(BigUint::from_str_radix(&n.to_str_radix(radix), radix), Ok(n.clone()));
+        }
+
+        let m = BigInt::from(-255);
+        assert_eq!(format!("{} {:x} {:X} {:#o} {:b}", m, m, m, m, m), "-255 -ff -FF -0o377 -11111111");
+        assert_eq!(format!("{:>+6}|{:06}", BigInt::from(42), m), "   +42|-00255");
+        assert_eq!("-000123".parse(), Ok(BigInt::from(-123)));
+        assert_eq!("".parse::<BigInt>(), Err(ParseBigIntError::Empty));
+        assert_eq!("-".parse::<BigInt>(), Err(ParseBigIntError::InvalidDigit('-')));
+        assert_eq!("-1".parse::<BigUint>(), Err(ParseBigIntError::InvalidDigit('-')));
+        assert_eq!("12a".parse::<BigUint>(), Err(ParseBigIntError::InvalidDigit('a')));
+    }
+
+    #[test]
+    fn assigns_and_sums() {
+        let mut n = BigUint::from(u64::MAX);
+        n += BigUint::one();
+        assert_eq!(n, BigUint::one() << 64);
+        n *= &n.clone();
+        assert_eq!(n.bits(), 129);
+        let total: BigInt = (1..=100).map(BigInt::from).sum();
+        assert_eq!(total, BigInt::from(5050));
+        let factorial: BigUint = (1..=30u32).map(BigUint::from).product();
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn refuses_negative_unsigned() {
+        let _ = BigUint::from(1u32) - BigUint::from(2u32);
+    }
+}
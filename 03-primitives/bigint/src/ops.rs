@@ -0,0 +1,329 @@
+// The operators. Each one is written once for two references, `&a + &b`;
+// the macros at the bottom derive the owned forms (`a + b`, `a + &b`,
+// `&a + b`) and the assigning ones (`a += b`) from it.
+
+use std::iter::{Product, Sum};
+use std::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, DivAssign, Mul,
+    MulAssign, Neg, Not, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+};
+
+use crate::int::{add_signed, BigInt};
+use crate::uint::{self, BigUint};
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, rhs: &BigUint) -> BigUint {
+        BigUint::from_limbs(uint::add(self.limbs(), rhs.limbs()))
+    }
+}
+
+// Like the primitive unsigned types, there are no negative results.
+impl Sub for &BigUint {
+    type Output = BigUint;
+
+    fn sub(self, rhs: &BigUint) -> BigUint {
+        self.checked_sub(rhs).expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, rhs: &BigUint) -> BigUint {
+        BigUint::from_limbs(uint::mul(self.limbs(), rhs.limbs()))
+    }
+}
+
+impl Div for &BigUint {
+    type Output = BigUint;
+
+    fn div(self, rhs: &BigUint) -> BigUint {
+        self.div_rem(rhs).0
+    }
+}
+
+impl Rem for &BigUint {
+    type Output = BigUint;
+
+    fn rem(self, rhs: &BigUint) -> BigUint {
+        if rhs.is_zero() {
+            panic!("attempt to calculate the remainder with a divisor of zero");
+        }
+        self.div_rem(rhs).1
+    }
+}
+
+// Limb by limb; the missing limbs of the shorter number are zeros.
+fn zip_limbs(a: &BigUint, b: &BigUint, op: impl Fn(u32, u32) -> u32) -> BigUint {
+    let len = a.limbs().len().max(b.limbs().len());
+    let limb = |n: &BigUint, i: usize| n.limbs().get(i).copied().unwrap_or(0);
+    BigUint::from_limbs((0..len).map(|i| op(limb(a, i), limb(b, i))).collect())
+}
+
+impl BitAnd for &BigUint {
+    type Output = BigUint;
+
+    fn bitand(self, rhs: &BigUint) -> BigUint {
+        zip_limbs(self, rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BigUint {
+    type Output = BigUint;
+
+    fn bitor(self, rhs: &BigUint) -> BigUint {
+        zip_limbs(self, rhs, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BigUint {
+    type Output = BigUint;
+
+    fn bitxor(self, rhs: &BigUint) -> BigUint {
+        zip_limbs(self, rhs, |a, b| a ^ b)
+    }
+}
+
+impl Shl<u32> for &BigUint {
+    type Output = BigUint;
+
+    fn shl(self, bits: u32) -> BigUint {
+        BigUint::from_limbs(uint::shl(self.limbs(), bits as usize))
+    }
+}
+
+impl Shr<u32> for &BigUint {
+    type Output = BigUint;
+
+    fn shr(self, bits: u32) -> BigUint {
+        BigUint::from_limbs(uint::shr(self.limbs(), bits as usize))
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: &BigInt) -> BigInt {
+        add_signed(self.is_negative(), self.magnitude(), rhs.is_negative(), rhs.magnitude())
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: &BigInt) -> BigInt {
+        add_signed(self.is_negative(), self.magnitude(), !rhs.is_negative(), rhs.magnitude())
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: &BigInt) -> BigInt {
+        BigInt::from_biguint(self.is_negative() != rhs.is_negative(), self.magnitude() * rhs.magnitude())
+    }
+}
+
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, rhs: &BigInt) -> BigInt {
+        self.div_rem(rhs).0
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, rhs: &BigInt) -> BigInt {
+        if rhs.is_zero() {
+            panic!("attempt to calculate the remainder with a divisor of zero");
+        }
+        self.div_rem(rhs).1
+    }
+}
+
+impl BitAnd for &BigInt {
+    type Output = BigInt;
+
+    fn bitand(self, rhs: &BigInt) -> BigInt {
+        self.bitwise(rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BigInt {
+    type Output = BigInt;
+
+    fn bitor(self, rhs: &BigInt) -> BigInt {
+        self.bitwise(rhs, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BigInt {
+    type Output = BigInt;
+
+    fn bitxor(self, rhs: &BigInt) -> BigInt {
+        self.bitwise(rhs, |a, b| a ^ b)
+    }
+}
+
+impl Shl<u32> for &BigInt {
+    type Output = BigInt;
+
+    fn shl(self, bits: u32) -> BigInt {
+        BigInt::from_biguint(self.is_negative(), self.magnitude() << bits)
+    }
+}
+
+// Rounds towards negative infinity, like `>>` on `i32`: -5 >> 1 is -3.
+impl Shr<u32> for &BigInt {
+    type Output = BigInt;
+
+    fn shr(self, bits: u32) -> BigInt {
+        if !self.is_negative() {
+            return BigInt::from(self.magnitude() >> bits);
+        }
+        // -x >> n is -((x - 1) >> n) - 1.
+        let shifted = &(self.magnitude() - &BigUint::one()) >> bits;
+        BigInt::from_biguint(true, &shifted + &BigUint::one())
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_biguint(!self.is_negative(), self.magnitude().clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        -&self
+    }
+}
+
+// !x is -x - 1, as in two's complement.
+impl Not for &BigInt {
+    type Output = BigInt;
+
+    fn not(self) -> BigInt {
+        &(-self) - &BigInt::one()
+    }
+}
+
+impl Not for BigInt {
+    type Output = BigInt;
+
+    fn not(self) -> BigInt {
+        !&self
+    }
+}
+
+macro_rules! forward_binop {
+    ($ty:ty: $($trait:ident $method:ident $assign:ident $assign_method:ident),*) => {
+        $(
+            impl $trait for $ty {
+                type Output = $ty;
+
+                fn $method(self, rhs: $ty) -> $ty {
+                    $trait::$method(&self, &rhs)
+                }
+            }
+
+            impl $trait<&$ty> for $ty {
+                type Output = $ty;
+
+                fn $method(self, rhs: &$ty) -> $ty {
+                    $trait::$method(&self, rhs)
+                }
+            }
+
+            impl $trait<$ty> for &$ty {
+                type Output = $ty;
+
+                fn $method(self, rhs: $ty) -> $ty {
+                    $trait::$method(self, &rhs)
+                }
+            }
+
+            impl $assign for $ty {
+                fn $assign_method(&mut self, rhs: $ty) {
+                    *self = $trait::$method(&*self, &rhs);
+                }
+            }
+
+            impl $assign<&$ty> for $ty {
+                fn $assign_method(&mut self, rhs: &$ty) {
+                    *self = $trait::$method(&*self, rhs);
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! forward_shift {
+    ($ty:ty: $($trait:ident $method:ident $assign:ident $assign_method:ident),*) => {
+        $(
+            impl $trait<u32> for $ty {
+                type Output = $ty;
+
+                fn $method(self, bits: u32) -> $ty {
+                    $trait::$method(&self, bits)
+                }
+            }
+
+            impl $assign<u32> for $ty {
+                fn $assign_method(&mut self, bits: u32) {
+                    *self = $trait::$method(&*self, bits);
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! sum_and_product {
+    ($($ty:ident),*) => {
+        $(
+            impl Sum for $ty {
+                fn sum<I: Iterator<Item = $ty>>(iter: I) -> $ty {
+                    iter.fold($ty::zero(), |total, n| total + n)
+                }
+            }
+
+            impl<'a> Sum<&'a $ty> for $ty {
+                fn sum<I: Iterator<Item = &'a $ty>>(iter: I) -> $ty {
+                    iter.fold($ty::zero(), |total, n| total + n)
+                }
+            }
+
+            impl Product for $ty {
+                fn product<I: Iterator<Item = $ty>>(iter: I) -> $ty {
+                    iter.fold($ty::one(), |total, n| total * n)
+                }
+            }
+
+            impl<'a> Product<&'a $ty> for $ty {
+                fn product<I: Iterator<Item = &'a $ty>>(iter: I) -> $ty {
+                    iter.fold($ty::one(), |total, n| total * n)
+                }
+            }
+        )*
+    };
+}
+
+forward_binop!(BigUint:
+    Add add AddAssign add_assign, Sub sub SubAssign sub_assign, Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign, Rem rem RemAssign rem_assign, BitAnd bitand BitAndAssign bitand_assign,
+    BitOr bitor BitOrAssign bitor_assign, BitXor bitxor BitXorAssign bitxor_assign);
+forward_binop!(BigInt:
+    Add add AddAssign add_assign, Sub sub SubAssign sub_assign, Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign, Rem rem RemAssign rem_assign, BitAnd bitand BitAndAssign bitand_assign,
+    BitOr bitor BitOrAssign bitor_assign, BitXor bitxor BitXorAssign bitxor_assign);
+forward_shift!(BigUint: Shl shl ShlAssign shl_assign, Shr shr ShrAssign shr_assign);
+forward_shift!(BigInt: Shl shl ShlAssign shl_assign, Shr shr ShrAssign shr_assign);
+sum_and_product!(BigUint, BigInt);
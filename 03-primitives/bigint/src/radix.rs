@@ -0,0 +1,201 @@
+// Reading and writing numbers in any radix from 2 to 36, and the `fmt`
+// traits built on that.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::int::BigInt;
+use crate::uint::{div_rem_small, normalize, BigUint};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBigIntError {
+    Empty,
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseBigIntError::Empty => write!(f, "cannot parse integer from empty string"),
+            ParseBigIntError::InvalidDigit(c) => write!(f, "invalid digit `{}` found in string", c),
+        }
+    }
+}
+
+impl Error for ParseBigIntError {}
+
+fn check_radix(radix: u32) {
+    assert!((2..=36).contains(&radix), "radix must be between 2 and 36, got {}", radix);
+}
+
+// The largest power of `radix` that fits in a limb, and how many digits
+// that is. Conversions go one such chunk of digits at a time.
+fn chunk(radix: u32) -> (u32, usize) {
+    let (mut power, mut digits) = (radix, 1);
+    while let Some(next) = power.checked_mul(radix) {
+        power = next;
+        digits += 1;
+    }
+    (power, digits)
+}
+
+// Lowercase digits, no sign or prefix.
+fn to_radix(limbs: &[u32], radix: u32) -> String {
+    check_radix(radix);
+    let (power, width) = chunk(radix);
+    let mut chunks = Vec::new();
+    let mut rest = limbs.to_vec();
+    while !rest.is_empty() {
+        let (quotient, remainder) = div_rem_small(&rest, power);
+        chunks.push(remainder);
+        rest = quotient;
+    }
+
+    let digits = |mut n: u32| {
+        let mut digits = Vec::new();
+        while n > 0 {
+            digits.push(char::from_digit(n % radix, radix).unwrap());
+            n /= radix;
+        }
+        digits.into_iter().rev().collect::<String>()
+    };
+    let mut text = match chunks.pop() {
+        Some(top) => digits(top),
+        None => return String::from("0"),
+    };
+    // Every chunk below the top one is zero-padded to its full width.
+    for &chunk in chunks.iter().rev() {
+        text.push_str(&format!("{:0>width$}", digits(chunk), width = width));
+    }
+    text
+}
+
+// Digits only, no sign.
+fn parse_radix(text: &str, radix: u32) -> Result<Vec<u32>, ParseBigIntError> {
+    check_radix(radix);
+    if text.is_empty() {
+        return Err(ParseBigIntError::Empty);
+    }
+    let values = text
+        .chars()
+        .map(|c| c.to_digit(radix).ok_or(ParseBigIntError::InvalidDigit(c)))
+        .collect::<Result<Vec<u32>, _>>()?;
+
+    let (_, width) = chunk(radix);
+    let mut limbs: Vec<u32> = Vec::new();
+    for digits in values.chunks(width) {
+        // limbs = limbs * radix^len + value, a chunk at a time.
+        let scale = radix.pow(digits.len() as u32);
+        let mut carry = u64::from(digits.iter().fold(0, |n, &d| n * radix + d));
+        for limb in &mut limbs {
+            let total = u64::from(*limb) * u64::from(scale) + carry;
+            *limb = total as u32;
+            carry = total >> 32;
+        }
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+    }
+    normalize(&mut limbs);
+    Ok(limbs)
+}
+
+// Splits off a leading `+` or `-`.
+fn sign(text: &str) -> (bool, &str) {
+    match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    }
+}
+
+// A sign without digits is an invalid digit, as it is for `i32`.
+fn digits_after_sign<'a>(text: &'a str, rest: &'a str) -> Result<&'a str, ParseBigIntError> {
+    match (rest.is_empty(), text.chars().next()) {
+        (true, Some(sign)) => Err(ParseBigIntError::InvalidDigit(sign)),
+        _ => Ok(rest),
+    }
+}
+
+impl BigUint {
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        to_radix(self.limbs(), radix)
+    }
+
+    // `radix` must be from 2 to 36; an optional `+` may come first.
+    pub fn from_str_radix(text: &str, radix: u32) -> Result<BigUint, ParseBigIntError> {
+        let (negative, rest) = sign(text);
+        let digits = digits_after_sign(text, rest)?;
+        if negative {
+            return Err(ParseBigIntError::InvalidDigit('-'));
+        }
+        parse_radix(digits, radix).map(BigUint::from_limbs)
+    }
+}
+
+impl BigInt {
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let digits = self.magnitude().to_str_radix(radix);
+        if self.is_negative() { format!("-{}", digits) } else { digits }
+    }
+
+    // `radix` must be from 2 to 36; an optional `+` or `-` may come first.
+    pub fn from_str_radix(text: &str, radix: u32) -> Result<BigInt, ParseBigIntError> {
+        let (negative, rest) = sign(text);
+        let digits = digits_after_sign(text, rest)?;
+        parse_radix(digits, radix).map(|limbs| BigInt::from_biguint(negative, BigUint::from_limbs(limbs)))
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<BigUint, ParseBigIntError> {
+        BigUint::from_str_radix(s, 10)
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<BigInt, ParseBigIntError> {
+        BigInt::from_str_radix(s, 10)
+    }
+}
+
+// Display, `{:b}`, `{:o}`, `{:x}` and `{:X}`, with width, fill, `+`, `0`
+// and `#` handled by `pad_integral` as for the primitive integers. Negative
+// numbers are written as a sign and a magnitude (`-ff`), since their two's
+// complement would be infinitely long.
+macro_rules! radix_fmt {
+    ($($trait:ident $radix:literal $prefix:literal $upper:literal),*) => {
+        $(
+            impl fmt::$trait for BigUint {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    write_radix(f, true, self, $radix, $prefix, $upper)
+                }
+            }
+
+            impl fmt::$trait for BigInt {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    write_radix(f, !self.is_negative(), self.magnitude(), $radix, $prefix, $upper)
+                }
+            }
+        )*
+    };
+}
+
+fn write_radix(
+    f: &mut fmt::Formatter,
+    nonnegative: bool,
+    magnitude: &BigUint,
+    radix: u32,
+    prefix: &str,
+    upper: bool,
+) -> fmt::Result {
+    let digits = magnitude.to_str_radix(radix);
+    let digits = if upper { digits.to_uppercase() } else { digits };
+    f.pad_integral(nonnegative, prefix, &digits)
+}
+
+radix_fmt!(Display 10 "" false, Binary 2 "0b" false, Octal 8 "0o" false, LowerHex 16 "0x" false, UpperHex 16 "0x" true);
@@ -0,0 +1,415 @@
+// `BigUint` and the arithmetic on its limbs.
+//
+// A number is stored as base-2^32 digits ("limbs"), least significant
+// first, with no zero limbs at the top; zero has no limbs at all. Every
+// operation works on `&[u32]` slices in that form and returns a `Vec<u32>`
+// in it again.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+// Below this many limbs in the shorter factor, schoolbook multiplication
+// beats Karatsuba's extra additions.
+pub(crate) const KARATSUBA_THRESHOLD: usize = 32;
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint { limbs: Vec::new() }
+    }
+
+    pub fn one() -> BigUint {
+        BigUint { limbs: vec![1] }
+    }
+
+    pub(crate) fn from_limbs(mut limbs: Vec<u32>) -> BigUint {
+        normalize(&mut limbs);
+        BigUint { limbs }
+    }
+
+    pub(crate) fn limbs(&self) -> &[u32] {
+        &self.limbs
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    // The number of bits needed to write the number: 0 for zero, 8 for 255.
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() as u64 * 32 - u64::from(top.leading_zeros()),
+            None => 0,
+        }
+    }
+
+    pub fn pow(&self, mut exponent: u32) -> BigUint {
+        let mut base = self.clone();
+        let mut result = BigUint::one();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    // `self - other`, or `None` where `u32` subtraction would overflow.
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        match cmp(&self.limbs, &other.limbs) {
+            Ordering::Less => None,
+            _ => Some(BigUint::from_limbs(sub(&self.limbs, &other.limbs))),
+        }
+    }
+
+    // Quotient and remainder in one go. Panics when `divisor` is zero.
+    pub fn div_rem(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+        if divisor.is_zero() {
+            panic!("attempt to divide by zero");
+        }
+        let (quotient, remainder) = div_rem(&self.limbs, &divisor.limbs);
+        (BigUint::from_limbs(quotient), BigUint::from_limbs(remainder))
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        self.to_u128().and_then(|n| u64::try_from(n).ok())
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        Some(self.limbs.iter().rev().fold(0, |n, &limb| (n << 32) | u128::from(limb)))
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for BigUint {
+                fn from(n: $ty) -> BigUint {
+                    let mut n = n as u128;
+                    let mut limbs = Vec::new();
+                    while n > 0 {
+                        limbs.push(n as u32);
+                        n >>= 32;
+                    }
+                    BigUint { limbs }
+                }
+            }
+        )*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        cmp(&self.limbs, &other.limbs)
+    }
+}
+
+// Like the primitive integers, `{:?}` is the same as `{}`.
+impl fmt::Debug for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+pub(crate) fn normalize(limbs: &mut Vec<u32>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+pub(crate) fn cmp(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+pub(crate) fn add(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = long.to_vec();
+    add_at(&mut sum, short, 0);
+    sum
+}
+
+// `acc += n << (32 * offset)`, growing `acc` as needed.
+fn add_at(acc: &mut Vec<u32>, n: &[u32], offset: usize) {
+    if acc.len() < offset + n.len() {
+        acc.resize(offset + n.len(), 0);
+    }
+    let mut carry = 0u64;
+    let mut i = offset;
+    for &limb in n {
+        let total = u64::from(acc[i]) + u64::from(limb) + carry;
+        acc[i] = total as u32;
+        carry = total >> 32;
+        i += 1;
+    }
+    while carry > 0 {
+        if i == acc.len() {
+            acc.push(0);
+        }
+        let total = u64::from(acc[i]) + carry;
+        acc[i] = total as u32;
+        carry = total >> 32;
+        i += 1;
+    }
+}
+
+// `a - b`, for `a >= b`.
+pub(crate) fn sub(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = false;
+    for (i, &limb) in a.iter().enumerate() {
+        let (d, under1) = limb.overflowing_sub(b.get(i).copied().unwrap_or(0));
+        let (d, under2) = d.overflowing_sub(u32::from(borrow));
+        difference.push(d);
+        borrow = under1 || under2;
+    }
+    debug_assert!(!borrow, "sub called with a < b");
+    normalize(&mut difference);
+    difference
+}
+
+pub(crate) fn mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return schoolbook(a, b);
+    }
+    karatsuba(a, b)
+}
+
+pub(crate) fn schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let total = u64::from(x) * u64::from(y) + u64::from(product[i + j]) + carry;
+            product[i + j] = total as u32;
+            carry = total >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    normalize(&mut product);
+    product
+}
+
+// Split both numbers at `m` limbs, `a = a1·B^m + a0` and likewise for `b`.
+// Then `a·b = z2·B^2m + z1·B^m + z0` with `z0 = a0·b0`, `z2 = a1·b1` and
+// `z1 = (a0 + a1)(b0 + b1) - z0 - z2`: three half-size products instead
+// of four.
+fn karatsuba(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let m = a.len() / 2;
+    let (a0, a1) = split(a, m);
+    let mut product = Vec::with_capacity(a.len() + b.len());
+
+    // A short `b` has no high half: `a·b = a1·b·B^m + a0·b`.
+    if b.len() <= m {
+        product.extend(mul(&a0, b));
+        add_at(&mut product, &mul(a1, b), m);
+        normalize(&mut product);
+        return product;
+    }
+
+    let (b0, b1) = split(b, m);
+    let z0 = mul(&a0, &b0);
+    let z2 = mul(a1, b1);
+    let z1 = sub(&sub(&mul(&add(&a0, a1), &add(&b0, b1)), &z0), &z2);
+    product.extend(&z0);
+    add_at(&mut product, &z1, m);
+    add_at(&mut product, &z2, 2 * m);
+    normalize(&mut product);
+    product
+}
+
+// The low `m` limbs (normalized) and the rest.
+fn split(n: &[u32], m: usize) -> (Vec<u32>, &[u32]) {
+    let (low, high) = n.split_at(m.min(n.len()));
+    let mut low = low.to_vec();
+    normalize(&mut low);
+    (low, high)
+}
+
+// Division by a single limb, the quick case.
+pub(crate) fn div_rem_small(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0; a.len()];
+    let mut remainder = 0u64;
+    for (i, &limb) in a.iter().enumerate().rev() {
+        let current = (remainder << 32) | u64::from(limb);
+        quotient[i] = (current / u64::from(divisor)) as u32;
+        remainder = current % u64::from(divisor);
+    }
+    normalize(&mut quotient);
+    (quotient, remainder as u32)
+}
+
+// Long division, Knuth's algorithm D (TAOCP vol. 2, 4.3.1), as written up
+// in Hacker's Delight. `b` must not be zero.
+pub(crate) fn div_rem(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if b.len() == 1 {
+        let (quotient, remainder) = div_rem_small(a, b[0]);
+        return (quotient, if remainder == 0 { Vec::new() } else { vec![remainder] });
+    }
+
+    // Shift both so that the divisor's top bit is set; this keeps the
+    // estimated quotient digits at most two too large.
+    let shift = b[b.len() - 1].leading_zeros() as usize;
+    let v = shl(b, shift);
+    let mut u = shl(a, shift);
+    u.resize(a.len() + 1, 0);
+    let n = v.len();
+    let base = 1u64 << 32;
+
+    let mut quotient = vec![0u32; a.len() - n + 1];
+    for j in (0..quotient.len()).rev() {
+        // Estimate the digit from the top two limbs, then correct it.
+        let top = (u64::from(u[j + n]) << 32) | u64::from(u[j + n - 1]);
+        let mut qhat = top / u64::from(v[n - 1]);
+        let mut rhat = top % u64::from(v[n - 1]);
+        while qhat >= base || qhat * u64::from(v[n - 2]) > ((rhat << 32) | u64::from(u[j + n - 2])) {
+            qhat -= 1;
+            rhat += u64::from(v[n - 1]);
+            if rhat >= base {
+                break;
+            }
+        }
+
+        // Multiply and subtract.
+        let mut borrow = 0i64;
+        for i in 0..n {
+            let p = qhat * u64::from(v[i]);
+            let t = i64::from(u[i + j]) - borrow - (p & 0xFFFF_FFFF) as i64;
+            u[i + j] = t as u32;
+            borrow = (p >> 32) as i64 - (t >> 32);
+        }
+        let t = i64::from(u[j + n]) - borrow;
+        u[j + n] = t as u32;
+
+        // The estimate was one too large: add the divisor back.
+        if t < 0 {
+            qhat -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let total = u64::from(u[i + j]) + u64::from(v[i]) + carry;
+                u[i + j] = total as u32;
+                carry = total >> 32;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u32);
+        }
+        quotient[j] = qhat as u32;
+    }
+
+    normalize(&mut quotient);
+    u.truncate(n);
+    normalize(&mut u);
+    (quotient, shr(&u, shift))
+}
+
+pub(crate) fn shl(n: &[u32], bits: usize) -> Vec<u32> {
+    if n.is_empty() {
+        return Vec::new();
+    }
+    let (limbs, bits) = (bits / 32, bits % 32);
+    let mut shifted = vec![0; limbs];
+    if bits == 0 {
+        shifted.extend_from_slice(n);
+    } else {
+        let mut carry = 0;
+        for &limb in n {
+            shifted.push((limb << bits) | carry);
+            carry = limb >> (32 - bits);
+        }
+        if carry > 0 {
+            shifted.push(carry);
+        }
+    }
+    shifted
+}
+
+pub(crate) fn shr(n: &[u32], bits: usize) -> Vec<u32> {
+    let (limbs, bits) = (bits / 32, bits % 32);
+    if limbs >= n.len() {
+        return Vec::new();
+    }
+    let n = &n[limbs..];
+    let mut shifted: Vec<u32> = if bits == 0 {
+        n.to_vec()
+    } else {
+        (0..n.len())
+            .map(|i| (n[i] >> bits) | n.get(i + 1).map_or(0, |next| next << (32 - bits)))
+            .collect()
+    };
+    normalize(&mut shifted);
+    shifted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Deterministic "random" limbs.
+    fn limbs(len: usize, seed: u64) -> Vec<u32> {
+        let mut state = seed;
+        let mut limbs: Vec<u32> = (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                (state >> 32) as u32
+            })
+            .collect();
+        normalize(&mut limbs);
+        limbs
+    }
+
+    #[test]
+    fn karatsuba_agrees_with_schoolbook() {
+        for (len_a, len_b) in [(32, 32), (100, 100), (257, 130), (300, 40), (64, 1000)] {
+            let (a, b) = (limbs(len_a, len_a as u64), limbs(len_b, 7 * len_b as u64));
+            assert_eq!(mul(&a, &b), schoolbook(&a, &b), "{} x {} limbs", len_a, len_b);
+        }
+    }
+
+    #[test]
+    fn divides_back() {
+        for (len_a, len_b) in [(10, 3), (50, 49), (200, 2), (120, 60), (5, 9)] {
+            let (a, b) = (limbs(len_a, 3 + len_a as u64), limbs(len_b, 11 * len_b as u64));
+            let (q, r) = div_rem(&a, &b);
+            assert_eq!(cmp(&r, &b), Ordering::Less);
+            assert_eq!(add(&mul(&q, &b), &r), a, "{} / {} limbs", len_a, len_b);
+        }
+        // The rare add-back step: a quotient digit estimated one too high.
+        let (q, r) = div_rem(&[0, 0, 0x8000_0000, 0x7FFF_FFFF], &[1, 0, 0x8000_0000]);
+        assert_eq!(add(&mul(&q, &[1, 0, 0x8000_0000]), &r), [0, 0, 0x8000_0000, 0x7FFF_FFFF]);
+    }
+
+    #[test]
+    fn shifts_across_limbs() {
+        assert_eq!(shl(&[1], 33), [0, 2]);
+        assert_eq!(shl(&[0x8000_0000], 1), [0, 1]);
+        assert_eq!(shr(&[0, 2], 33), [1]);
+        assert_eq!(shr(&[5], 40), Vec::<u32>::new());
+        assert_eq!(BigUint::from(255u8).bits(), 8);
+        assert_eq!(BigUint::from(u128::MAX).to_u128(), Some(u128::MAX));
+        assert_eq!(BigUint::from(u128::MAX).to_u64(), None);
+    }
+}
@@ -0,0 +1,60 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+// A mistake in an expression: a character that is not part of the
+// language, operands of the wrong types, or an operation that overflows or
+// divides by zero. The messages are the ones rustc would give.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalcError {
+    // Byte offsets of the part at fault, and where it starts as a 1-based
+    // column (in characters) for humans.
+    pub span: Range<usize>,
+    pub column: usize,
+    pub message: String,
+    // What exactly went wrong, for the errors where rustc adds a note:
+    // "attempt to compute `1_u32 - 2_u32`, which would overflow".
+    pub note: Option<String>,
+    // The whole expression, for `{:#}`.
+    pub source: String,
+}
+
+impl CalcError {
+    pub(crate) fn new(source: &str, span: Range<usize>, message: impl Into<String>) -> CalcError {
+        CalcError {
+            column: source[..span.start].chars().count() + 1,
+            span,
+            message: message.into(),
+            note: None,
+            source: source.to_owned(),
+        }
+    }
+
+    pub(crate) fn with_note(self, note: impl Into<String>) -> CalcError {
+        CalcError { note: Some(note.into()), ..self }
+    }
+}
+
+// `{}` is one line; `{:#}` adds the expression with the part at fault
+// underlined, and the note next to it:
+//
+//     this arithmetic operation will overflow at column 1
+//       |
+//       | 1u32 - 2
+//       | ^^^^^^^^ attempt to compute `1_u32 - 2_u32`, which would overflow
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at column {}", self.message, self.column)?;
+        if f.alternate() {
+            let width = self.source[self.span.clone()].chars().count().max(1);
+            let carets = format!("{:>col$}", "^".repeat(width), col = self.column - 1 + width);
+            write!(f, "\n  |\n  | {}\n  | {}", self.source, carets)?;
+            if let Some(note) = &self.note {
+                write!(f, " {}", note)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Error for CalcError {}
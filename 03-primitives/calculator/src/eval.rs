@@ -0,0 +1,434 @@
+// Type inference and evaluation.
+//
+// An unsuffixed literal takes its type from the expression around it:
+// in `1 + 2u8` both are `u8`, and in `(1 + 2) * 3u8` all three are. Only
+// when nothing says otherwise does an integer default to `i32` and a float
+// to `f64`. Both operands of arithmetic, bitwise and comparison operators
+// must end up with the same type, while the right side of a shift is typed
+// on its own, as in Rust.
+//
+// Integer results are computed exactly and then checked against the range
+// of their type, which is how the overflows that rustc's
+// `arithmetic_overflow` lint reports are found.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use bigint::BigInt;
+
+use crate::error::CalcError;
+use crate::parse::{Expr, ExprKind};
+use crate::ty::Ty;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(BigInt, Ty),
+    // Always rounded to the precision of its type.
+    Float(f64, Ty),
+    Bool(bool),
+}
+
+impl Value {
+    pub fn ty(&self) -> Ty {
+        match self {
+            Value::Int(_, ty) | Value::Float(_, ty) => *ty,
+            Value::Bool(_) => Ty::Bool,
+        }
+    }
+}
+
+// Numbers with their type, the way rustc writes them in notes: `255_u8`,
+// `0.5_f64`.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Int(n, ty) => write!(f, "{}_{}", n, ty),
+            Value::Float(x, Ty::F32) => write!(f, "{}_f32", *x as f32),
+            Value::Float(x, ty) => write!(f, "{}_{}", x, ty),
+            Value::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+// What inference knows about an expression's type: either the type, or
+// only that it is an integer or a float whose type is still open.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Class {
+    Known(Ty),
+    Integer,
+    Float,
+}
+
+impl Class {
+    fn or_default(self) -> Ty {
+        match self {
+            Class::Known(ty) => ty,
+            Class::Integer => Ty::I32,
+            Class::Float => Ty::F64,
+        }
+    }
+
+    fn is_integer(self) -> bool {
+        matches!(self, Class::Integer) || matches!(self, Class::Known(ty) if ty.is_integer())
+    }
+
+    fn is_float(self) -> bool {
+        matches!(self, Class::Float) || matches!(self, Class::Known(ty) if ty.is_float())
+    }
+}
+
+// As rustc writes them: `u8`, `{integer}`, `{float}`.
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Class::Known(ty) => write!(f, "{}", ty),
+            Class::Integer => write!(f, "{{integer}}"),
+            Class::Float => write!(f, "{{float}}"),
+        }
+    }
+}
+
+pub(crate) struct Evaluator<'a> {
+    pub(crate) source: &'a str,
+}
+
+impl Evaluator<'_> {
+    fn error(&self, expr: &Expr, message: impl Into<String>) -> CalcError {
+        CalcError::new(self.source, expr.span.clone(), message)
+    }
+
+    fn unsupported(&self, expr: &Expr, op: &str, class: Class) -> CalcError {
+        self.error(expr, format!("binary operation `{}` cannot be applied to type `{}`", op, class))
+    }
+
+    // The type of `expr`, as far as its own literals and operators tell.
+    fn infer(&self, expr: &Expr) -> Result<Class, CalcError> {
+        let (links, operand) = left_edge(expr);
+        let mut class = self.infer_operand(operand)?;
+        for link in links.into_iter().rev() {
+            class = self.infer_link(link, class)?;
+        }
+        Ok(class)
+    }
+
+    fn infer_operand(&self, expr: &Expr) -> Result<Class, CalcError> {
+        Ok(match &expr.kind {
+            ExprKind::Int { suffix: Some(ty), .. } | ExprKind::Float { suffix: Some(ty), .. } => Class::Known(*ty),
+            ExprKind::Int { suffix: None, .. } => Class::Integer,
+            ExprKind::Float { suffix: None, .. } => Class::Float,
+            ExprKind::Bool(_) => Class::Known(Ty::Bool),
+            ExprKind::Unary(op, operand) => {
+                let class = self.infer(operand)?;
+                let allowed = match *op {
+                    "-" => !matches!(class, Class::Known(ty) if !ty.is_signed() && !ty.is_float()),
+                    _ => !class.is_float(),
+                };
+                if !allowed {
+                    return Err(self.error(expr, format!("cannot apply unary operator `{}` to type `{}`", op, class)));
+                }
+                class
+            }
+            ExprKind::Cast(..) | ExprKind::Binary(..) => unreachable!("`left_edge` walks past these"),
+        })
+    }
+
+    // The type of a cast or binary operation whose left operand is a `left`.
+    fn infer_link(&self, expr: &Expr, left: Class) -> Result<Class, CalcError> {
+        Ok(match &expr.kind {
+            ExprKind::Cast(_, to) => {
+                let from = left;
+                let valid = match (from, to) {
+                    (_, Ty::Bool) => from == Class::Known(Ty::Bool),
+                    (Class::Known(Ty::Bool), to) => to.is_integer(),
+                    _ => true,
+                };
+                if !valid {
+                    return Err(self.error(expr, format!("casting `{}` as `{}` is invalid", from, to)));
+                }
+                Class::Known(*to)
+            }
+            ExprKind::Binary(op, left_expr, right) => {
+                let (l, r) = (left, self.infer(right)?);
+                match *op {
+                    "<<" | ">>" => {
+                        for (side, class) in [(left_expr, l), (right, r)] {
+                            if !class.is_integer() {
+                                return Err(self.unsupported(side, op, class));
+                            }
+                        }
+                        l
+                    }
+                    "&&" | "||" => {
+                        for (side, class) in [(left_expr, l), (right, r)] {
+                            if class != Class::Known(Ty::Bool) {
+                                let message = format!("mismatched types: expected `bool`, found `{}`", class);
+                                return Err(self.error(side, message));
+                            }
+                        }
+                        Class::Known(Ty::Bool)
+                    }
+                    _ => {
+                        let class = self.unify(l, r, right)?;
+                        let allowed = match *op {
+                            "==" | "!=" | "<" | ">" | "<=" | ">=" => return Ok(Class::Known(Ty::Bool)),
+                            "&" | "|" | "^" => !class.is_float(),
+                            _ => class != Class::Known(Ty::Bool),
+                        };
+                        if !allowed {
+                            return Err(self.unsupported(expr, op, class));
+                        }
+                        class
+                    }
+                }
+            }
+            _ => unreachable!("only casts and binary operations are links"),
+        })
+    }
+
+    // The one type two operands must share; `right` is blamed if there is
+    // none.
+    fn unify(&self, left: Class, right: Class, blame: &Expr) -> Result<Class, CalcError> {
+        match (left, right) {
+            (a, b) if a == b => Ok(a),
+            (Class::Known(ty), open) | (open, Class::Known(ty))
+                if (open == Class::Integer && ty.is_integer()) || (open == Class::Float && ty.is_float()) =>
+            {
+                Ok(Class::Known(ty))
+            }
+            _ => Err(self.error(blame, format!("mismatched types: expected `{}`, found `{}`", left, right))),
+        }
+    }
+
+    // The type `expr` ends up with when nothing around it decides.
+    pub(crate) fn resolve(&self, expr: &Expr) -> Result<Ty, CalcError> {
+        self.infer(expr).map(Class::or_default)
+    }
+
+    // Evaluates `expr` as a `ty`, which inference has already checked it
+    // can be.
+    pub(crate) fn eval(&self, expr: &Expr, ty: Ty) -> Result<Value, CalcError> {
+        let (links, operand) = left_edge(expr);
+        // The type each link's left operand is evaluated as, found from the
+        // top down, after the classes of those operands from the bottom up.
+        let mut classes = vec![self.infer_operand(operand)?];
+        for link in links.iter().skip(1).rev() {
+            classes.push(self.infer_link(link, classes[classes.len() - 1])?);
+        }
+        let mut types = vec![ty];
+        for (link, left) in links.iter().zip(classes.iter().rev()) {
+            let parent = types[types.len() - 1];
+            types.push(match &link.kind {
+                ExprKind::Binary("&&" | "||", ..) => Ty::Bool,
+                ExprKind::Binary("==" | "!=" | "<" | ">" | "<=" | ">=", _, right) => {
+                    self.unify(*left, self.infer(right)?, right)?.or_default()
+                }
+                ExprKind::Binary(..) => parent,
+                _ => left.or_default(),
+            });
+        }
+
+        let mut value = self.eval_operand(operand, types[types.len() - 1])?;
+        for (i, link) in links.iter().enumerate().rev() {
+            value = match &link.kind {
+                ExprKind::Cast(_, to) => cast(value, *to),
+                ExprKind::Binary(op, _, right) => self.binary(link, op, value, right, types[i], types[i + 1])?,
+                _ => unreachable!("only casts and binary operations are links"),
+            };
+        }
+        Ok(value)
+    }
+
+    fn eval_operand(&self, expr: &Expr, ty: Ty) -> Result<Value, CalcError> {
+        match &expr.kind {
+            ExprKind::Int { value, .. } => self.literal(expr, BigInt::from(value.clone()), ty),
+            ExprKind::Float { digits, .. } => {
+                let x = match ty {
+                    Ty::F32 => digits.parse::<f32>().map(f64::from),
+                    _ => digits.parse::<f64>(),
+                }
+                .expect("the lexer only lets valid floats through");
+                if x.is_infinite() {
+                    let note = format!("the literal `{}` does not fit into the type `{}` and will be converted to `{}::INFINITY`", digits, ty, ty);
+                    return Err(self.error(expr, format!("literal out of range for `{}`", ty)).with_note(note));
+                }
+                Ok(Value::Float(x, ty))
+            }
+            ExprKind::Bool(b) => Ok(Value::Bool(*b)),
+            ExprKind::Unary("-", operand) => {
+                // `-128i8` is fine even though `128i8` alone is not.
+                if let ExprKind::Int { value, .. } = &operand.kind {
+                    return self.literal(expr, -BigInt::from(value.clone()), ty);
+                }
+                match self.eval(operand, ty)? {
+                    Value::Float(x, ty) => Ok(Value::Float(-x, ty)),
+                    Value::Int(n, ty) => {
+                        let negated = -n;
+                        if !ty.contains(&negated) {
+                            let note = format!("attempt to negate `{}::MIN`, which would overflow", ty);
+                            return Err(self.error(expr, "this arithmetic operation will overflow").with_note(note));
+                        }
+                        Ok(Value::Int(negated, ty))
+                    }
+                    Value::Bool(_) => unreachable!("inference rejects `-bool`"),
+                }
+            }
+            ExprKind::Unary(_, operand) => match self.eval(operand, ty)? {
+                Value::Int(n, ty) => Ok(Value::Int(ty.wrap(&!n), ty)),
+                Value::Bool(b) => Ok(Value::Bool(!b)),
+                Value::Float(..) => unreachable!("inference rejects `!` on floats"),
+            },
+            ExprKind::Cast(..) | ExprKind::Binary(..) => unreachable!("`left_edge` walks past these"),
+        }
+    }
+
+    fn literal(&self, expr: &Expr, n: BigInt, ty: Ty) -> Result<Value, CalcError> {
+        if !ty.contains(&n) {
+            let note = format!(
+                "the literal `{}` does not fit into the type `{}` whose range is `{}..={}`",
+                &self.source[expr.span.clone()],
+                ty,
+                ty.min(),
+                ty.max()
+            );
+            return Err(self.error(expr, format!("literal out of range for `{}`", ty)).with_note(note));
+        }
+        Ok(Value::Int(n, ty))
+    }
+
+    // `left` has already been evaluated, as a `left_ty`.
+    fn binary(&self, expr: &Expr, op: &str, left: Value, right: &Expr, ty: Ty, left_ty: Ty) -> Result<Value, CalcError> {
+        match op {
+            "&&" | "||" => {
+                let Value::Bool(l) = left else { unreachable!("inference checks for `bool`") };
+                // Short-circuits: the right side is not evaluated at all.
+                if l == (op == "||") {
+                    return Ok(Value::Bool(l));
+                }
+                self.eval(right, Ty::Bool)
+            }
+            "==" | "!=" | "<" | ">" | "<=" | ">=" => {
+                let ordering = match (left, self.eval(right, left_ty)?) {
+                    (Value::Int(a, _), Value::Int(b, _)) => Some(a.cmp(&b)),
+                    (Value::Float(a, _), Value::Float(b, _)) => a.partial_cmp(&b),
+                    (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(&b)),
+                    _ => unreachable!("both sides have the same type"),
+                };
+                Ok(Value::Bool(match op {
+                    "==" => ordering == Some(Ordering::Equal),
+                    "!=" => ordering != Some(Ordering::Equal),
+                    "<" => ordering == Some(Ordering::Less),
+                    ">" => ordering == Some(Ordering::Greater),
+                    "<=" => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                    _ => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+                }))
+            }
+            "<<" | ">>" => {
+                let Value::Int(n, ty) = left else { unreachable!("inference checks for integers") };
+                let amount = self.eval(right, self.resolve(right)?)?;
+                let shift = match &amount {
+                    Value::Int(bits, _) => bits.to_i64().filter(|&bits| (0..i64::from(ty.bits())).contains(&bits)),
+                    _ => unreachable!("inference checks for integers"),
+                };
+                let Some(shift) = shift else {
+                    let direction = if op == "<<" { "left" } else { "right" };
+                    let note = format!("attempt to shift {} by `{}`, which would overflow", direction, amount);
+                    return Err(self.error(expr, "this arithmetic operation will overflow").with_note(note));
+                };
+                // Bits shifted out are lost rather than an overflow.
+                let result = if op == "<<" { ty.wrap(&(n << shift as u32)) } else { n >> shift as u32 };
+                Ok(Value::Int(result, ty))
+            }
+            _ => {
+                let (a, b) = (left, self.eval(right, ty)?);
+                match (&a, &b) {
+                    (Value::Int(..), Value::Int(..)) => self.integer(expr, op, &a, &b),
+                    (Value::Float(x, _), Value::Float(y, _)) => {
+                        let result = match op {
+                            "+" => x + y,
+                            "-" => x - y,
+                            "*" => x * y,
+                            "/" => x / y,
+                            _ => x % y,
+                        };
+                        let result = if ty == Ty::F32 { f64::from(result as f32) } else { result };
+                        Ok(Value::Float(result, ty))
+                    }
+                    (Value::Bool(x), Value::Bool(y)) => Ok(Value::Bool(match op {
+                        "&" => x & y,
+                        "|" => x | y,
+                        _ => x ^ y,
+                    })),
+                    _ => unreachable!("both sides have the same type"),
+                }
+            }
+        }
+    }
+
+    fn integer(&self, expr: &Expr, op: &str, a: &Value, b: &Value) -> Result<Value, CalcError> {
+        let (Value::Int(x, ty), Value::Int(y, _)) = (a, b) else { unreachable!("called with two integers") };
+        if y.is_zero() && (op == "/" || op == "%") {
+            let note = match op {
+                "/" => format!("attempt to divide `{}` by zero", a),
+                _ => format!("attempt to calculate the remainder of `{}` with a divisor of zero", a),
+            };
+            return Err(self.error(expr, "this operation will panic at runtime").with_note(note));
+        }
+        let result = match op {
+            "+" => x + y,
+            "-" => x - y,
+            "*" => x * y,
+            "/" => x / y,
+            "%" => x % y,
+            "&" => x & y,
+            "|" => x | y,
+            _ => x ^ y,
+        };
+        if !ty.contains(&result) {
+            let note = format!("attempt to compute `{} {} {}`, which would overflow", a, op, b);
+            return Err(self.error(expr, "this arithmetic operation will overflow").with_note(note));
+        }
+        Ok(Value::Int(result, *ty))
+    }
+}
+
+// The casts and binary operations down the left side of `expr`, from the
+// top, and the operand they end at. `1 + 2 + 3` is `(1 + 2) + 3`, so a long
+// chain goes down this edge; walking it in a loop rather than recursively
+// keeps such chains from running out of stack.
+fn left_edge(expr: &Expr) -> (Vec<&Expr>, &Expr) {
+    let mut links = Vec::new();
+    let mut operand = expr;
+    while let ExprKind::Binary(_, left, _) | ExprKind::Cast(left, _) = &operand.kind {
+        links.push(operand);
+        operand = left;
+    }
+    (links, operand)
+}
+
+fn int_to_float(n: &BigInt, ty: Ty) -> f64 {
+    // Every integer type fits in an `i128` or a `u128`; converting straight
+    // to `f32` avoids rounding twice.
+    match (n.to_i128(), ty) {
+        (Some(i), Ty::F32) => f64::from(i as f32),
+        (Some(i), _) => i as f64,
+        (None, Ty::F32) => f64::from(n.magnitude().to_u128().expect("at most 128 bits") as f32),
+        (None, _) => n.magnitude().to_u128().expect("at most 128 bits") as f64,
+    }
+}
+
+// `as`: integers wrap, floats saturate when turned into integers (NaN
+// becomes 0), and `bool` is 0 or 1.
+fn cast(value: Value, to: Ty) -> Value {
+    match value {
+        Value::Bool(b) if to == Ty::Bool => Value::Bool(b),
+        Value::Bool(b) => Value::Int(BigInt::from(u8::from(b)), to),
+        Value::Int(n, _) if to.is_float() => Value::Float(int_to_float(&n, to), to),
+        Value::Int(n, _) => Value::Int(to.wrap(&n), to),
+        Value::Float(x, _) if to == Ty::F32 => Value::Float(f64::from(x as f32), to),
+        Value::Float(x, _) if to.is_float() => Value::Float(x, to),
+        Value::Float(x, _) => {
+            let n = if to.is_signed() { BigInt::from(x as i128) } else { BigInt::from(x as u128) };
+            Value::Int(n.clamp(to.min(), to.max()), to)
+        }
+    }
+}
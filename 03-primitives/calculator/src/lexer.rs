@@ -0,0 +1,228 @@
+// Splits an expression into tokens. Number literals follow Rust's rules:
+// `_` anywhere among the digits, `0x`, `0o` and `0b` prefixes, a fraction
+// and an exponent for floats, and a type suffix such as `u8` or `f32`.
+
+use std::ops::Range;
+
+use bigint::BigUint;
+
+use crate::error::CalcError;
+use crate::ty::Ty;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    Int { value: BigUint, suffix: Option<Ty> },
+    // The digits are kept as text, because a float literal is rounded to
+    // `f32` or `f64` only once its type is known.
+    Float { digits: String, suffix: Option<Ty> },
+    Bool(bool),
+    // An operator or a parenthesis.
+    Op(&'static str),
+    As,
+    Type(Ty),
+    Eof,
+}
+
+// Longest first, so that `<<` is not read as two `<`.
+const OPERATORS: [&str; 21] = [
+    "<<", ">>", "<=", ">=", "==", "!=", "&&", "||", "+", "-", "*", "/", "%", "&", "|", "^", "!", "<", ">", "(", ")",
+];
+
+pub(crate) fn tokenize(source: &str) -> Result<Vec<(Token, Range<usize>)>, CalcError> {
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while let Some(c) = source[pos..].chars().next() {
+        let start = pos;
+        let rest = &source[pos..];
+        let token = if c.is_whitespace() {
+            pos += c.len_utf8();
+            continue;
+        } else if c.is_ascii_digit() {
+            let (token, len) = number(source, pos)?;
+            pos += len;
+            token
+        } else if c.is_alphabetic() || c == '_' {
+            let word = identifier(rest);
+            pos += word.len();
+            match word {
+                "true" => Token::Bool(true),
+                "false" => Token::Bool(false),
+                "as" => Token::As,
+                _ => match Ty::from_name(word) {
+                    Some(ty) => Token::Type(ty),
+                    None => {
+                        let message = format!("cannot find value `{}` in this scope", word);
+                        return Err(CalcError::new(source, start..pos, message));
+                    }
+                },
+            }
+        } else if let Some(op) = OPERATORS.into_iter().find(|op| rest.starts_with(op)) {
+            pos += op.len();
+            Token::Op(op)
+        } else {
+            let message = format!("unknown start of token: {}", c);
+            return Err(CalcError::new(source, start..start + c.len_utf8(), message));
+        };
+        tokens.push((token, start..pos));
+    }
+    tokens.push((Token::Eof, pos..pos));
+    Ok(tokens)
+}
+
+fn identifier(text: &str) -> &str {
+    let end = text.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(text.len());
+    &text[..end]
+}
+
+// The number literal at `start`, and its length in bytes.
+fn number(source: &str, start: usize) -> Result<(Token, usize), CalcError> {
+    let text = &source[start..];
+    let error = |range: Range<usize>, message: String| CalcError::new(source, start + range.start..start + range.end, message);
+
+    let (radix, prefix) = match text.get(..2) {
+        Some("0x") => (16, 2),
+        Some("0o") => (8, 2),
+        Some("0b") => (2, 2),
+        _ => (10, 0),
+    };
+    // Hex digits include `a` to `f`; for the other radixes a digit that is
+    // too big (`0b102`) is still read as a digit, so it can be reported.
+    let is_digit = |c: char| c == '_' || if radix == 16 { c.is_ascii_hexdigit() } else { c.is_ascii_digit() };
+    let digits_end = |from: usize| text[from..].find(|c| !is_digit(c)).map_or(text.len(), |i| from + i);
+
+    let mut end = digits_end(prefix);
+    let mut float = false;
+    if radix == 10 {
+        // `1.5` and `1.` are floats, but `1..2` and `1.max(2)` would not be.
+        let after_dot = text[end..].strip_prefix('.').map(|after| after.chars().next());
+        if let Some(next) = after_dot
+            && !next.is_some_and(|c| c == '.' || c.is_alphabetic() || c == '_')
+        {
+            float = true;
+            end = digits_end(end + 1);
+        }
+        let exponent = text[end..].strip_prefix(['e', 'E']).map(|after| after.strip_prefix(['+', '-']).unwrap_or(after));
+        if let Some(exponent) = exponent
+            && exponent.trim_start_matches('_').starts_with(|c: char| c.is_ascii_digit())
+        {
+            float = true;
+            end = digits_end(text.len() - exponent.len());
+        }
+    }
+    let digits: String = text[prefix..end].chars().filter(|&c| c != '_').collect();
+    let suffix_end = end + identifier(&text[end..]).len();
+    let suffix = &text[end..suffix_end];
+
+    if digits.is_empty() {
+        return Err(error(0..suffix_end, String::from("no valid digits found for number")));
+    }
+    if radix < 10
+        && let Some(i) = text[prefix..end].find(|c: char| c.to_digit(radix).is_none() && c != '_')
+    {
+        let at = prefix + i;
+        return Err(error(at..at + 1, format!("invalid digit for a base {} literal", radix)));
+    }
+
+    let suffix = match (suffix, Ty::from_name(suffix)) {
+        ("", _) => None,
+        (_, Some(ty)) if ty.is_integer() && !float => Some(ty),
+        (_, Some(ty)) if ty.is_float() && radix == 10 => {
+            float = true;
+            Some(ty)
+        }
+        (_, Some(ty)) if ty.is_float() => {
+            let name = if radix == 2 { "binary" } else if radix == 8 { "octal" } else { "hexadecimal" };
+            return Err(error(0..suffix_end, format!("{} float literal is not supported", name)));
+        }
+        _ => {
+            let kind = if float { "float" } else { "number" };
+            return Err(error(end..suffix_end, format!("invalid suffix `{}` for {} literal", suffix, kind)));
+        }
+    };
+
+    let token = if float {
+        Token::Float { digits, suffix }
+    } else {
+        let value = BigUint::from_str_radix(&digits, radix).expect("digits were checked");
+        if value.bits() > 128 {
+            return Err(error(0..suffix_end, String::from("integer literal is too large")));
+        }
+        Token::Int { value, suffix }
+    };
+    Ok((token, suffix_end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(source: &str) -> Vec<Token> {
+        tokenize(source).unwrap().into_iter().map(|(token, _)| token).collect()
+    }
+
+    fn int(value: u32, suffix: Option<Ty>) -> Token {
+        Token::Int { value: BigUint::from(value), suffix }
+    }
+
+    fn float(digits: &str, suffix: Option<Ty>) -> Token {
+        Token::Float { digits: digits.to_owned(), suffix }
+    }
+
+    #[test]
+    fn reads_literals() {
+        assert_eq!(
+            tokens("0b0011u32 0x80 0o17 1_000_000 0xffu8 1e4 -2.5e-3 1f32 1. 2E+1_0"),
+            [
+                int(3, Some(Ty::U32)),
+                int(128, None),
+                int(15, None),
+                int(1_000_000, None),
+                int(255, Some(Ty::U8)),
+                float("1e4", None),
+                Token::Op("-"),
+                float("2.5e-3", None),
+                float("1", Some(Ty::F32)),
+                float("1.", None),
+                float("2E+10", None),
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_operators_and_words() {
+        assert_eq!(
+            tokens("!(1<<2)>=3 && true as u8"),
+            [
+                Token::Op("!"),
+                Token::Op("("),
+                int(1, None),
+                Token::Op("<<"),
+                int(2, None),
+                Token::Op(")"),
+                Token::Op(">="),
+                int(3, None),
+                Token::Op("&&"),
+                Token::Bool(true),
+                Token::As,
+                Token::Type(Ty::U8),
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_bad_literals() {
+        let error = |source: &str| {
+            let e = tokenize(source).unwrap_err();
+            (e.message, e.column)
+        };
+        assert_eq!(error("1 + 0b102"), (String::from("invalid digit for a base 2 literal"), 9));
+        assert_eq!(error("0x"), (String::from("no valid digits found for number"), 1));
+        assert_eq!(error("1.5u8"), (String::from("invalid suffix `u8` for float literal"), 4));
+        assert_eq!(error("7 + 1xyz"), (String::from("invalid suffix `xyz` for number literal"), 6));
+        assert_eq!(error("0b1f32"), (String::from("binary float literal is not supported"), 1));
+        assert_eq!(error("1 + x"), (String::from("cannot find value `x` in this scope"), 5));
+        assert_eq!(error(&"9".repeat(40)), (String::from("integer literal is too large"), 1));
+    }
+}
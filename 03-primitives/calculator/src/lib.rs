@@ -0,0 +1,158 @@
+// A calculator for Rust expressions on primitive values.
+//
+// It reads the literals rustc reads (`0b0011u32`, `0x80`, `1_000`,
+// `-2.5e-3`, `1f32`), applies the arithmetic, bitwise, shift, comparison
+// and boolean operators with Rust's precedence, and infers types from
+// suffixes the way rustc does: `1 + 2u8` is a `u8`, and a plain `1` is an
+// `i32` unless something says otherwise.
+//
+//     evaluate("0b0011u32 & 0b0101")     Ok(1_u32)
+//     evaluate("0x80u32 >> 2")           Ok(32_u32)
+//     evaluate("300 as u8")              Ok(44_u8)
+//     evaluate("1u32 - 2")               Err(this arithmetic operation will overflow)
+//
+// What rustc rejects at compile time is an error here too, with the same
+// message: mixing types, overflowing arithmetic (where the program would
+// panic in a debug build), shifting by too much, dividing by zero and
+// literals too big for their type. So is nesting parentheses or prefix
+// operators deeper than `NESTING_LIMIT`, which rustc would refuse for its
+// own reasons.
+
+mod error;
+mod eval;
+mod lexer;
+mod parse;
+mod ty;
+
+pub use error::CalcError;
+pub use eval::Value;
+pub use parse::NESTING_LIMIT;
+pub use ty::Ty;
+
+use eval::Evaluator;
+
+pub fn evaluate(source: &str) -> Result<Value, CalcError> {
+    let expr = parse::parse(source)?;
+    let evaluator = Evaluator { source };
+    evaluator.eval(&expr, evaluator.resolve(&expr)?)
+}
+
+// The type `evaluate` would give the result, without evaluating it; an
+// expression that overflows still has a type.
+pub fn type_of(source: &str) -> Result<Ty, CalcError> {
+    Evaluator { source }.resolve(&parse::parse(source)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(source: &str) -> String {
+        match evaluate(source) {
+            Ok(value) => value.to_string(),
+            Err(e) => panic!("{}: {:#}", source, e),
+        }
+    }
+
+    // The message and the note.
+    fn fail(source: &str) -> (String, Option<String>) {
+        let e = evaluate(source).unwrap_err();
+        (e.message, e.note)
+    }
+
+    #[test]
+    fn evaluates_like_rust() {
+        assert_eq!(eval("1u32 + 2"), "3_u32");
+        assert_eq!(eval("1i32 - 2"), "-1_i32");
+        assert_eq!(eval("1e4"), "10000_f64");
+        assert_eq!(eval("-2.5e-3"), "-0.0025_f64");
+        assert_eq!(eval("true && false || !true"), "false");
+        assert_eq!(eval("0b0011u32 ^ 0b0101"), "6_u32");
+        assert_eq!(eval("1u32 << 5"), "32_u32");
+        assert_eq!(eval("1_000_000u32"), "1000000_u32");
+        assert_eq!(eval("7 / -2"), "-3_i32");
+        assert_eq!(eval("7 % -2"), "1_i32");
+        assert_eq!(eval("-7 >> 1"), "-4_i32");
+        assert_eq!(eval("!0u8"), "255_u8");
+        assert_eq!(eval("0.1 + 0.2"), "0.30000000000000004_f64");
+        assert_eq!(eval("0.1f32 + 0.2"), "0.3_f32");
+        assert_eq!(eval("2 * (3 + 4) == 14"), "true");
+        assert_eq!(eval("-128i8"), "-128_i8");
+        assert_eq!(eval("0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffffu128"), "340282366920938463463374607431768211455_u128");
+    }
+
+    #[test]
+    fn infers_from_suffixes() {
+        assert_eq!(eval("(1 + 2) * 3u8"), "9_u8");
+        assert_eq!(eval("1i64 << 40u8"), "1099511627776_i64");
+        assert_eq!(type_of("1 << 40u64"), Ok(Ty::I32));
+        assert_eq!(type_of("1u64 << 40"), Ok(Ty::U64));
+        assert_eq!(type_of("1.5 < 2f32"), Ok(Ty::Bool));
+        assert_eq!(type_of("255u8 + 1"), Ok(Ty::U8));
+        assert_eq!(fail("1u32 + 2i64").0, "mismatched types: expected `u32`, found `i64`");
+        assert_eq!(fail("1 + 2.0").0, "mismatched types: expected `{integer}`, found `{float}`");
+        assert_eq!(fail("1 && true").0, "mismatched types: expected `bool`, found `{integer}`");
+        assert_eq!(fail("-1u8").0, "cannot apply unary operator `-` to type `u8`");
+        assert_eq!(fail("1.5 & 2.5").0, "binary operation `&` cannot be applied to type `{float}`");
+        assert_eq!(fail("1.5 as bool").0, "casting `{float}` as `bool` is invalid");
+    }
+
+    #[test]
+    fn casts() {
+        assert_eq!(eval("300 as u8"), "44_u8");
+        assert_eq!(eval("-1i8 as u32"), "4294967295_u32");
+        assert_eq!(eval("200u8 as i8"), "-56_i8");
+        assert_eq!(eval("-1.5 as u8"), "0_u8");
+        assert_eq!(eval("1e10 as i32"), "2147483647_i32");
+        assert_eq!(eval("true as u8 + 1"), "2_u8");
+        assert_eq!(eval("16777217 as f32"), "16777216_f32");
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let overflow = |note: &str| (String::from("this arithmetic operation will overflow"), Some(note.to_owned()));
+        assert_eq!(fail("1u32 - 2"), overflow("attempt to compute `1_u32 - 2_u32`, which would overflow"));
+        assert_eq!(fail("255u8 + 1"), overflow("attempt to compute `255_u8 + 1_u8`, which would overflow"));
+        assert_eq!(fail("2147483647 + 1"), overflow("attempt to compute `2147483647_i32 + 1_i32`, which would overflow"));
+        assert_eq!(fail("-128i8 / -1"), overflow("attempt to compute `-128_i8 / -1_i8`, which would overflow"));
+        assert_eq!(fail("-(-128i8)"), overflow("attempt to negate `i8::MIN`, which would overflow"));
+        assert_eq!(fail("1 << 32"), overflow("attempt to shift left by `32_i32`, which would overflow"));
+        assert_eq!(fail("1u8 >> -1"), overflow("attempt to shift right by `-1_i32`, which would overflow"));
+        assert_eq!(
+            fail("1 / (2 - 2)"),
+            (String::from("this operation will panic at runtime"), Some(String::from("attempt to divide `1_i32` by zero")))
+        );
+        assert_eq!(
+            fail("256u8"),
+            (
+                String::from("literal out of range for `u8`"),
+                Some(String::from("the literal `256u8` does not fit into the type `u8` whose range is `0..=255`"))
+            )
+        );
+        // Bits shifted out of a `<<` are simply lost.
+        assert_eq!(eval("0xffu8 << 4"), "240_u8");
+        // And the right side of `&&` is never evaluated when the left is false.
+        assert_eq!(eval("false && 255u8 + 1 == 0"), "false");
+    }
+
+    #[test]
+    fn underlines_the_culprit() {
+        let e = evaluate("2 * (1u32 - 2)").unwrap_err();
+        assert_eq!(
+            format!("{:#}", e),
+            "this arithmetic operation will overflow at column 6\n  |\n  | 2 * (1u32 - 2)\n  |      ^^^^^^^^ attempt to compute `1_u32 - 2_u32`, which would overflow"
+        );
+    }
+
+    #[test]
+    fn evaluates_up_to_the_nesting_limit() {
+        let sum = vec!["1u64"; 50_000].join(" + ");
+        assert_eq!(evaluate(&sum).unwrap().to_string(), "50000_u64");
+        let casts = format!("300{}", " as u8 as i64".repeat(50_000));
+        assert_eq!(evaluate(&casts).unwrap().to_string(), "44_i64");
+        let negated = format!("{}1", "-".repeat(NESTING_LIMIT - 1));
+        assert_eq!(evaluate(&negated).unwrap().to_string(), "-1_i32");
+        let e = evaluate(&format!("{}1{}", "(".repeat(50_000), ")".repeat(50_000))).unwrap_err();
+        assert_eq!(e.to_string(), "expression is nested too deeply at column 129");
+    }
+}
@@ -0,0 +1,67 @@
+// A read-eval-print loop for the calculator:
+//
+//     $ cargo run
+//     > 0b0011u32 & 0b0101
+//     1_u32
+//     > 1u32 - 2
+//     error: this arithmetic operation will overflow at column 1
+//       |
+//       | 1u32 - 2
+//       | ^^^^^^^^ attempt to compute `1_u32 - 2_u32`, which would overflow
+//     > :type 1 << 40u64
+//     i32
+//
+// With arguments, it evaluates them as one expression and exits, with 1
+// when that fails: `cargo run -- '300 as u8'`.
+
+use std::process;
+
+use calculator::{evaluate, type_of};
+use prompt::{Prompt, PromptError};
+
+const HELP: &str = "\
+Enter an expression such as `0x80u32 >> 2` or `1e4 as u16 * 2`.
+  :type <expr>   show the type of an expression without evaluating it
+  :help          show this message
+  :quit          leave (so does end of input)";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        match evaluate(&args.join(" ")) {
+            Ok(value) => println!("{}", value),
+            Err(e) => {
+                eprintln!("error: {:#}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let mut prompt = Prompt::stdio();
+    loop {
+        let line: String = match prompt.ask(">") {
+            Ok(line) => line,
+            Err(PromptError::Eof) => {
+                println!();
+                break;
+            }
+            Err(e) => {
+                eprintln!("error: {}", e);
+                process::exit(2);
+            }
+        };
+        let result = match line.split_once(' ').unwrap_or((&line, "")) {
+            ("", _) => continue,
+            (":quit" | ":q", _) => break,
+            (":help" | ":h", _) => Ok(String::from(HELP)),
+            (":type" | ":t", expr) => type_of(expr).map(|ty| ty.to_string()),
+            (command, _) if command.starts_with(':') => Ok(format!("unknown command `{}`; try :help", command)),
+            _ => evaluate(&line).map(|value| value.to_string()),
+        };
+        match result {
+            Ok(output) => println!("{}", output),
+            Err(e) => println!("error: {:#}", e),
+        }
+    }
+}
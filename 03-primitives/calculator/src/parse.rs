@@ -0,0 +1,274 @@
+// Builds the expression tree, with Rust's operator precedence. From the
+// loosest to the tightest:
+//
+//     ||
+//     &&
+//     == != < > <= >=     (cannot be chained: `a < b < c` is an error)
+//     |
+//     ^
+//     &
+//     << >>
+//     + -
+//     * / %
+//     as
+//     unary - !
+//
+// Every binary operator is left-associative.
+//
+// Like rustc's `recursion_limit`, nesting is capped: at most `NESTING_LIMIT`
+// parentheses and prefix operators may be open at once, so that parsing
+// cannot run out of stack. A flat chain like `1 + 2 + 3 + ...` nests nothing
+// and may be as long as it likes; it is built in a loop here, and
+// evaluated and dropped in loops too.
+
+use std::ops::Range;
+
+use bigint::BigUint;
+
+use crate::error::CalcError;
+use crate::lexer::{tokenize, Token};
+use crate::ty::Ty;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Expr {
+    pub(crate) kind: ExprKind,
+    pub(crate) span: Range<usize>,
+}
+
+// The derived drop would recurse down the left operands of a long chain
+// and overflow the stack, so the chain is taken apart in a loop instead.
+impl Drop for Expr {
+    fn drop(&mut self) {
+        let mut next = take_left(&mut self.kind);
+        while let Some(mut expr) = next {
+            next = take_left(&mut expr.kind);
+        }
+    }
+}
+
+fn take_left(kind: &mut ExprKind) -> Option<Box<Expr>> {
+    match kind {
+        ExprKind::Binary(_, left, _) | ExprKind::Cast(left, _) => {
+            Some(std::mem::replace(left, Box::new(Expr { kind: ExprKind::Bool(false), span: 0..0 })))
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ExprKind {
+    Int { value: BigUint, suffix: Option<Ty> },
+    Float { digits: String, suffix: Option<Ty> },
+    Bool(bool),
+    // `-` or `!`.
+    Unary(&'static str, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Cast(Box<Expr>, Ty),
+}
+
+// How tightly each binary operator binds; `as` binds tighter than all of
+// them.
+fn precedence(op: &str) -> Option<u8> {
+    Some(match op {
+        "||" => 1,
+        "&&" => 2,
+        "==" | "!=" | "<" | ">" | "<=" | ">=" => 3,
+        "|" => 4,
+        "^" => 5,
+        "&" => 6,
+        "<<" | ">>" => 7,
+        "+" | "-" => 8,
+        "*" | "/" | "%" => 9,
+        _ => return None,
+    })
+}
+
+const COMPARISON: u8 = 3;
+const CAST: u8 = 10;
+
+pub const NESTING_LIMIT: usize = 128;
+
+pub(crate) fn parse(source: &str) -> Result<Expr, CalcError> {
+    let mut parser = Parser { source, tokens: tokenize(source)?, next: 0, nesting: 0 };
+    let expr = parser.expr(0)?;
+    match parser.peek() {
+        Token::Eof => Ok(expr),
+        Token::Op(")") => Err(parser.error("unexpected closing delimiter: `)`")),
+        _ => Err(parser.error("expected an operator")),
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<(Token, Range<usize>)>,
+    next: usize,
+    // Calls to `unary` under way: open parentheses and prefix operators.
+    nesting: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.next].0
+    }
+
+    fn span(&self) -> Range<usize> {
+        self.tokens[self.next].1.clone()
+    }
+
+    fn bump(&mut self) -> (Token, Range<usize>) {
+        let token = self.tokens[self.next].clone();
+        if token.0 != Token::Eof {
+            self.next += 1;
+        }
+        token
+    }
+
+    // Where the last token taken ends.
+    fn end(&self) -> usize {
+        self.tokens[self.next.saturating_sub(1)].1.end
+    }
+
+    // An error at the next token.
+    fn error(&self, message: &str) -> CalcError {
+        CalcError::new(self.source, self.span(), message)
+    }
+
+    fn too_deep(&self, span: Range<usize>) -> CalcError {
+        CalcError::new(self.source, span, "expression is nested too deeply")
+            .with_note(format!("the nesting limit is {}", NESTING_LIMIT))
+    }
+
+    // Operators binding tighter than `min` are part of this expression;
+    // looser ones are left for the caller.
+    fn expr(&mut self, min: u8) -> Result<Expr, CalcError> {
+        // Spans run from the first token to the last, parentheses included,
+        // while a parenthesized expression keeps the span of what is inside.
+        let start = self.span().start;
+        let mut left = self.unary()?;
+        loop {
+            let (op, bp) = match self.peek() {
+                Token::As if CAST > min => (None, CAST),
+                &Token::Op(op) => match precedence(op) {
+                    Some(bp) if bp > min => (Some(op), bp),
+                    _ => break,
+                },
+                _ => break,
+            };
+            self.bump();
+            let Some(op) = op else {
+                let ty = match self.bump() {
+                    (Token::Type(ty), _) => ty,
+                    (_, span) => return Err(CalcError::new(self.source, span, "expected a type after `as`")),
+                };
+                left = Expr { kind: ExprKind::Cast(Box::new(left), ty), span: start..self.end() };
+                continue;
+            };
+
+            let right = self.expr(bp)?;
+            left = Expr { kind: ExprKind::Binary(op, Box::new(left), Box::new(right)), span: start..self.end() };
+            if bp == COMPARISON
+                && let &Token::Op(next) = self.peek()
+                && precedence(next) == Some(COMPARISON)
+            {
+                return Err(self.error("comparison operators cannot be chained"));
+            }
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, CalcError> {
+        if self.nesting == NESTING_LIMIT {
+            return Err(self.too_deep(self.span()));
+        }
+        self.nesting += 1;
+        let result = self.operand();
+        self.nesting -= 1;
+        result
+    }
+
+    // A literal, a parenthesized expression, or a prefix operator and its
+    // operand.
+    fn operand(&mut self) -> Result<Expr, CalcError> {
+        let (token, span) = self.bump();
+        let kind = match token {
+            Token::Op(op @ ("-" | "!")) => {
+                let operand = self.unary()?;
+                return Ok(Expr { kind: ExprKind::Unary(op, Box::new(operand)), span: span.start..self.end() });
+            }
+            Token::Op("(") => {
+                let inner = self.expr(0)?;
+                return match self.bump() {
+                    (Token::Op(")"), _) => Ok(inner),
+                    (_, found) => Err(CalcError::new(self.source, found, "expected `)`")),
+                };
+            }
+            Token::Int { value, suffix } => ExprKind::Int { value, suffix },
+            Token::Float { digits, suffix } => ExprKind::Float { digits, suffix },
+            Token::Bool(b) => ExprKind::Bool(b),
+            Token::Eof => return Err(CalcError::new(self.source, span, "expected an expression, found end of input")),
+            _ => return Err(CalcError::new(self.source, span, "expected an expression")),
+        };
+        Ok(Expr { kind, span })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The tree with every operation in parentheses.
+    fn shape(expr: &Expr) -> String {
+        match &expr.kind {
+            ExprKind::Int { value, .. } => value.to_string(),
+            ExprKind::Float { digits, .. } => digits.clone(),
+            ExprKind::Bool(b) => b.to_string(),
+            ExprKind::Unary(op, operand) => format!("({}{})", op, shape(operand)),
+            ExprKind::Binary(op, left, right) => format!("({} {} {})", shape(left), op, shape(right)),
+            ExprKind::Cast(operand, ty) => format!("({} as {})", shape(operand), ty),
+        }
+    }
+
+    #[test]
+    fn follows_precedence() {
+        let parsed = |source: &str| shape(&parse(source).unwrap());
+        assert_eq!(parsed("1 + 2 * 3 - 4"), "((1 + (2 * 3)) - 4)");
+        assert_eq!(parsed("1 << 2 + 3 & 7"), "((1 << (2 + 3)) & 7)");
+        assert_eq!(parsed("1 | 2 ^ 3 & 4 == 5"), "((1 | (2 ^ (3 & 4))) == 5)");
+        assert_eq!(parsed("-1 as u8 * 2"), "(((-1) as u8) * 2)");
+        assert_eq!(parsed("!(1 < 2) || true && false"), "((!(1 < 2)) || (true && false))");
+    }
+
+    #[test]
+    fn reports_where() {
+        let error = |source: &str| {
+            let e = parse(source).unwrap_err();
+            (e.message, e.column)
+        };
+        assert_eq!(error("1 < 2 < 3"), (String::from("comparison operators cannot be chained"), 7));
+        assert_eq!(error("(1 + 2"), (String::from("expected `)`"), 7));
+        assert_eq!(error("1 +"), (String::from("expected an expression, found end of input"), 4));
+        assert_eq!(error("1 2"), (String::from("expected an operator"), 3));
+        assert_eq!(error("1 as 2"), (String::from("expected a type after `as`"), 6));
+    }
+
+    #[test]
+    fn limits_nesting() {
+        let nested = |open: &str, close: &str, n: usize| format!("{}1{}", open.repeat(n), close.repeat(n));
+        let too_deep = (String::from("expression is nested too deeply"), Some(format!("the nesting limit is {}", NESTING_LIMIT)));
+        let error = |source: &str| parse(source).map(|_| ()).map_err(|e| (e.message, e.note));
+
+        assert_eq!(error(&nested("(", ")", NESTING_LIMIT - 1)), Ok(()));
+        assert_eq!(error(&nested("-", "", NESTING_LIMIT - 1)), Ok(()));
+        // Flat chains nest nothing, however long they are.
+        assert_eq!(error(&vec!["1"; 50_000].join(" + ")), Ok(()));
+        assert_eq!(error(&format!("1{}", " as i64".repeat(50_000))), Ok(()));
+        for source in [
+            nested("(", ")", 50_000),
+            nested("-", "", 50_000),
+            nested("!(", ")", NESTING_LIMIT / 2),
+        ] {
+            assert_eq!(error(&source), Err(too_deep.clone()), "{:.20}", source);
+        }
+        assert_eq!(parse(&nested("(", ")", 200)).unwrap_err().column, NESTING_LIMIT + 1);
+    }
+}
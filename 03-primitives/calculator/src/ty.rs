@@ -0,0 +1,122 @@
+// The primitive types an expression can have, and the integer ranges that
+// decide what overflows.
+
+use std::fmt;
+
+use bigint::BigInt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ty {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    F32,
+    F64,
+    Bool,
+}
+
+impl Ty {
+    pub const ALL: [Ty; 15] = [
+        Ty::I8,
+        Ty::I16,
+        Ty::I32,
+        Ty::I64,
+        Ty::I128,
+        Ty::Isize,
+        Ty::U8,
+        Ty::U16,
+        Ty::U32,
+        Ty::U64,
+        Ty::U128,
+        Ty::Usize,
+        Ty::F32,
+        Ty::F64,
+        Ty::Bool,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Ty::I8 => "i8",
+            Ty::I16 => "i16",
+            Ty::I32 => "i32",
+            Ty::I64 => "i64",
+            Ty::I128 => "i128",
+            Ty::Isize => "isize",
+            Ty::U8 => "u8",
+            Ty::U16 => "u16",
+            Ty::U32 => "u32",
+            Ty::U64 => "u64",
+            Ty::U128 => "u128",
+            Ty::Usize => "usize",
+            Ty::F32 => "f32",
+            Ty::F64 => "f64",
+            Ty::Bool => "bool",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Ty> {
+        Ty::ALL.into_iter().find(|ty| ty.name() == name)
+    }
+
+    pub fn is_integer(self) -> bool {
+        !matches!(self, Ty::F32 | Ty::F64 | Ty::Bool)
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(self, Ty::F32 | Ty::F64)
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(self, Ty::I8 | Ty::I16 | Ty::I32 | Ty::I64 | Ty::I128 | Ty::Isize)
+    }
+
+    // The width of an integer type; `isize` and `usize` are as wide as on
+    // the machine running the calculator.
+    pub fn bits(self) -> u32 {
+        match self {
+            Ty::I8 | Ty::U8 => 8,
+            Ty::I16 | Ty::U16 => 16,
+            Ty::I32 | Ty::U32 | Ty::F32 => 32,
+            Ty::I64 | Ty::U64 | Ty::F64 => 64,
+            Ty::I128 | Ty::U128 => 128,
+            Ty::Isize | Ty::Usize => usize::BITS,
+            Ty::Bool => 1,
+        }
+    }
+
+    pub fn min(self) -> BigInt {
+        if self.is_signed() { -(BigInt::one() << (self.bits() - 1)) } else { BigInt::zero() }
+    }
+
+    pub fn max(self) -> BigInt {
+        let bits = if self.is_signed() { self.bits() - 1 } else { self.bits() };
+        (BigInt::one() << bits) - BigInt::one()
+    }
+
+    pub fn contains(self, n: &BigInt) -> bool {
+        self.min() <= *n && *n <= self.max()
+    }
+
+    // Keeps the low `bits()` bits of `n`, as `as` and `<<` do: 300 becomes
+    // 44 in a `u8` and 200 becomes -56 in an `i8`.
+    pub fn wrap(self, n: &BigInt) -> BigInt {
+        let modulus = BigInt::one() << self.bits();
+        let low = n & &(&modulus - &BigInt::one());
+        if low > self.max() { low - modulus } else { low }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
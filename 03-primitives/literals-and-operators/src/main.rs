@@ -1,3 +1,7 @@
+use bigint::{BigInt, BigUint};
+use calculator::evaluate;
+use number_format::{Locale, NumberFormat};
+
 fn main() {
     // Integer addition
     println!("1 + 2 = {}", 1u32 + 2);
@@ -10,6 +14,20 @@ fn main() {
     //  = note: `#[deny(arithmetic_overflow)]` on by default
     // println!("1 - 2 = {}", 1u32 - 2);
 
+    // Arbitrary-precision integers never overflow, though unsigned ones
+    // still cannot go below zero.
+    println!("1 - 2 = {}", BigInt::from(1) - BigInt::from(2));
+    println!("1 << 100 is {}", BigUint::one() << 100);
+
+    // The calculator applies the same rules at runtime, so the mistake
+    // rustc catches above can be tried out without breaking the build.
+    for expression in ["1i32 - 2", "1u32 - 2", "0b0011u32 & 0b0101", "0x80u32 >> 2"] {
+        match evaluate(expression) {
+            Ok(value) => println!("{} = {}", expression, value),
+            Err(e) => println!("{} fails: {}", expression, e.note.unwrap_or(e.message)),
+        }
+    }
+
     // Scientific notation
     println!("1e4 is {}, -2.5e-3 is {}", 1e4, -2.5e-3);
 
@@ -27,4 +45,9 @@ fn main() {
 
     // Use underscores to improve readability!
     println!("One million is written as {}", 1_000_000u32);
-}
\ No newline at end of file
+
+    // ...and to print them readably.
+    println!("One million is easier to read as {}", 1_000_000u32.grouped());
+    println!("or as {} in SI style", 1_000_000u32.grouped().locale(Locale::SI));
+    println!("1e4 is {} and -2.5e-3 is {}", 1e4.si(), (-2.5e-3).si());
+}
@@ -0,0 +1,94 @@
+// The layout of an enum: the size of each variant's payload, and where the
+// discriminant went.
+//
+// The compiler either stores the discriminant in a tag next to the
+// payload, or hides it in a "niche": bit patterns a payload can never
+// have, such as a null `Box` or a `char` above 0x10FFFF. A niche costs no
+// space, which is why `Option<Box<T>>` is no bigger than `Box<T>`. A tag
+// can also cost nothing without a niche, when it fits in the padding of
+// every payload, as in `enum E { A(u8, u32), B }`.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    pub name: &'static str,
+    // As declared: `(char)`, `{ x: i64, y: i64 }`, or empty for a unit
+    // variant.
+    pub fields: String,
+    // The size and alignment of the fields packed together without the
+    // discriminant.
+    pub payload_size: usize,
+    pub payload_align: usize,
+    // Whether the fields have a niche to spare: `Option` of them is no
+    // bigger than they are.
+    pub payload_has_niche: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumLayout {
+    pub name: String,
+    pub size: usize,
+    pub align: usize,
+    pub variants: Vec<Variant>,
+    // The size of `Option` of the enum.
+    pub option_size: usize,
+}
+
+impl EnumLayout {
+    pub fn largest_payload(&self) -> usize {
+        self.variants.iter().map(|variant| variant.payload_size).max().unwrap_or(0)
+    }
+
+    // The bytes beyond the largest payload: the tag and the padding that
+    // comes with it. Zero when the discriminant is in a niche, or in
+    // padding the payloads already had.
+    pub fn tag_size(&self) -> usize {
+        self.size - self.largest_payload()
+    }
+
+    // Whether there is a discriminant and it is hidden in a niche of the
+    // largest payload, rather than in padding.
+    pub fn uses_niche(&self) -> bool {
+        self.variants.len() > 1
+            && self.tag_size() == 0
+            && self.variants.iter().any(|variant| variant.payload_size == self.size && variant.payload_has_niche)
+    }
+
+    // Whether `Option<Self>` found a niche too, so that it costs nothing
+    // either.
+    pub fn option_is_free(&self) -> bool {
+        self.option_size == self.size
+    }
+}
+
+impl fmt::Display for EnumLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}: {} bytes, align {}", self.name, self.size, self.align)?;
+        let labels: Vec<String> = self
+            .variants
+            .iter()
+            .map(|variant| {
+                let space = if variant.fields.starts_with('{') { " " } else { "" };
+                format!("{}{}{}", variant.name, space, variant.fields)
+            })
+            .collect();
+        let width = labels.iter().map(String::len).chain([7]).max().unwrap_or(0);
+        writeln!(f, "  {:<w$}  payload  align", "variant", w = width)?;
+        for (label, variant) in labels.iter().zip(&self.variants) {
+            writeln!(f, "  {:<w$}  {:>7}  {:>5}", label, variant.payload_size, variant.payload_align, w = width)?;
+        }
+        match self.variants.len() {
+            0 => writeln!(f, "no variants, so no value can ever exist")?,
+            1 => writeln!(f, "a single variant needs no discriminant")?,
+            _ if self.uses_niche() => writeln!(f, "the discriminant is stored in a niche of a payload and takes no space")?,
+            _ if self.tag_size() == 0 => writeln!(f, "the discriminant is a tag in the padding of the payloads and takes no extra space")?,
+            _ => writeln!(f, "the discriminant and its padding take {} bytes", self.tag_size())?,
+        }
+        if self.option_is_free() {
+            write!(f, "Option<{}> is {} bytes too: `None` fits in a niche", self.name, self.option_size)
+        } else {
+            write!(f, "Option<{}> is {} bytes: no niche was left for `None`", self.name, self.option_size)
+        }
+    }
+}
@@ -0,0 +1,136 @@
+// The layout of a struct: where each field sits, and the padding the
+// compiler put between and after them.
+
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    // The field name, or its index in a tuple struct.
+    pub name: &'static str,
+    pub type_name: String,
+    pub offset: usize,
+    pub size: usize,
+    pub align: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    pub name: String,
+    pub size: usize,
+    pub align: usize,
+    // In memory order, which for a struct without `#[repr(C)]` need not be
+    // the order they were declared in.
+    pub fields: Vec<Field>,
+}
+
+impl Layout {
+    pub fn new(name: String, size: usize, align: usize, mut fields: Vec<Field>) -> Layout {
+        fields.sort_by_key(|field| (field.offset, field.size));
+        Layout { name, size, align, fields }
+    }
+
+    // The byte ranges no field covers.
+    pub fn gaps(&self) -> Vec<Range<usize>> {
+        let mut gaps = Vec::new();
+        let mut end = 0;
+        for field in &self.fields {
+            if field.offset > end {
+                gaps.push(end..field.offset);
+            }
+            end = end.max(field.offset + field.size);
+        }
+        if self.size > end {
+            gaps.push(end..self.size);
+        }
+        gaps
+    }
+
+    pub fn padding(&self) -> usize {
+        self.gaps().iter().map(|gap| gap.len()).sum()
+    }
+
+    // The bytes of the struct, eight to a row, each labelled with the
+    // field it belongs to; padding is dotted out:
+    //
+    //        +-------------------------------+
+    //      0 |name                           |
+    //        ...
+    //     24 |age|...........................|
+    //        +-------------------------------+
+    pub fn diagram(&self) -> String {
+        if self.size == 0 {
+            return String::from("(zero-sized, no bytes at all)\n");
+        }
+        // Which field each byte belongs to, if any.
+        let mut owners = vec![None; self.size];
+        for (i, field) in self.fields.iter().enumerate() {
+            for owner in &mut owners[field.offset..field.offset + field.size] {
+                *owner = Some(i);
+            }
+        }
+
+        let per_row = self.size.min(8);
+        let offset_width = (self.size - 1).to_string().len();
+        let border = format!("{:w$} +{}+\n", "", "-".repeat(per_row * 4 - 1), w = offset_width);
+        let mut diagram = border.clone();
+        for (row, bytes) in owners.chunks(per_row).enumerate() {
+            let cells: Vec<String> = bytes
+                .chunk_by(|a, b| a == b)
+                .map(|run| {
+                    let width = run.len() * 4 - 1;
+                    match run[0] {
+                        Some(i) => format!("{:<w$.w$}", self.fields[i].name, w = width),
+                        None => ".".repeat(width),
+                    }
+                })
+                .collect();
+            // A short last row is filled up with blanks.
+            let rest = (per_row - bytes.len()) * 4;
+            let blank = if rest > 0 { format!("|{}", " ".repeat(rest - 1)) } else { String::new() };
+            diagram.push_str(&format!("{:>w$} |{}{}|\n", row * per_row, cells.join("|"), blank, w = offset_width));
+        }
+        diagram.push_str(&border);
+        diagram
+    }
+}
+
+// A table of the fields and padding, then the diagram.
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}: {} bytes, align {}, {} bytes of padding", self.name, self.size, self.align, self.padding())?;
+        writeln!(f, "  offset  size  align  field")?;
+        let mut rows: Vec<(usize, String)> = self
+            .fields
+            .iter()
+            .map(|field| {
+                let row = format!("{:>6}{:>6}{:>7}  {}: {}", field.offset, field.size, field.align, field.name, field.type_name);
+                (field.offset, row)
+            })
+            .collect();
+        rows.extend(self.gaps().into_iter().map(|gap| (gap.start, format!("{:>6}{:>6}{:>7}  (padding)", gap.start, gap.len(), ""))));
+        rows.sort_by_key(|(offset, _)| *offset);
+        for (_, row) in rows {
+            writeln!(f, "  {}", row)?;
+        }
+        write!(f, "{}", self.diagram())
+    }
+}
+
+// `std::any::type_name` spells out every path (`alloc::string::String`);
+// this keeps the last segment of each: `String`, `Vec<Option<u8>>`.
+pub(crate) fn short_type_name(name: &str) -> String {
+    let mut short = String::new();
+    let mut segment = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            short.push_str(segment.rsplit("::").next().unwrap_or(""));
+            segment.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(segment.rsplit("::").next().unwrap_or(""));
+    short
+}
@@ -0,0 +1,248 @@
+// How the compiler lays out a type in memory.
+//
+// `layout!` lists a struct's fields with their offset, size and alignment,
+// finds the padding, and draws the bytes:
+//
+//     println!("{}", layout!(Person { name, age }));
+//
+//     Person: 32 bytes, align 8, 7 bytes of padding
+//       offset  size  align  field
+//            0    24      8  name: String
+//           24     1      1  age: u8
+//           25     7         (padding)
+//        +-------------------------------+
+//      0 |name                           |
+//      8 |name                           |
+//     16 |name                           |
+//     24 |age|...........................|
+//        +-------------------------------+
+//
+// `enum_layout!` does the same for an enum's variants and tells where the
+// discriminant went, and `assert_layout!` fails the build if a type's size
+// or alignment is not what the code around it expects.
+//
+// Both listing macros check the list against the type, so a field or
+// variant that is misspelled or left out is a compile error.
+
+mod enums;
+mod layout;
+
+pub use enums::{EnumLayout, Variant};
+pub use layout::{Field, Layout};
+
+use std::mem;
+
+// Used by `layout!`: the field that `project` picks out of a `T`. The
+// closure is never called; its type says what the field's type is.
+#[doc(hidden)]
+pub fn field<T, F>(name: &'static str, offset: usize, _project: fn(&T) -> &F) -> Field {
+    Field {
+        name,
+        type_name: layout::short_type_name(std::any::type_name::<F>()),
+        offset,
+        size: mem::size_of::<F>(),
+        align: mem::align_of::<F>(),
+    }
+}
+
+// Used by `enum_layout!`: `P` is a tuple of the variant's fields.
+#[doc(hidden)]
+pub fn variant<P>(name: &'static str, fields: &str) -> Variant {
+    Variant {
+        name,
+        fields: tidy(fields),
+        payload_size: mem::size_of::<P>(),
+        payload_align: mem::align_of::<P>(),
+        payload_has_niche: mem::size_of::<Option<P>>() == mem::size_of::<P>(),
+    }
+}
+
+// `stringify!` spaces tokens out (`{ x : i64 }`, `Box < u8 >`); this
+// writes them the way rustfmt would.
+fn tidy(tokens: &str) -> String {
+    [(" (", "("), ("( ", "("), (" )", ")"), (" :", ":"), (" ,", ","), (" <", "<"), ("< ", "<"), (" >", ">")]
+        .iter()
+        .fold(tokens.to_owned(), |text, (from, to)| text.replace(from, to))
+}
+
+#[doc(hidden)]
+pub fn type_name<T>() -> String {
+    layout::short_type_name(std::any::type_name::<T>())
+}
+
+// `layout!(Type { field, ... })`, with every field of the struct; tuple
+// struct fields are numbered: `layout!(Pair { 0, 1 })`.
+#[macro_export]
+macro_rules! layout {
+    ($ty:ty { $($field:tt),* $(,)? }) => {{
+        type Inspected = $ty;
+        // Destructuring without `..` only compiles if every field is listed.
+        let _ = |value: &Inspected| {
+            let Inspected { $($field: _),* } = value;
+        };
+        $crate::Layout::new(
+            $crate::type_name::<Inspected>(),
+            ::core::mem::size_of::<Inspected>(),
+            ::core::mem::align_of::<Inspected>(),
+            ::std::vec![$(
+                $crate::field(
+                    stringify!($field),
+                    ::core::mem::offset_of!(Inspected, $field),
+                    |value: &Inspected| &value.$field,
+                )
+            ),*],
+        )
+    }};
+}
+
+// `enum_layout!(Type { Unit, Tuple(A, B), Struct { x: X } })`, with every
+// variant of the enum and the types of their fields.
+#[macro_export]
+macro_rules! enum_layout {
+    ($ty:ty {
+        $($variant:ident $(($($tuple:ty),* $(,)?))? $({ $($name:ident: $field:ty),* $(,)? })?),* $(,)?
+    }) => {{
+        type Inspected = $ty;
+        // A `match` without a catch-all only compiles if every variant is
+        // listed.
+        let _ = |value: &Inspected| match value {
+            $(Inspected::$variant { .. } => {})*
+        };
+        $crate::EnumLayout {
+            name: $crate::type_name::<Inspected>(),
+            size: ::core::mem::size_of::<Inspected>(),
+            align: ::core::mem::align_of::<Inspected>(),
+            variants: ::std::vec![$(
+                $crate::variant::<($($($tuple,)*)? $($($field,)*)?)>(
+                    stringify!($variant),
+                    stringify!($(($($tuple),*))? $({ $($name: $field),* })?),
+                )
+            ),*],
+            option_size: ::core::mem::size_of::<::core::option::Option<Inspected>>(),
+        }
+    }};
+}
+
+// Fails to compile unless `$ty` has the given size, and alignment if one
+// is given:
+//
+//     assert_layout!(Complex<f32>, size = 8, align = 4);
+#[macro_export]
+macro_rules! assert_layout {
+    ($ty:ty, size = $size:expr) => {
+        const _: () = assert!(
+            ::core::mem::size_of::<$ty>() == $size,
+            concat!("`", stringify!($ty), "` is not ", stringify!($size), " bytes"),
+        );
+    };
+    ($ty:ty, size = $size:expr, align = $align:expr) => {
+        $crate::assert_layout!($ty, size = $size);
+        const _: () = assert!(
+            ::core::mem::align_of::<$ty>() == $align,
+            concat!("`", stringify!($ty), "` is not aligned to ", stringify!($align), " bytes"),
+        );
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(dead_code)]
+
+    #[repr(C)]
+    struct Header {
+        tag: u8,
+        length: u32,
+        flags: u16,
+    }
+
+    struct Reordered {
+        tag: u8,
+        length: u32,
+        flags: u16,
+    }
+
+    struct Pair(u8, u64);
+
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rect { w: u32, h: u32 },
+    }
+
+    enum Maybe {
+        Nothing,
+        Just(Box<u8>),
+    }
+
+    assert_layout!(Header, size = 12, align = 4);
+    assert_layout!(Option<Box<u8>>, size = 8);
+
+    #[test]
+    fn finds_padding() {
+        let header = layout!(Header { tag, length, flags });
+        let offsets: Vec<_> = header.fields.iter().map(|field| (field.name, field.offset)).collect();
+        assert_eq!(offsets, [("tag", 0), ("length", 4), ("flags", 8)]);
+        assert_eq!(header.gaps(), [1..4, 10..12]);
+        assert_eq!(header.padding(), 5);
+
+        // Without `#[repr(C)]` the compiler is free to sort the fields so
+        // that only the trailing padding is left.
+        let reordered = layout!(Reordered { tag, length, flags });
+        assert_eq!(reordered.size, 8);
+        assert_eq!(reordered.padding(), 1);
+
+        let pair = layout!(Pair { 0, 1 });
+        assert_eq!(pair.fields.iter().map(|field| field.name).collect::<Vec<_>>(), ["1", "0"]);
+        assert_eq!(pair.fields[0].type_name, "u64");
+    }
+
+    #[test]
+    fn draws_the_bytes() {
+        let header = layout!(Header { tag, length, flags });
+        assert_eq!(
+            header.to_string(),
+            "\
+Header: 12 bytes, align 4, 5 bytes of padding
+  offset  size  align  field
+       0     1      1  tag: u8
+       1     3         (padding)
+       4     4      4  length: u32
+       8     2      2  flags: u16
+      10     2         (padding)
+   +-------------------------------+
+ 0 |tag|...........|length         |
+ 8 |flags  |.......|               |
+   +-------------------------------+
+"
+        );
+    }
+
+    #[test]
+    fn finds_the_discriminant() {
+        let shape = enum_layout!(Shape { Empty, Circle(f64), Rect { w: u32, h: u32 } });
+        assert_eq!(shape.variants[2].fields, "{ w: u32, h: u32 }");
+        assert_eq!(shape.variants[1].fields, "(f64)");
+        assert_eq!(shape.largest_payload(), 8);
+        assert_eq!(shape.tag_size(), 8);
+        assert!(!shape.uses_niche());
+        assert!(shape.option_is_free());
+
+        let maybe = enum_layout!(Maybe { Nothing, Just(Box<u8>) });
+        assert!(maybe.uses_niche());
+        assert!(!maybe.option_is_free());
+        assert!(maybe.to_string().contains("niche of a payload"));
+
+        // Neither `u8` nor `u32` has a niche; the tag sits in the padding
+        // after the `u8` instead.
+        #[allow(dead_code)]
+        enum Padded {
+            A(u8, u32),
+            B,
+        }
+        let padded = enum_layout!(Padded { A(u8, u32), B });
+        assert_eq!((padded.size, padded.tag_size()), (8, 0));
+        assert!(!padded.variants[0].payload_has_niche);
+        assert!(!padded.uses_niche());
+        assert!(padded.to_string().contains("a tag in the padding"), "{}", padded);
+    }
+}
@@ -0,0 +1,131 @@
+// Digit grouping: `1234567.891` as `1,234,567.891`, `1 234 567.891`,
+// `1.234.567,891` or `12,34,567.891`, depending on the locale.
+
+use std::fmt;
+
+// How one part of the world writes numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    // Between groups of digits.
+    pub separator: &'static str,
+    // Between the whole number and the fraction.
+    pub decimal_point: &'static str,
+    // Digits in the group next to the decimal point...
+    pub group: usize,
+    // ...and in every group further left.
+    pub secondary_group: usize,
+}
+
+impl Locale {
+    // 1,234,567.89
+    pub const EN: Locale = Locale { separator: ",", decimal_point: ".", group: 3, secondary_group: 3 };
+    // 1 234 567.89, the SI and ISO 31-0 style.
+    pub const SI: Locale = Locale { separator: " ", decimal_point: ".", group: 3, secondary_group: 3 };
+    // 1.234.567,89
+    pub const DE: Locale = Locale { separator: ".", decimal_point: ",", group: 3, secondary_group: 3 };
+    // 1 234 567,89 with a narrow no-break space.
+    pub const FR: Locale = Locale { separator: "\u{202F}", decimal_point: ",", group: 3, secondary_group: 3 };
+    // 1'234'567.89
+    pub const CH: Locale = Locale { separator: "'", decimal_point: ".", group: 3, secondary_group: 3 };
+    // 12,34,567.89: lakhs and crores.
+    pub const IN: Locale = Locale { separator: ",", decimal_point: ".", group: 3, secondary_group: 2 };
+}
+
+impl Default for Locale {
+    fn default() -> Locale {
+        Locale::EN
+    }
+}
+
+// Any number that implements `Display`, with its digits grouped. The
+// precision and `+` flag are applied to the number before grouping.
+#[derive(Debug, Clone, Copy)]
+pub struct Grouped<T> {
+    value: T,
+    locale: Locale,
+}
+
+impl<T: fmt::Display> Grouped<T> {
+    pub fn new(value: T) -> Grouped<T> {
+        Grouped { value, locale: Locale::EN }
+    }
+
+    pub fn locale(self, locale: Locale) -> Grouped<T> {
+        Grouped { locale, ..self }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grouped<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match (f.precision(), f.sign_plus()) {
+            (Some(p), true) => format!("{:+.*}", p, self.value),
+            (Some(p), false) => format!("{:.*}", p, self.value),
+            (None, true) => format!("{:+}", self.value),
+            (None, false) => self.value.to_string(),
+        };
+        crate::pad(f, &group(&text, &self.locale))
+    }
+}
+
+// Group the digits of the whole-number part of `text` and swap in the
+// decimal point. Anything that does not look like a number (`NaN`, `inf`)
+// comes back unchanged.
+fn group(text: &str, locale: &Locale) -> String {
+    let (sign, rest) = match text.chars().next() {
+        Some(c @ ('-' | '+')) => (&text[..1], &text[c.len_utf8()..]),
+        _ => ("", text),
+    };
+    let end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    let (digits, tail) = rest.split_at(end);
+    if digits.is_empty() {
+        return text.to_owned();
+    }
+
+    // Cut the digits into groups from the right.
+    let mut groups = Vec::new();
+    let mut remaining = digits;
+    let mut size = locale.group.max(1);
+    while remaining.len() > size {
+        let (left, right) = remaining.split_at(remaining.len() - size);
+        groups.push(right);
+        remaining = left;
+        size = locale.secondary_group.max(1);
+    }
+    groups.push(remaining);
+    groups.reverse();
+
+    let tail = match tail.strip_prefix('.') {
+        Some(fraction) => format!("{}{}", locale.decimal_point, fraction),
+        None => tail.to_owned(),
+    };
+    format!("{}{}{}", sign, groups.join(locale.separator), tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NumberFormat;
+
+    #[test]
+    fn groups_digits() {
+        assert_eq!(1_000_000.grouped().to_string(), "1,000,000");
+        assert_eq!(999.grouped().to_string(), "999");
+        assert_eq!((-1234567).grouped().to_string(), "-1,234,567");
+        assert_eq!(format!("{:+}", 1234.grouped()), "+1,234");
+        assert_eq!(u128::MAX.grouped().to_string(), "340,282,366,920,938,463,463,374,607,431,768,211,455");
+        assert_eq!(format!("{:.2}", 1234567.891.grouped()), "1,234,567.89");
+        assert_eq!(f64::NAN.grouped().to_string(), "NaN");
+        assert_eq!(1e21.grouped().to_string(), "1,000,000,000,000,000,000,000");
+    }
+
+    #[test]
+    fn follows_the_locale() {
+        let n = 1234567.891;
+        assert_eq!(n.grouped().locale(Locale::SI).to_string(), "1 234 567.891");
+        assert_eq!(n.grouped().locale(Locale::DE).to_string(), "1.234.567,891");
+        assert_eq!(n.grouped().locale(Locale::FR).to_string(), "1\u{202F}234\u{202F}567,891");
+        assert_eq!(n.grouped().locale(Locale::CH).to_string(), "1'234'567.891");
+        assert_eq!(n.grouped().locale(Locale::IN).to_string(), "12,34,567.891");
+        assert_eq!(100.grouped().locale(Locale::IN).to_string(), "100");
+    }
+}
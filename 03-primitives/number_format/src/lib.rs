@@ -0,0 +1,157 @@
+// Numbers formatted for people rather than for parsers.
+//
+// Each format is a small wrapper that implements `Display`, so it goes
+// straight into `println!`:
+//
+//     1_000_000.grouped()                          1,000,000
+//     1_000_000.grouped().locale(Locale::SI)       1 000 000
+//     1234.5.si()                                  1.23 k
+//     3_565_158_u64.bytes()                        3.40 MiB
+//     0.000_123_456.sig(2)                         0.00012
+//     47_000.0.eng()                               47.0e3
+//
+// Width, fill and alignment work as usual (`{:>10}`). For the prefixed and
+// engineering formats the precision is the number of decimals (`{:.1}` gives
+// `1.2 k`); without one they show three significant digits.
+
+mod group;
+mod notation;
+mod prefix;
+
+use std::fmt;
+
+pub use group::{Grouped, Locale};
+pub use notation::{Eng, Sig};
+pub use prefix::{Binary, Si};
+
+// `.grouped()`, `.si()` and friends on every primitive number.
+pub trait NumberFormat: Copy {
+    fn grouped(self) -> Grouped<Self>
+    where
+        Self: fmt::Display,
+    {
+        Grouped::new(self)
+    }
+
+    fn si<'a>(self) -> Si<'a> {
+        Si::new(self.to_f64())
+    }
+
+    fn binary<'a>(self) -> Binary<'a> {
+        Binary::new(self.to_f64())
+    }
+
+    // A size in bytes: binary prefixes and a `B`.
+    fn bytes<'a>(self) -> Binary<'a> {
+        Binary::new(self.to_f64()).unit("B")
+    }
+
+    fn sig(self, digits: usize) -> Sig {
+        Sig::new(self.to_f64(), digits)
+    }
+
+    fn eng(self) -> Eng {
+        Eng::new(self.to_f64())
+    }
+
+    // The number as an `f64`. Integers above 2^53 lose their last digits,
+    // which none of the formats above show anyway.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! number {
+    ($($ty:ty),*) => {
+        $(
+            impl NumberFormat for $ty {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+// `value` rounded to `digits` significant digits and written out without an
+// exponent: `significant(1234.5, 2)` is `1200`, `significant(0.012345, 2)`
+// is `0.012`. The rounding is done by `{:e}`, so it is exact.
+pub(crate) fn significant(value: f64, digits: usize) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    let digits = digits.max(1);
+    let scientific = format!("{:.*e}", digits - 1, value.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    let figures: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+
+    let sign = if value.is_sign_negative() && figures.bytes().any(|b| b != b'0') { "-" } else { "" };
+    // Where the decimal point goes, counted from the left of `figures`.
+    let point = exponent + 1;
+    let text = if point <= 0 {
+        format!("0.{}{}", "0".repeat(-point as usize), figures)
+    } else if point as usize >= figures.len() {
+        format!("{}{}", figures, "0".repeat(point as usize - figures.len()))
+    } else {
+        let (whole, fraction) = figures.split_at(point as usize);
+        format!("{}.{}", whole, fraction)
+    };
+    format!("{}{}", sign, text)
+}
+
+// The number part of a scaled value: `precision` decimals if given, three
+// significant digits otherwise.
+pub(crate) fn mantissa(value: f64, precision: Option<usize>) -> String {
+    match precision {
+        Some(p) => format!("{:.*}", p, value),
+        None => significant(value, 3),
+    }
+}
+
+// Pad `text` to the formatter's width. `Formatter::pad` cannot be used since
+// it would also cut `text` short to the precision, which the wrappers use
+// for something else. Numbers are right-aligned unless asked otherwise.
+pub(crate) fn pad(f: &mut fmt::Formatter, text: &str) -> fmt::Result {
+    let len = text.chars().count();
+    let padding = f.width().unwrap_or(0).saturating_sub(len);
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (padding, 0),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        write!(f, "{}", fill)?;
+    }
+    f.write_str(text)?;
+    for _ in 0..after {
+        write!(f, "{}", fill)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounds_to_significant_digits() {
+        assert_eq!(significant(1234.5, 2), "1200");
+        assert_eq!(significant(1234.5, 5), "1234.5");
+        assert_eq!(significant(1234.5, 7), "1234.500");
+        assert_eq!(significant(0.012345, 2), "0.012");
+        assert_eq!(significant(-9.996, 3), "-10.0");
+        assert_eq!(significant(0.0, 3), "0.00");
+        assert_eq!(significant(-0.0001, 1), "-0.0001");
+        assert_eq!(significant(f64::NEG_INFINITY, 3), "-inf");
+    }
+
+    #[test]
+    fn works_inside_format_strings() {
+        assert_eq!(format!("[{:>12}]", 1_000_000.grouped()), "[   1,000,000]");
+        assert_eq!(format!("[{:<8}]", 1234.5.si()), "[1.23 k  ]");
+        assert_eq!(format!("[{:*^9.1}]", 1234.5.si()), "[**1.2 k**]");
+        assert_eq!(format!("{} / {}", 69420.si(), 69420_u32.bytes()), "69.4 k / 67.8 KiB");
+    }
+}
@@ -0,0 +1,95 @@
+// Significant digits and engineering notation.
+
+use std::fmt;
+
+use crate::{mantissa, pad, significant};
+
+// A number rounded to a number of significant digits: 3 gives `1230` for
+// 1234.5 and `0.00123` for 0.0012345. Trailing zeros are kept, since they
+// are significant too.
+#[derive(Debug, Clone, Copy)]
+pub struct Sig {
+    value: f64,
+    digits: usize,
+}
+
+impl Sig {
+    pub fn new(value: f64, digits: usize) -> Sig {
+        Sig { value, digits }
+    }
+}
+
+impl fmt::Display for Sig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        pad(f, &significant(self.value, self.digits))
+    }
+}
+
+// Scientific notation with the exponent a multiple of three, so that it
+// lines up with the SI prefixes: `47.0e3` rather than `4.7e4`.
+#[derive(Debug, Clone, Copy)]
+pub struct Eng {
+    value: f64,
+}
+
+impl Eng {
+    pub fn new(value: f64) -> Eng {
+        Eng { value }
+    }
+}
+
+impl fmt::Display for Eng {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+        if self.value == 0.0 || !self.value.is_finite() {
+            let text = mantissa(self.value, precision);
+            return pad(f, &if self.value.is_finite() { format!("{}e0", text) } else { text });
+        }
+
+        let mut exponent = self.value.abs().log10().floor() as i32;
+        exponent -= exponent.rem_euclid(3);
+        let mut text = mantissa(scale(self.value, exponent), precision);
+        // 999.96 rounds to `1.00e3`, not `1000e0`.
+        if text.trim_start_matches('-').parse::<f64>().is_ok_and(|m| m >= 1000.0) {
+            exponent += 3;
+            text = mantissa(scale(self.value, exponent), precision);
+        }
+        pad(f, &format!("{}e{}", text, exponent))
+    }
+}
+
+// `value / 10^exponent`, in two steps: for the smallest values `10^exponent`
+// on its own would underflow to zero.
+fn scale(value: f64, exponent: i32) -> f64 {
+    let half = exponent / 2;
+    value / 10f64.powi(half) / 10f64.powi(exponent - half)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::NumberFormat;
+
+    #[test]
+    fn rounds_to_significant_digits() {
+        assert_eq!(1234.5.sig(3).to_string(), "1230");
+        assert_eq!(0.0012345.sig(3).to_string(), "0.00123");
+        assert_eq!(2.sig(3).to_string(), "2.00");
+        assert_eq!(69420.sig(2).to_string(), "69000");
+        assert_eq!(format!("{:>8}", 1.23456.sig(3)), "    1.23");
+    }
+
+    #[test]
+    fn writes_engineering_notation() {
+        assert_eq!(47_000.0.eng().to_string(), "47.0e3");
+        assert_eq!(0.00047.eng().to_string(), "470e-6");
+        assert_eq!(format!("{:.1}", 1234.5.eng()), "1.2e3");
+        assert_eq!((-69420).eng().to_string(), "-69.4e3");
+        assert_eq!(999.96.eng().to_string(), "1.00e3");
+        assert_eq!(1.0.eng().to_string(), "1.00e0");
+        assert_eq!(0.eng().to_string(), "0.00e0");
+        assert_eq!(1e-320.eng().to_string(), "10.0e-321");
+        assert_eq!(f64::MIN_POSITIVE.eng().to_string(), "22.3e-309");
+        assert_eq!(5e-324.eng().to_string(), "4.94e-324");
+        assert_eq!(f64::MAX.eng().to_string(), "180e306");
+    }
+}
@@ -0,0 +1,154 @@
+// Metric and binary prefixes: `1.23 k`, `4.70 µF`, `3.40 MiB`.
+
+use std::fmt;
+
+use crate::{mantissa, pad};
+
+// From 10^-30 to 10^30 in steps of a thousand; the empty one is 10^0.
+const SI_PREFIXES: [&str; 21] =
+    ["q", "r", "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"];
+const SI_UNPREFIXED: i32 = 10;
+
+// Powers of 1024, as IEC 80000-13 names them.
+const BINARY_PREFIXES: [&str; 9] = ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi"];
+
+// A number scaled by a power of 1000 and given the matching SI prefix,
+// with an optional unit after it.
+#[derive(Debug, Clone, Copy)]
+pub struct Si<'a> {
+    value: f64,
+    unit: &'a str,
+}
+
+impl<'a> Si<'a> {
+    pub fn new(value: f64) -> Si<'a> {
+        Si { value, unit: "" }
+    }
+
+    // `1.23 km` rather than `1.23 k`.
+    pub fn unit(self, unit: &'a str) -> Si<'a> {
+        Si { unit, ..self }
+    }
+}
+
+impl fmt::Display for Si<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+        if self.value == 0.0 || !self.value.is_finite() {
+            return pad(f, &join(&mantissa(self.value, precision), "", self.unit));
+        }
+
+        let last = SI_PREFIXES.len() as i32 - 1 - SI_UNPREFIXED;
+        let first = -SI_UNPREFIXED;
+        let mut power = (self.value.abs().log10() / 3.0).floor() as i32;
+        power = power.clamp(first, last);
+        let mut text = mantissa(self.value / 1000f64.powi(power), precision);
+        // Rounding can carry into the next prefix: 999.96 is `1.00 k`.
+        if power < last && text.trim_start_matches('-').parse::<f64>().is_ok_and(|m| m >= 1000.0) {
+            power += 1;
+            text = mantissa(self.value / 1000f64.powi(power), precision);
+        }
+        let prefix = SI_PREFIXES[(power + SI_UNPREFIXED) as usize];
+        pad(f, &join(&text, prefix, self.unit))
+    }
+}
+
+// A number scaled by a power of 1024, for sizes in memory. Numbers below
+// 1024 are written as they are, so 512 bytes is `512 B`, not `512.00 B`.
+#[derive(Debug, Clone, Copy)]
+pub struct Binary<'a> {
+    value: f64,
+    unit: &'a str,
+}
+
+impl<'a> Binary<'a> {
+    pub fn new(value: f64) -> Binary<'a> {
+        Binary { value, unit: "" }
+    }
+
+    pub fn unit(self, unit: &'a str) -> Binary<'a> {
+        Binary { unit, ..self }
+    }
+}
+
+impl fmt::Display for Binary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+        let magnitude = self.value.abs();
+        if !magnitude.is_finite() {
+            return pad(f, &join(&mantissa(self.value, precision), "", self.unit));
+        }
+        if magnitude < 1024.0 && self.value.fract() == 0.0 {
+            return pad(f, &join(&self.value.to_string(), "", self.unit));
+        }
+
+        // Scaled values go up to 1023.99..., which has four digits before the
+        // point. Three significant digits would round those to `1020`, so
+        // the whole part is always kept. The same goes for a fraction below
+        // 1024, which is scaled by 1024^0; under 1 it keeps three
+        // significant digits.
+        let scaled = |power: i32| {
+            let scaled = self.value / 1024f64.powi(power);
+            if scaled.abs() < 1.0 {
+                return mantissa(scaled, precision);
+            }
+            let whole_digits = (scaled.abs().log10().floor() as usize) + 1;
+            format!("{:.*}", precision.unwrap_or(3usize.saturating_sub(whole_digits)), scaled)
+        };
+        let last = BINARY_PREFIXES.len() as i32 - 1;
+        let mut power = ((magnitude.log2() / 10.0).floor() as i32).clamp(0, last);
+        let mut text = scaled(power);
+        if power < last && text.trim_start_matches('-').parse::<f64>().is_ok_and(|m| m >= 1024.0) {
+            power += 1;
+            text = scaled(power);
+        }
+        pad(f, &join(&text, BINARY_PREFIXES[power as usize], self.unit))
+    }
+}
+
+// `1.23 km`, `1.23 k`, `1.23`.
+fn join(number: &str, prefix: &str, unit: &str) -> String {
+    if prefix.is_empty() && unit.is_empty() {
+        number.to_owned()
+    } else {
+        format!("{} {}{}", number, prefix, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::NumberFormat;
+
+    #[test]
+    fn picks_si_prefixes() {
+        assert_eq!(1234.5.si().to_string(), "1.23 k");
+        assert_eq!(format!("{:.1}", 1234.5.si()), "1.2 k");
+        assert_eq!(0.0000047.si().unit("F").to_string(), "4.70 µF");
+        assert_eq!(1500.si().unit("m").to_string(), "1.50 km");
+        assert_eq!((-2_500_000).si().to_string(), "-2.50 M");
+        assert_eq!(999.96.si().to_string(), "1.00 k");
+        assert_eq!(format!("{:.0}", 999_600.si()), "1 M");
+        assert_eq!(12.si().to_string(), "12.0");
+        assert_eq!(0.si().unit("V").to_string(), "0.00 V");
+        assert_eq!(1e33.si().to_string(), "1000 Q");
+        assert_eq!(f64::INFINITY.si().unit("Ω").to_string(), "inf Ω");
+    }
+
+    #[test]
+    fn picks_binary_prefixes() {
+        assert_eq!(3_565_158_u64.bytes().to_string(), "3.40 MiB");
+        assert_eq!(format!("{:.1}", 3_565_158_u64.bytes()), "3.4 MiB");
+        assert_eq!(512.bytes().to_string(), "512 B");
+        assert_eq!(1024.bytes().to_string(), "1.00 KiB");
+        assert_eq!(1_048_575.bytes().to_string(), "1.00 MiB");
+        assert_eq!(u64::MAX.bytes().to_string(), "16.0 EiB");
+        assert_eq!(1536.binary().to_string(), "1.50 Ki");
+        assert_eq!(100.binary().to_string(), "100");
+        assert_eq!(1000.7.bytes().to_string(), "1001 B");
+        assert_eq!(1023.4.bytes().to_string(), "1023 B");
+        assert_eq!(1023.9999.bytes().to_string(), "1.00 KiB");
+        assert_eq!(512.25.bytes().to_string(), "512 B");
+        assert_eq!(0.5.bytes().to_string(), "0.500 B");
+        assert_eq!(format!("{:.1}", 1000.5.bytes()), "1000.5 B");
+    }
+}
@@ -0,0 +1,378 @@
+// Heterogeneous lists: tuples of any length.
+//
+// The standard library implements `Debug`, `PartialEq` and the rest for
+// tuples of up to 12 elements, one impl per length. An `HList` is built
+// from just two types instead, `HNil` (the empty list) and `HCons` (an
+// element in front of a shorter list), so one impl for each covers every
+// length:
+//
+//     let list = hlist![1, "two", 3.0];       // HCons { head: 1, tail: HCons { ... } }
+//     let hlist_pat![a, b, c] = list;          // a = 1, b = "two", c = 3.0
+//     list.get::<I1>()                         // &"two"
+//
+// The type of that list is `HList![i32, &str, f64]`. Comparisons are
+// lexicographic, like those of tuples, and lists of up to 12 elements
+// convert to and from tuples with `From`.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HNil;
+
+// Deriving works because an `HCons` compares, hashes and orders its head
+// first and then its tail, which is exactly the tuple order.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HCons<H, T> {
+    pub head: H,
+    pub tail: T,
+}
+
+// `hlist![a, b, c]` builds a list; a trailing comma is fine.
+#[macro_export]
+macro_rules! hlist {
+    () => { $crate::hlist::HNil };
+    ($head:expr $(, $tail:expr)* $(,)?) => {
+        $crate::hlist::HCons { head: $head, tail: $crate::hlist!($($tail),*) }
+    };
+}
+
+// `hlist_pat![a, b, c]` destructures one; `hlist_pat![a, ..]` ignores the
+// rest.
+#[macro_export]
+macro_rules! hlist_pat {
+    () => { $crate::hlist::HNil };
+    (..) => { _ };
+    ($head:pat $(, $($tail:tt)*)?) => {
+        $crate::hlist::HCons { head: $head, tail: $crate::hlist_pat!($($($tail)*)?) }
+    };
+}
+
+// `HList![A, B, C]` is the type of `hlist![a, b, c]`.
+#[macro_export]
+#[allow(non_snake_case)]
+macro_rules! HList {
+    () => { $crate::hlist::HNil };
+    ($head:ty $(, $tail:ty)* $(,)?) => {
+        $crate::hlist::HCons<$head, $crate::HList!($($tail),*)>
+    };
+}
+
+pub trait HList: Sized {
+    const LEN: usize;
+
+    fn len(&self) -> usize {
+        Self::LEN
+    }
+
+    fn is_empty(&self) -> bool {
+        Self::LEN == 0
+    }
+
+    fn prepend<H>(self, head: H) -> HCons<H, Self> {
+        HCons { head, tail: self }
+    }
+}
+
+impl HList for HNil {
+    const LEN: usize = 0;
+}
+
+impl<H, T: HList> HList for HCons<H, T> {
+    const LEN: usize = 1 + T::LEN;
+}
+
+// Indexes are types, so that `get` knows the type of what it returns:
+// `Here` is the head and `There<I>` is index `I` of the tail. `I0` to
+// `I15` spell the first few out.
+pub struct Here;
+pub struct There<I>(PhantomData<I>);
+
+pub type I0 = Here;
+pub type I1 = There<I0>;
+pub type I2 = There<I1>;
+pub type I3 = There<I2>;
+pub type I4 = There<I3>;
+pub type I5 = There<I4>;
+pub type I6 = There<I5>;
+pub type I7 = There<I6>;
+pub type I8 = There<I7>;
+pub type I9 = There<I8>;
+pub type I10 = There<I9>;
+pub type I11 = There<I10>;
+pub type I12 = There<I11>;
+pub type I13 = There<I12>;
+pub type I14 = There<I13>;
+pub type I15 = There<I14>;
+
+// A list with an element at index `I`. There is no impl for `HNil`, so an
+// index past the end is a compile error.
+pub trait Get<I> {
+    type Output;
+
+    fn get(&self) -> &Self::Output;
+    fn get_mut(&mut self) -> &mut Self::Output;
+}
+
+impl<H, T> Get<Here> for HCons<H, T> {
+    type Output = H;
+
+    fn get(&self) -> &H {
+        &self.head
+    }
+
+    fn get_mut(&mut self) -> &mut H {
+        &mut self.head
+    }
+}
+
+impl<H, T: Get<I>, I> Get<There<I>> for HCons<H, T> {
+    type Output = T::Output;
+
+    fn get(&self) -> &T::Output {
+        self.tail.get()
+    }
+
+    fn get_mut(&mut self) -> &mut T::Output {
+        self.tail.get_mut()
+    }
+}
+
+// `self` followed by the elements of `Rhs`.
+pub trait Append<Rhs> {
+    type Output;
+
+    fn append(self, rhs: Rhs) -> Self::Output;
+}
+
+impl<Rhs> Append<Rhs> for HNil {
+    type Output = Rhs;
+
+    fn append(self, rhs: Rhs) -> Rhs {
+        rhs
+    }
+}
+
+impl<H, T: Append<Rhs>, Rhs> Append<Rhs> for HCons<H, T> {
+    type Output = HCons<H, T::Output>;
+
+    fn append(self, rhs: Rhs) -> Self::Output {
+        HCons { head: self.head, tail: self.tail.append(rhs) }
+    }
+}
+
+// Reversing moves each head onto an accumulator, so the first element
+// ends up last: `Acc` is what has been moved so far.
+pub trait ReverseOnto<Acc> {
+    type Output;
+
+    fn reverse_onto(self, acc: Acc) -> Self::Output;
+}
+
+impl<Acc> ReverseOnto<Acc> for HNil {
+    type Output = Acc;
+
+    fn reverse_onto(self, acc: Acc) -> Acc {
+        acc
+    }
+}
+
+impl<H, T: ReverseOnto<HCons<H, Acc>>, Acc> ReverseOnto<Acc> for HCons<H, T> {
+    type Output = T::Output;
+
+    fn reverse_onto(self, acc: Acc) -> T::Output {
+        self.tail.reverse_onto(HCons { head: self.head, tail: acc })
+    }
+}
+
+impl<H, T> HCons<H, T> {
+    // The element at index `I`: `list.get::<I2>()`.
+    pub fn get<I>(&self) -> &<Self as Get<I>>::Output
+    where
+        Self: Get<I>,
+    {
+        Get::get(self)
+    }
+
+    pub fn get_mut<I>(&mut self) -> &mut <Self as Get<I>>::Output
+    where
+        Self: Get<I>,
+    {
+        Get::get_mut(self)
+    }
+
+    pub fn append<Rhs>(self, rhs: Rhs) -> <Self as Append<Rhs>>::Output
+    where
+        Self: Append<Rhs>,
+    {
+        Append::append(self, rhs)
+    }
+
+    pub fn reverse(self) -> <Self as ReverseOnto<HNil>>::Output
+    where
+        Self: ReverseOnto<HNil>,
+    {
+        self.reverse_onto(HNil)
+    }
+}
+
+// `Debug` and `Display` print a list like a tuple: `(1, "two", 3.0)`.
+// These two traits walk the elements for them.
+pub trait DebugItems {
+    fn debug_items(&self, tuple: &mut fmt::DebugTuple);
+}
+
+impl DebugItems for HNil {
+    fn debug_items(&self, _: &mut fmt::DebugTuple) {}
+}
+
+impl<H: fmt::Debug, T: DebugItems> DebugItems for HCons<H, T> {
+    fn debug_items(&self, tuple: &mut fmt::DebugTuple) {
+        tuple.field(&self.head);
+        self.tail.debug_items(tuple);
+    }
+}
+
+pub trait DisplayItems {
+    fn display_items(&self, f: &mut fmt::Formatter, first: bool) -> fmt::Result;
+}
+
+impl DisplayItems for HNil {
+    fn display_items(&self, _: &mut fmt::Formatter, _: bool) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<H: fmt::Display, T: DisplayItems> DisplayItems for HCons<H, T> {
+    fn display_items(&self, f: &mut fmt::Formatter, first: bool) -> fmt::Result {
+        if !first {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", self.head)?;
+        self.tail.display_items(f, false)
+    }
+}
+
+// The same as `Debug` for tuples, `{:#?}` included. The empty list is `()`.
+impl fmt::Debug for HNil {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "()")
+    }
+}
+
+impl<H: fmt::Debug, T: DebugItems> fmt::Debug for HCons<H, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tuple = f.debug_tuple("");
+        self.debug_items(&mut tuple);
+        tuple.finish()
+    }
+}
+
+impl fmt::Display for HNil {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "()")
+    }
+}
+
+impl<H: fmt::Display, T: DisplayItems> fmt::Display for HCons<H, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        self.display_items(f, true)?;
+        write!(f, ")")
+    }
+}
+
+// `From` in both directions between lists and tuples of each length up
+// to 12, the longest tuples the standard library has impls for.
+macro_rules! tuple_conversions {
+    ($(($($name:ident),*))*) => {
+        $(
+            impl<$($name),*> From<($($name,)*)> for HList![$($name),*] {
+                #[allow(non_snake_case)]
+                fn from(($($name,)*): ($($name,)*)) -> Self {
+                    hlist![$($name),*]
+                }
+            }
+
+            impl<$($name),*> From<HList![$($name),*]> for ($($name,)*) {
+                // For the empty list the body is `()`, which clippy would
+                // rather see left out.
+                #[allow(non_snake_case, clippy::unused_unit)]
+                fn from(hlist_pat![$($name),*]: HList![$($name),*]) -> Self {
+                    ($($name,)*)
+                }
+            }
+        )*
+    };
+}
+
+tuple_conversions! {
+    ()
+    (A)
+    (A, B)
+    (A, B, C)
+    (A, B, C, D)
+    (A, B, C, D, E)
+    (A, B, C, D, E, F)
+    (A, B, C, D, E, F, G)
+    (A, B, C, D, E, F, G, H)
+    (A, B, C, D, E, F, G, H, I)
+    (A, B, C, D, E, F, G, H, I, J)
+    (A, B, C, D, E, F, G, H, I, J, K)
+    (A, B, C, D, E, F, G, H, I, J, K, L)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn goes_past_twelve() {
+        let list = hlist![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 'x'];
+        assert_eq!(list.len(), 13);
+        assert_eq!(format!("{:?}", list), "(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 'x')");
+        assert_eq!(list.to_string(), "(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, x)");
+        assert_eq!(*list.get::<There<I11>>(), 'x');
+        assert_eq!(format!("{:#?}", hlist![1, "a"]), "(\n    1,\n    \"a\",\n)");
+        assert_eq!(format!("{:?}", hlist![]), format!("{:?}", ()));
+        assert_eq!(format!("{:#?}", hlist![]), format!("{:#?}", ()));
+        assert_eq!(format!("{:?}", hlist![5]), format!("{:?}", (5,)));
+    }
+
+    #[test]
+    fn compares_like_tuples() {
+        assert!(hlist![1, "b"] < hlist![2, "a"]);
+        assert!(hlist![1, "a"] < hlist![1, "b"]);
+        assert_eq!(hlist![1, 2.5], hlist![1, 2.5]);
+        let set: HashSet<HList![i32, &str]> = [hlist![1, "a"], hlist![1, "a"], hlist![2, "a"]].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn indexes_and_rearranges() {
+        let mut list = hlist![1u8, "two", 3.0];
+        *list.get_mut::<I0>() += 1;
+        assert_eq!(*list.get::<I1>(), "two");
+
+        let longer = list.append(hlist!['4', true]);
+        assert_eq!(longer, hlist![2u8, "two", 3.0, '4', true]);
+        assert_eq!(longer.reverse(), hlist![true, '4', 3.0, "two", 2u8]);
+
+        let hlist_pat![first, ..] = longer;
+        assert_eq!(first, 2);
+        let hlist_pat![_, second, _, _, last] = longer;
+        assert_eq!((second, last), ("two", true));
+    }
+
+    #[test]
+    fn converts_to_and_from_tuples() {
+        let list: HList![i32, &str, bool] = (1, "a", true).into();
+        assert_eq!(list, hlist![1, "a", true]);
+        let tuple: (i32, &str, bool) = list.into();
+        assert_eq!(tuple, (1, "a", true));
+        let twelve: HList![u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8] = (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11).into();
+        assert_eq!(*twelve.get::<I11>(), 11);
+        assert_eq!(HNil, ().into());
+    }
+}
@@ -0,0 +1,4 @@
+// The types the tuple examples in `main.rs` grow into.
+
+pub mod hlist;
+pub mod matrix;
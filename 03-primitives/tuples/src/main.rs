@@ -1,4 +1,6 @@
-use std::fmt;
+use tuples::hlist::{HList, I12};
+use tuples::matrix::{DMatrix, Matrix};
+use tuples::{hlist, hlist_pat};
 
 // Tuples can be used as function arguments and as return values.
 fn reverse(pair: (i32, bool)) -> (bool, i32) {
@@ -8,23 +10,6 @@ fn reverse(pair: (i32, bool)) -> (bool, i32) {
     (bool_param, int_param)
 }
 
-// The following struct is for the activity.
-#[derive(Debug)]
-struct Matrix(f32, f32, f32, f32);
-
-// Implement Display for Matrix
-impl fmt::Display for Matrix {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "( {} {} )\n( {} {} )", self.0, self.1, self.2, self.3)
-    }
-}
-
-// Add this function above main
-fn transpose(matrix: Matrix) -> Matrix {
-    // Swap the elements at (0,1) and (1,0)
-    Matrix(matrix.0, matrix.2, matrix.1, matrix.3)
-}
-
 fn main() {
     // A tuple with a bunch of different types.
     let long_tuple = (1u8, 2u16, 3u32, 4u64,
@@ -47,6 +32,19 @@ fn main() {
     //println!("Too long tuple: {:?}", too_long_tuple);
     // TODO ^ Uncomment the above 2 lines to see the compiler error
 
+    // A heterogeneous list has no such limit: its impls are written once
+    // for every length.
+    let long_list = hlist![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13];
+    println!("Long list: {:?}", long_list);
+    println!("It has {} elements; the last is {}", long_list.len(), long_list.get::<I12>());
+
+    // Up to 12 elements, lists and tuples convert into each other.
+    let record = hlist!["Ferris", 7u8, 'R'].append(hlist![true]);
+    let hlist_pat![name, age, ..] = record;
+    println!("{} is {} years old", name, age);
+    let tuple: (&str, u8, char, bool) = record.into();
+    println!("As a tuple: {:?}, reversed: {}", tuple, record.reverse());
+
     let pair = (1, true);
     println!("Pair is {:?}", pair);
 
@@ -63,13 +61,30 @@ fn main() {
     let (a, b, c, d) = tuple;
     println!("{:?}, {:?}, {:?}, {:?}", a, b, c, d);
 
-    let matrix = Matrix(1.1, 1.2, 2.1, 2.2);
+    // The activity's 2x2 matrix grew into a matrix of any size; its
+    // shape is part of its type.
+    let matrix = Matrix::new([[1.1, 1.2], [2.1, 2.2]]);
     // Print using Display
     println!("Matrix:\n{}", matrix);
 
-    let transposed = transpose(matrix);
+    let transposed = matrix.transpose();
     println!("Transposed:\n{}", transposed);
 
+    // Solve the system  2x + y = 5,  x - 3y = -8.
+    let system = Matrix::new([[2.0, 1.0], [1.0, -3.0]]);
+    println!("Determinant: {}", system.determinant());
+    println!("Inverse:\n{:.3}", system.inverse().unwrap());
+    println!("2x + y = 5 and x - 3y = -8 when (x, y) = {:?}", system.solve([5.0, -8.0]));
+
+    // A 2x3 times a 3x2 is a 2x2; the other way round would not compile.
+    let wide = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    println!("Product with its transpose:\n{}", wide * wide.transpose());
+
+    // When the shape is only known at runtime, `DMatrix` checks it then.
+    let dynamic = DMatrix::from(wide);
+    println!("Rank of the 2x3 matrix: {}", dynamic.rank());
+    println!("Multiplying it by itself: {:?}", dynamic.checked_mul(&dynamic).map(|_| ()));
+
     // Recap: Add the fmt::Display trait to the Matrix struct in the above example, so that if you switch from printing the debug format {:?} to the display format {}, you see the following output:
 
     // ( 1.1 1.2 )
@@ -0,0 +1,303 @@
+// Matrices of `f64`.
+//
+// `Matrix<R, C>` has its shape in its type, so adding a 2x3 to a 3x2 or
+// multiplying a 2x3 by a 2x3 does not compile, and a product has the
+// right shape without checks: `Matrix<2, 3> * Matrix<3, 4>` is a
+// `Matrix<2, 4>`. `DMatrix` is the same for shapes only known at runtime,
+// where a mismatch is an error instead.
+//
+// Determinants, inverses and the solutions of `A·x = b` come from an LU
+// decomposition with partial pivoting; the rank from Gaussian elimination.
+// Both treat pivots that are zero up to rounding error, next to the size of
+// their row, as zero, so a matrix that is singular on paper is singular
+// here too.
+//
+// Display writes one row per line with the columns aligned, and passes a
+// precision on to the cells:
+//
+//     ( 1.1 1.2 )
+//     ( 2.1 2.2 )
+
+mod dynamic;
+mod lu;
+
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+
+pub use dynamic::DMatrix;
+
+use lu::Lu;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatrixError {
+    // `rows` x `cols` is not what `expected` needs.
+    ShapeMismatch { expected: (usize, usize), found: (usize, usize) },
+    // A flat list of cells of the wrong length for the shape.
+    WrongLength { expected: usize, found: usize },
+    // Determinants, inverses and solving need a square matrix.
+    NotSquare { rows: usize, cols: usize },
+    // The matrix has no inverse, and `A·x = b` no unique solution.
+    Singular,
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MatrixError::ShapeMismatch { expected: (r1, c1), found: (r2, c2) } => {
+                write!(f, "expected a {}x{} matrix, found {}x{}", r1, c1, r2, c2)
+            }
+            MatrixError::WrongLength { expected, found } => write!(f, "expected {} cells, found {}", expected, found),
+            MatrixError::NotSquare { rows, cols } => write!(f, "a {}x{} matrix is not square", rows, cols),
+            MatrixError::Singular => write!(f, "the matrix is singular"),
+        }
+    }
+}
+
+impl Error for MatrixError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix<const R: usize, const C: usize> {
+    rows: [[f64; C]; R],
+}
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    pub fn new(rows: [[f64; C]; R]) -> Self {
+        Matrix { rows }
+    }
+
+    pub fn zero() -> Self {
+        Matrix { rows: [[0.0; C]; R] }
+    }
+
+    pub fn from_fn(mut f: impl FnMut(usize, usize) -> f64) -> Self {
+        Matrix { rows: std::array::from_fn(|r| std::array::from_fn(|c| f(r, c))) }
+    }
+
+    pub fn rows(&self) -> &[[f64; C]; R] {
+        &self.rows
+    }
+
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<f64> {
+        self.rows.get(row)?.get(col).copied()
+    }
+
+    pub fn transpose(&self) -> Matrix<C, R> {
+        Matrix::from_fn(|r, c| self.rows[c][r])
+    }
+
+    pub fn rank(&self) -> usize {
+        lu::rank(self.rows.as_flattened(), R, C)
+    }
+
+    // The cells in the order `DMatrix` and the LU code keep them.
+    fn flat(&self) -> &[f64] {
+        self.rows.as_flattened()
+    }
+
+    fn from_flat(cells: &[f64]) -> Self {
+        Matrix::from_fn(|r, c| cells[r * C + c])
+    }
+}
+
+impl<const N: usize> Matrix<N, N> {
+    pub fn identity() -> Self {
+        Matrix::from_fn(|r, c| if r == c { 1.0 } else { 0.0 })
+    }
+
+    pub fn determinant(&self) -> f64 {
+        Lu::new(self.flat(), N).determinant()
+    }
+
+    // `None` for a singular matrix.
+    pub fn inverse(&self) -> Option<Self> {
+        Lu::new(self.flat(), N).inverse().map(|cells| Matrix::from_flat(&cells))
+    }
+
+    // The `x` with `self · x = b`; `None` when there is no unique one.
+    pub fn solve(&self, b: [f64; N]) -> Option<[f64; N]> {
+        let x = Lu::new(self.flat(), N).solve(&b)?;
+        Some(std::array::from_fn(|i| x[i]))
+    }
+}
+
+impl<const R: usize, const C: usize> Default for Matrix<R, C> {
+    fn default() -> Self {
+        Matrix::zero()
+    }
+}
+
+impl<const R: usize, const C: usize> Index<(usize, usize)> for Matrix<R, C> {
+    type Output = f64;
+
+    fn index(&self, (row, col): (usize, usize)) -> &f64 {
+        &self.rows[row][col]
+    }
+}
+
+impl<const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<R, C> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut f64 {
+        &mut self.rows[row][col]
+    }
+}
+
+impl<const R: usize, const C: usize> Add for Matrix<R, C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Matrix::from_fn(|r, c| self.rows[r][c] + rhs.rows[r][c])
+    }
+}
+
+impl<const R: usize, const C: usize> Sub for Matrix<R, C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Matrix::from_fn(|r, c| self.rows[r][c] - rhs.rows[r][c])
+    }
+}
+
+impl<const R: usize, const C: usize> Neg for Matrix<R, C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Matrix::from_fn(|r, c| -self.rows[r][c])
+    }
+}
+
+// (R x C) · (C x K) is R x K; the inner sizes must agree, which the type
+// checker sees to.
+impl<const R: usize, const C: usize, const K: usize> Mul<Matrix<C, K>> for Matrix<R, C> {
+    type Output = Matrix<R, K>;
+
+    fn mul(self, rhs: Matrix<C, K>) -> Matrix<R, K> {
+        Matrix::from_fn(|r, k| (0..C).map(|c| self.rows[r][c] * rhs.rows[c][k]).sum())
+    }
+}
+
+// A matrix times a column vector.
+impl<const R: usize, const C: usize> Mul<[f64; C]> for Matrix<R, C> {
+    type Output = [f64; R];
+
+    fn mul(self, rhs: [f64; C]) -> [f64; R] {
+        std::array::from_fn(|r| (0..C).map(|c| self.rows[r][c] * rhs[c]).sum())
+    }
+}
+
+impl<const R: usize, const C: usize> Mul<f64> for Matrix<R, C> {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self {
+        Matrix::from_fn(|r, c| self.rows[r][c] * rhs)
+    }
+}
+
+impl<const R: usize, const C: usize> Mul<Matrix<R, C>> for f64 {
+    type Output = Matrix<R, C>;
+
+    fn mul(self, rhs: Matrix<R, C>) -> Matrix<R, C> {
+        rhs * self
+    }
+}
+
+impl<const R: usize, const C: usize> fmt::Display for Matrix<R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_rows(f, R, C, |r, c| self.rows[r][c])
+    }
+}
+
+// `( a b )` per row, each column right-aligned to its widest cell.
+fn write_rows(f: &mut fmt::Formatter, rows: usize, cols: usize, cell: impl Fn(usize, usize) -> f64) -> fmt::Result {
+    let text: Vec<Vec<String>> = (0..rows)
+        .map(|r| {
+            (0..cols)
+                .map(|c| match f.precision() {
+                    Some(precision) => format!("{:.*}", precision, cell(r, c)),
+                    None => cell(r, c).to_string(),
+                })
+                .collect()
+        })
+        .collect();
+    let widths: Vec<usize> = (0..cols).map(|c| text.iter().map(|row| row[c].len()).max().unwrap_or(0)).collect();
+    for (r, row) in text.iter().enumerate() {
+        if r > 0 {
+            writeln!(f)?;
+        }
+        write!(f, "(")?;
+        for (cell, width) in row.iter().zip(&widths) {
+            write!(f, " {:>w$}", cell, w = width)?;
+        }
+        write!(f, " )")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close<const N: usize>(a: [f64; N], b: [f64; N]) -> bool {
+        a.iter().zip(&b).all(|(x, y)| (x - y).abs() < 1e-9)
+    }
+
+    #[test]
+    fn multiplies_by_shape() {
+        let a = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let b = a.transpose();
+        assert_eq!(a * b, Matrix::new([[14.0, 32.0], [32.0, 77.0]]));
+        assert_eq!(b * a * [1.0, 0.0, 0.0], [17.0, 22.0, 27.0]);
+        assert_eq!(2.0 * a - a, a);
+        assert_eq!(a + -a, Matrix::zero());
+        assert_eq!(Matrix::<3, 3>::identity() * b, b);
+    }
+
+    #[test]
+    fn decomposes() {
+        let a = Matrix::new([[2.0, 1.0, 1.0], [4.0, -6.0, 0.0], [-2.0, 7.0, 2.0]]);
+        assert!((a.determinant() - -16.0).abs() < 1e-9);
+        let x = a.solve([5.0, -2.0, 9.0]).unwrap();
+        assert!(close(x, [1.0, 1.0, 2.0]), "{:?}", x);
+        let product = a * a.inverse().unwrap();
+        for (row, expected) in product.rows().iter().zip(Matrix::<3, 3>::identity().rows()) {
+            assert!(close(*row, *expected), "{}", product);
+        }
+        assert_eq!(a.rank(), 3);
+
+        // The third row is the sum of the first two, though rounding leaves
+        // a last pivot of about 1e-15 rather than 0.
+        let nearly = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [5.0, 7.0, 9.0]]);
+        assert_eq!(nearly.determinant(), 0.0);
+        assert_eq!(nearly.inverse(), None);
+        assert_eq!(nearly.solve([1.0, 2.0, 3.0]), None);
+        assert_eq!(nearly.rank(), 2);
+        // Here elimination gives an exact zero row.
+        let singular = Matrix::new([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 1.0, 1.0]]);
+        assert_eq!(singular.determinant(), 0.0);
+        assert_eq!(singular.inverse(), None);
+        assert_eq!(singular.solve([1.0, 2.0, 3.0]), None);
+        assert_eq!(singular.rank(), 2);
+        assert_eq!(Matrix::new([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0]]).rank(), 1);
+    }
+
+    #[test]
+    fn handles_badly_scaled_matrices() {
+        for (big, small) in [(1e20, 1.0), (1e10, 1e-10), (1e300, 1e-300)] {
+            let a = Matrix::new([[big, 0.0], [0.0, small]]);
+            assert_eq!(a.determinant(), big * small);
+            assert_eq!(a.inverse(), Some(Matrix::new([[1.0 / big, 0.0], [0.0, 1.0 / small]])));
+            assert_eq!(a.solve([big, small]), Some([1.0, 1.0]));
+            assert_eq!(a.rank(), 2);
+        }
+        let not_finite = Matrix::new([[1.0, 0.0], [f64::NAN, 1.0]]);
+        assert!(not_finite.determinant().is_nan());
+        assert_eq!(not_finite.inverse(), None);
+        assert!(Matrix::new([[f64::INFINITY, 0.0], [0.0, 1.0]]).determinant().is_nan());
+    }
+
+    #[test]
+    fn aligns_columns() {
+        let m = Matrix::new([[1.1, 1.2], [2.1, 2.2]]);
+        assert_eq!(m.to_string(), "( 1.1 1.2 )\n( 2.1 2.2 )");
+        assert_eq!(format!("{:.1}", Matrix::new([[1.0, -20.0], [300.0, 4.0]])), "(   1.0 -20.0 )\n( 300.0   4.0 )");
+    }
+}
@@ -0,0 +1,240 @@
+// `DMatrix`: a matrix whose shape is only known at runtime.
+//
+// The operators panic on mismatched shapes, as indexing out of bounds
+// does; `checked_mul` and the rest return a `MatrixError` instead.
+
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+
+use super::lu::{self, Lu};
+use super::{write_rows, Matrix, MatrixError};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DMatrix {
+    rows: usize,
+    cols: usize,
+    // Row after row.
+    cells: Vec<f64>,
+}
+
+impl DMatrix {
+    pub fn new(rows: usize, cols: usize, cells: Vec<f64>) -> Result<DMatrix, MatrixError> {
+        if cells.len() != rows * cols {
+            return Err(MatrixError::WrongLength { expected: rows * cols, found: cells.len() });
+        }
+        Ok(DMatrix { rows, cols, cells })
+    }
+
+    pub fn zero(rows: usize, cols: usize) -> DMatrix {
+        DMatrix { rows, cols, cells: vec![0.0; rows * cols] }
+    }
+
+    pub fn identity(n: usize) -> DMatrix {
+        DMatrix::from_fn(n, n, |r, c| if r == c { 1.0 } else { 0.0 })
+    }
+
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> f64) -> DMatrix {
+        DMatrix { rows, cols, cells: (0..rows * cols).map(|i| f(i / cols, i % cols)).collect() }
+    }
+
+    // Rows of equal length.
+    pub fn from_rows(rows: &[&[f64]]) -> Result<DMatrix, MatrixError> {
+        let cols = rows.first().map_or(0, |row| row.len());
+        if let Some(row) = rows.iter().find(|row| row.len() != cols) {
+            return Err(MatrixError::ShapeMismatch { expected: (rows.len(), cols), found: (rows.len(), row.len()) });
+        }
+        Ok(DMatrix { rows: rows.len(), cols, cells: rows.concat() })
+    }
+
+    // `(rows, cols)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<f64> {
+        (row < self.rows && col < self.cols).then(|| self.cells[row * self.cols + col])
+    }
+
+    pub fn row(&self, row: usize) -> &[f64] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn transpose(&self) -> DMatrix {
+        DMatrix::from_fn(self.cols, self.rows, |r, c| self[(c, r)])
+    }
+
+    pub fn rank(&self) -> usize {
+        lu::rank(&self.cells, self.rows, self.cols)
+    }
+
+    fn same_shape(&self, other: &DMatrix) -> Result<(), MatrixError> {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::ShapeMismatch { expected: self.shape(), found: other.shape() });
+        }
+        Ok(())
+    }
+
+    pub fn checked_add(&self, rhs: &DMatrix) -> Result<DMatrix, MatrixError> {
+        self.same_shape(rhs)?;
+        Ok(self.zip(rhs, |a, b| a + b))
+    }
+
+    pub fn checked_sub(&self, rhs: &DMatrix) -> Result<DMatrix, MatrixError> {
+        self.same_shape(rhs)?;
+        Ok(self.zip(rhs, |a, b| a - b))
+    }
+
+    // `self`'s columns must be as many as `rhs`'s rows.
+    pub fn checked_mul(&self, rhs: &DMatrix) -> Result<DMatrix, MatrixError> {
+        if self.cols != rhs.rows {
+            return Err(MatrixError::ShapeMismatch { expected: (self.cols, rhs.cols), found: rhs.shape() });
+        }
+        Ok(DMatrix::from_fn(self.rows, rhs.cols, |r, k| (0..self.cols).map(|c| self[(r, c)] * rhs[(c, k)]).sum()))
+    }
+
+    fn zip(&self, rhs: &DMatrix, f: impl Fn(f64, f64) -> f64) -> DMatrix {
+        let cells = self.cells.iter().zip(&rhs.cells).map(|(&a, &b)| f(a, b)).collect();
+        DMatrix { rows: self.rows, cols: self.cols, cells }
+    }
+
+    fn lu(&self) -> Result<Lu, MatrixError> {
+        if self.rows != self.cols {
+            return Err(MatrixError::NotSquare { rows: self.rows, cols: self.cols });
+        }
+        Ok(Lu::new(&self.cells, self.rows))
+    }
+
+    pub fn determinant(&self) -> Result<f64, MatrixError> {
+        Ok(self.lu()?.determinant())
+    }
+
+    pub fn inverse(&self) -> Result<DMatrix, MatrixError> {
+        let cells = self.lu()?.inverse().ok_or(MatrixError::Singular)?;
+        Ok(DMatrix { cells, ..self.clone() })
+    }
+
+    // The `x` with `self · x = b`.
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        let lu = self.lu()?;
+        if b.len() != self.rows {
+            return Err(MatrixError::ShapeMismatch { expected: (self.rows, 1), found: (b.len(), 1) });
+        }
+        lu.solve(b).ok_or(MatrixError::Singular)
+    }
+}
+
+impl<const R: usize, const C: usize> From<Matrix<R, C>> for DMatrix {
+    fn from(matrix: Matrix<R, C>) -> DMatrix {
+        DMatrix { rows: R, cols: C, cells: matrix.flat().to_vec() }
+    }
+}
+
+impl<const R: usize, const C: usize> TryFrom<DMatrix> for Matrix<R, C> {
+    type Error = MatrixError;
+
+    fn try_from(matrix: DMatrix) -> Result<Matrix<R, C>, MatrixError> {
+        if matrix.shape() != (R, C) {
+            return Err(MatrixError::ShapeMismatch { expected: (R, C), found: matrix.shape() });
+        }
+        Ok(Matrix::from_flat(&matrix.cells))
+    }
+}
+
+impl Index<(usize, usize)> for DMatrix {
+    type Output = f64;
+
+    fn index(&self, (row, col): (usize, usize)) -> &f64 {
+        assert!(row < self.rows && col < self.cols, "({}, {}) out of range for a {}x{} matrix", row, col, self.rows, self.cols);
+        &self.cells[row * self.cols + col]
+    }
+}
+
+impl IndexMut<(usize, usize)> for DMatrix {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut f64 {
+        assert!(row < self.rows && col < self.cols, "({}, {}) out of range for a {}x{} matrix", row, col, self.rows, self.cols);
+        &mut self.cells[row * self.cols + col]
+    }
+}
+
+impl Add for &DMatrix {
+    type Output = DMatrix;
+
+    fn add(self, rhs: &DMatrix) -> DMatrix {
+        self.checked_add(rhs).unwrap_or_else(|e| panic!("cannot add: {}", e))
+    }
+}
+
+impl Sub for &DMatrix {
+    type Output = DMatrix;
+
+    fn sub(self, rhs: &DMatrix) -> DMatrix {
+        self.checked_sub(rhs).unwrap_or_else(|e| panic!("cannot subtract: {}", e))
+    }
+}
+
+impl Mul for &DMatrix {
+    type Output = DMatrix;
+
+    fn mul(self, rhs: &DMatrix) -> DMatrix {
+        self.checked_mul(rhs).unwrap_or_else(|e| panic!("cannot multiply: {}", e))
+    }
+}
+
+impl Mul<f64> for &DMatrix {
+    type Output = DMatrix;
+
+    fn mul(self, rhs: f64) -> DMatrix {
+        DMatrix { cells: self.cells.iter().map(|x| x * rhs).collect(), ..self.clone() }
+    }
+}
+
+impl Mul<&DMatrix> for f64 {
+    type Output = DMatrix;
+
+    fn mul(self, rhs: &DMatrix) -> DMatrix {
+        rhs * self
+    }
+}
+
+impl Neg for &DMatrix {
+    type Output = DMatrix;
+
+    fn neg(self) -> DMatrix {
+        self * -1.0
+    }
+}
+
+impl fmt::Display for DMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_rows(f, self.rows, self.cols, |r, c| self[(r, c)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_shapes_at_runtime() {
+        let a = DMatrix::from_rows(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]]).unwrap();
+        let b = a.transpose();
+        assert_eq!(&a * &b, DMatrix::from(Matrix::new([[14.0, 32.0], [32.0, 77.0]])));
+        assert_eq!(a.checked_mul(&a), Err(MatrixError::ShapeMismatch { expected: (3, 3), found: (2, 3) }));
+        assert_eq!(a.checked_add(&b).unwrap_err().to_string(), "expected a 2x3 matrix, found 3x2");
+        assert_eq!(a.determinant(), Err(MatrixError::NotSquare { rows: 2, cols: 3 }));
+        assert_eq!(a.rank(), 2);
+        assert_eq!(Matrix::<3, 2>::try_from(b.clone()), Ok(Matrix::new([[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]])));
+        assert!(Matrix::<2, 3>::try_from(b).is_err());
+    }
+
+    #[test]
+    fn solves_systems() {
+        let a = DMatrix::from_rows(&[&[0.0, 2.0], &[3.0, 1.0]]).unwrap();
+        assert_eq!(a.determinant(), Ok(-6.0));
+        assert_eq!(a.solve(&[4.0, 5.0]), Ok(vec![1.0, 2.0]));
+        assert_eq!(&a * &a.inverse().unwrap(), DMatrix::identity(2));
+        assert_eq!(DMatrix::zero(2, 2).solve(&[1.0, 1.0]), Err(MatrixError::Singular));
+        assert_eq!(format!("{}", 2.0 * &a), "( 0 4 )\n( 6 2 )");
+        assert_eq!(DMatrix::new(2, 2, vec![1.0]), Err(MatrixError::WrongLength { expected: 4, found: 1 }));
+    }
+}
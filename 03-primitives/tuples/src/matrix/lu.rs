@@ -0,0 +1,153 @@
+// LU decomposition with partial pivoting, and the elimination behind
+// `rank`. Both matrix types hand their cells over as a flat row-major
+// slice and get flat results back.
+
+use std::ops::Range;
+
+// Floating point rarely gives an exact 0 for dependent rows, so a pivot
+// this small, relative to the largest entry of the row it came from,
+// counts as zero. Each row gets its own tolerance: one measured against
+// the whole matrix would make `diag(1e20, 1)` singular.
+fn tolerances(cells: &[f64], rows: usize, cols: usize) -> Vec<f64> {
+    cells
+        .chunks(cols.max(1))
+        .take(rows)
+        .map(|row| row.iter().fold(0.0f64, |max, x| max.max(x.abs())) * rows.max(cols) as f64 * f64::EPSILON)
+        .collect()
+}
+
+// The row in `candidates` to pivot on in column `col`: the one whose entry
+// is largest next to its tolerance, which keeps the rounding errors small.
+// `None` if every entry counts as zero.
+fn pivot(a: &[f64], cols: usize, col: usize, candidates: Range<usize>, tolerances: &[f64]) -> Option<usize> {
+    candidates
+        .filter(|&row| a[row * cols + col].abs() > tolerances[row])
+        .max_by(|&x, &y| {
+            let scaled = |row: usize| a[row * cols + col].abs() / tolerances[row];
+            scaled(x).total_cmp(&scaled(y))
+        })
+}
+
+// `P·A = L·U` for a square `A`: `L` (below the diagonal, with an implicit
+// diagonal of ones) and `U` (the rest) share one array, and `P` is kept as
+// the order the rows were swapped into.
+pub(crate) struct Lu {
+    lu: Vec<f64>,
+    n: usize,
+    permutation: Vec<usize>,
+    // +1 or -1: each row swap flips the determinant's sign.
+    sign: f64,
+    singular: bool,
+    // An infinite or NaN cell leaves nothing meaningful to decompose.
+    finite: bool,
+}
+
+impl Lu {
+    pub(crate) fn new(cells: &[f64], n: usize) -> Lu {
+        let mut lu = cells.to_vec();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut sign = 1.0;
+        let finite = cells.iter().all(|x| x.is_finite());
+        if !finite {
+            return Lu { lu, n, permutation, sign, singular: true, finite };
+        }
+        let mut tolerances = tolerances(cells, n, n);
+        let mut singular = false;
+        for k in 0..n {
+            let Some(pivot) = pivot(&lu, n, k, k..n, &tolerances) else {
+                singular = true;
+                continue;
+            };
+            if pivot != k {
+                for col in 0..n {
+                    lu.swap(k * n + col, pivot * n + col);
+                }
+                permutation.swap(k, pivot);
+                tolerances.swap(k, pivot);
+                sign = -sign;
+            }
+            for row in k + 1..n {
+                let factor = lu[row * n + k] / lu[k * n + k];
+                lu[row * n + k] = factor;
+                for col in k + 1..n {
+                    lu[row * n + col] -= factor * lu[k * n + col];
+                }
+            }
+        }
+        Lu { lu, n, permutation, sign, singular, finite }
+    }
+
+    // NaN if a cell is not finite.
+    pub(crate) fn determinant(&self) -> f64 {
+        if !self.finite {
+            return f64::NAN;
+        }
+        if self.singular {
+            return 0.0;
+        }
+        (0..self.n).map(|i| self.lu[i * self.n + i]).product::<f64>() * self.sign
+    }
+
+    // `x` with `A·x = b`, or `None` if `A` is singular.
+    pub(crate) fn solve(&self, b: &[f64]) -> Option<Vec<f64>> {
+        if self.singular {
+            return None;
+        }
+        let n = self.n;
+        // Forward substitution through `L`, then back through `U`.
+        let mut x: Vec<f64> = self.permutation.iter().map(|&row| b[row]).collect();
+        for row in 0..n {
+            for col in 0..row {
+                x[row] -= self.lu[row * n + col] * x[col];
+            }
+        }
+        for row in (0..n).rev() {
+            for col in row + 1..n {
+                x[row] -= self.lu[row * n + col] * x[col];
+            }
+            x[row] /= self.lu[row * n + row];
+        }
+        Some(x)
+    }
+
+    // Solves for each column of the identity in turn.
+    pub(crate) fn inverse(&self) -> Option<Vec<f64>> {
+        let n = self.n;
+        let mut inverse = vec![0.0; n * n];
+        for col in 0..n {
+            let unit: Vec<f64> = (0..n).map(|row| if row == col { 1.0 } else { 0.0 }).collect();
+            for (row, x) in self.solve(&unit)?.into_iter().enumerate() {
+                inverse[row * n + col] = x;
+            }
+        }
+        Some(inverse)
+    }
+}
+
+// The number of linearly independent rows: Gaussian elimination to row
+// echelon form, counting the pivots that are not (nearly) zero.
+pub(crate) fn rank(cells: &[f64], rows: usize, cols: usize) -> usize {
+    let mut tolerances = tolerances(cells, rows, cols);
+    let mut a = cells.to_vec();
+    let mut rank = 0;
+    for col in 0..cols {
+        if rank == rows {
+            break;
+        }
+        let Some(pivot) = pivot(&a, cols, col, rank..rows, &tolerances) else {
+            continue;
+        };
+        for c in 0..cols {
+            a.swap(rank * cols + c, pivot * cols + c);
+        }
+        tolerances.swap(rank, pivot);
+        for row in rank + 1..rows {
+            let factor = a[row * cols + col] / a[rank * cols + col];
+            for c in col..cols {
+                a[row * cols + c] -= factor * a[rank * cols + c];
+            }
+        }
+        rank += 1;
+    }
+    rank
+}
@@ -0,0 +1,259 @@
+// An event bus: instead of one `match` that handles every `WebEvent`,
+// handlers subscribe to the variants they care about and the bus calls
+// them in turn.
+//
+//     let mut bus = Bus::new();
+//     bus.on_key_press(|c| println!("pressed {}", c));
+//     bus.subscribe(Filter::All, 10, |event| println!("{:?}", event));
+//     bus.dispatch(&WebEvent::KeyPress('x'))?;
+//
+// Handlers with a higher priority run first; equal priorities run in the
+// order they subscribed.
+//
+// A bus can keep a log: every event it dispatches is appended to a file
+// as one line of JSON, before any handler sees it.
+//
+//     "PageLoad"
+//     {"KeyPress":"x"}
+//     {"Click":{"x":20,"y":80}}
+//
+// `replay` reads such a log back and dispatches its events again, into a
+// fresh bus for instance, to reproduce what happened.
+
+use std::error::Error;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use crate::{EventKind, WebEvent};
+
+// Which events a subscriber gets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    All,
+    Only(EventKind),
+}
+
+impl Filter {
+    fn accepts(self, event: &WebEvent) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Only(kind) => event.kind() == kind,
+        }
+    }
+}
+
+// Returned by every `subscribe`, to `unsubscribe` with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+struct Subscriber {
+    id: SubscriptionId,
+    filter: Filter,
+    priority: i32,
+    handler: Box<dyn FnMut(&WebEvent)>,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    // A line of the log that is not an event, 1-based.
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Io(e) => write!(f, "cannot read the event log: {}", e),
+            ReplayError::Parse { line, message } => write!(f, "{} at line {} of the event log", message, line),
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(e: io::Error) -> ReplayError {
+        ReplayError::Io(e)
+    }
+}
+
+#[derive(Default)]
+pub struct Bus {
+    // Sorted by priority, highest first, then by subscription order.
+    subscribers: Vec<Subscriber>,
+    next_id: u64,
+    log: Option<File>,
+}
+
+impl Bus {
+    pub fn new() -> Bus {
+        Bus::default()
+    }
+
+    // A bus that appends every event it dispatches to the file at `path`,
+    // which is created if needed. What is already in it stays.
+    pub fn with_log(path: impl AsRef<Path>) -> io::Result<Bus> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Bus { log: Some(file), ..Bus::default() })
+    }
+
+    pub fn subscribe(&mut self, filter: Filter, priority: i32, handler: impl FnMut(&WebEvent) + 'static) -> SubscriptionId {
+        let id = SubscriptionId(self.next_id);
+        self.next_id += 1;
+        // After every subscriber of the same or a higher priority.
+        let at = self.subscribers.partition_point(|s| s.priority >= priority);
+        self.subscribers.insert(at, Subscriber { id, filter, priority, handler: Box::new(handler) });
+        id
+    }
+
+    // Whether there was such a subscription.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let before = self.subscribers.len();
+        self.subscribers.retain(|s| s.id != id);
+        self.subscribers.len() < before
+    }
+
+    pub fn subscribers(&self) -> usize {
+        self.subscribers.len()
+    }
+
+    // Typed subscriptions, at priority 0: the handler gets the variant's
+    // data rather than the whole event.
+
+    pub fn on_page_load(&mut self, mut handler: impl FnMut() + 'static) -> SubscriptionId {
+        self.subscribe(Filter::Only(EventKind::PageLoad), 0, move |_| handler())
+    }
+
+    pub fn on_page_unload(&mut self, mut handler: impl FnMut() + 'static) -> SubscriptionId {
+        self.subscribe(Filter::Only(EventKind::PageUnload), 0, move |_| handler())
+    }
+
+    pub fn on_key_press(&mut self, mut handler: impl FnMut(char) + 'static) -> SubscriptionId {
+        self.subscribe(Filter::Only(EventKind::KeyPress), 0, move |event| {
+            if let WebEvent::KeyPress(c) = event {
+                handler(*c);
+            }
+        })
+    }
+
+    pub fn on_paste(&mut self, mut handler: impl FnMut(&str) + 'static) -> SubscriptionId {
+        self.subscribe(Filter::Only(EventKind::Paste), 0, move |event| {
+            if let WebEvent::Paste(s) = event {
+                handler(s);
+            }
+        })
+    }
+
+    pub fn on_click(&mut self, mut handler: impl FnMut(i64, i64) + 'static) -> SubscriptionId {
+        self.subscribe(Filter::Only(EventKind::Click), 0, move |event| {
+            if let WebEvent::Click { x, y } = event {
+                handler(*x, *y);
+            }
+        })
+    }
+
+    // Logs `event`, then hands it to each matching subscriber. Returns how
+    // many there were. If the log cannot be written, no handler runs.
+    pub fn dispatch(&mut self, event: &WebEvent) -> io::Result<usize> {
+        if let Some(log) = &mut self.log {
+            log.write_all(format!("{}\n", json::to_string(event)).as_bytes())?;
+        }
+        let mut called = 0;
+        for subscriber in &mut self.subscribers {
+            if subscriber.filter.accepts(event) {
+                (subscriber.handler)(event);
+                called += 1;
+            }
+        }
+        Ok(called)
+    }
+
+    // Dispatches every event in a log, in order, and returns how many there
+    // were. Blank lines are skipped. If this bus keeps a log of its own, the
+    // replayed events go into it too.
+    pub fn replay(&mut self, log: impl BufRead) -> Result<usize, ReplayError> {
+        let mut count = 0;
+        for (index, line) in log.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event: WebEvent = json::from_str(&line)
+                .map_err(|e| ReplayError::Parse { line: index + 1, message: e.to_string() })?;
+            self.dispatch(&event)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    pub fn replay_file(&mut self, path: impl AsRef<Path>) -> Result<usize, ReplayError> {
+        self.replay(BufReader::new(File::open(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // A handler that writes what it saw into a shared list.
+    fn recorder() -> (Rc<RefCell<Vec<String>>>, impl Fn(String)) {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let writer = Rc::clone(&seen);
+        (seen, move |entry| writer.borrow_mut().push(entry))
+    }
+
+    #[test]
+    fn dispatches_by_variant_and_priority() {
+        let (seen, record) = recorder();
+        let record = Rc::new(record);
+        let mut bus = Bus::new();
+
+        let r = Rc::clone(&record);
+        bus.on_key_press(move |c| r(format!("key {}", c)));
+        let r = Rc::clone(&record);
+        bus.on_click(move |x, y| r(format!("click {} {}", x, y)));
+        let r = Rc::clone(&record);
+        let all = bus.subscribe(Filter::All, -1, move |event| r(format!("all {:?}", event.kind())));
+        let r = Rc::clone(&record);
+        bus.subscribe(Filter::Only(EventKind::KeyPress), 5, move |_| r(String::from("first")));
+
+        assert_eq!(bus.dispatch(&WebEvent::KeyPress('x')).unwrap(), 3);
+        assert_eq!(bus.dispatch(&WebEvent::Click { x: 1, y: 2 }).unwrap(), 2);
+        assert!(bus.unsubscribe(all));
+        assert!(!bus.unsubscribe(all));
+        assert_eq!(bus.dispatch(&WebEvent::PageLoad).unwrap(), 0);
+
+        assert_eq!(*seen.borrow(), ["first", "key x", "all KeyPress", "click 1 2", "all Click"]);
+    }
+
+    #[test]
+    fn replays_its_log() {
+        let path = std::env::temp_dir().join(format!("custom_enums_bus_{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let events = [
+            WebEvent::PageLoad,
+            WebEvent::Paste(String::from("two\nlines")),
+            WebEvent::Click { x: 20, y: 80 },
+        ];
+        let mut bus = Bus::with_log(&path).unwrap();
+        for event in &events {
+            bus.dispatch(event).unwrap();
+        }
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(text, "\"PageLoad\"\n{\"Paste\":\"two\\nlines\"}\n{\"Click\":{\"x\":20,\"y\":80}}\n");
+
+        let (seen, record) = recorder();
+        let mut fresh = Bus::new();
+        fresh.subscribe(Filter::All, 0, move |event| record(json::to_string(event)));
+        assert_eq!(fresh.replay_file(&path).unwrap(), 3);
+        assert_eq!(seen.borrow().join("\n") + "\n", text);
+        std::fs::remove_file(&path).unwrap();
+
+        let err = Bus::new().replay("\"PageLoad\"\n\n{\"Scroll\": 1}\n".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "unknown WebEvent variant `Scroll` at line 3 of the event log");
+    }
+}
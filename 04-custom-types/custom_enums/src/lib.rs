@@ -0,0 +1,101 @@
+// `WebEvent` and what is built around it: JSON conversion, and an event
+// bus that hands events to subscribers and keeps a log of them.
+
+pub mod bus;
+
+use json::{FromJson, FromJsonError, Json, ToJson};
+
+// Create an `enum` to classify a web event. Note how both
+// names and type information together specify the variant:
+// `PageLoad != PageUnload` and `KeyPress(char) != Paste(String)`.
+// Each is different and independent.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebEvent {
+    // An `enum` variant may either be `unit-like`,
+    PageLoad,
+    PageUnload,
+    // like tuple structs,
+    KeyPress(char),
+    Paste(String),
+    // or c-like structures.
+    Click { x: i64, y: i64 },
+}
+
+// Which variant an event is, without its data; subscribers use it to pick
+// the events they want.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    PageLoad,
+    PageUnload,
+    KeyPress,
+    Paste,
+    Click,
+}
+
+impl WebEvent {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            WebEvent::PageLoad => EventKind::PageLoad,
+            WebEvent::PageUnload => EventKind::PageUnload,
+            WebEvent::KeyPress(_) => EventKind::KeyPress,
+            WebEvent::Paste(_) => EventKind::Paste,
+            WebEvent::Click { .. } => EventKind::Click,
+        }
+    }
+}
+
+// Each kind of variant maps to JSON differently: a unit variant is just its
+// name, the others are an object with the name as the only key.
+//
+//     "PageLoad"   {"KeyPress": "x"}   {"Click": {"x": 20, "y": 80}}
+impl ToJson for WebEvent {
+    fn to_json(&self) -> Json {
+        match self {
+            WebEvent::PageLoad => "PageLoad".to_json(),
+            WebEvent::PageUnload => "PageUnload".to_json(),
+            WebEvent::KeyPress(c) => Json::variant_of("KeyPress", c.to_json()),
+            WebEvent::Paste(s) => Json::variant_of("Paste", s.to_json()),
+            WebEvent::Click { x, y } => {
+                Json::variant_of("Click", Json::object([("x", x.to_json()), ("y", y.to_json())]))
+            }
+        }
+    }
+}
+
+impl FromJson<'_> for WebEvent {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        match json.variant()? {
+            ("PageLoad", None) => Ok(WebEvent::PageLoad),
+            ("PageUnload", None) => Ok(WebEvent::PageUnload),
+            ("KeyPress", Some(c)) => Ok(WebEvent::KeyPress(FromJson::from_json(c)?)),
+            ("Paste", Some(s)) => Ok(WebEvent::Paste(FromJson::from_json(s)?)),
+            ("Click", Some(click)) => Ok(WebEvent::Click { x: click.field("x")?, y: click.field("y")? }),
+            (name, _) => Err(FromJsonError::new(format!("unknown WebEvent variant `{}`", name))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let events = vec![
+            WebEvent::PageLoad,
+            WebEvent::PageUnload,
+            WebEvent::KeyPress('x'),
+            WebEvent::Paste("my \"text\"".to_owned()),
+            WebEvent::Click { x: 20, y: -80 },
+        ];
+        let text = json::to_string(&events);
+        assert_eq!(
+            text,
+            r#"["PageLoad","PageUnload",{"KeyPress":"x"},{"Paste":"my \"text\""},{"Click":{"x":20,"y":-80}}]"#
+        );
+        assert_eq!(json::from_str::<Vec<WebEvent>>(&text), Ok(events));
+
+        let err = json::from_str::<WebEvent>(r#"{"Scroll": 1}"#).unwrap_err();
+        assert_eq!(err.to_string(), "unknown WebEvent variant `Scroll`");
+    }
+}
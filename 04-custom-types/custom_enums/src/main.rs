@@ -1,17 +1,6 @@
-// Create an `enum` to classify a web event. Note how both
-// names and type information together specify the variant:
-// `PageLoad != PageUnload` and `KeyPress(char) != Paste(String)`.
-// Each is different and independent.
-enum WebEvent {
-    // An `enum` variant may either be `unit-like`,
-    PageLoad,
-    PageUnload,
-    // like tuple structs,
-    KeyPress(char),
-    Paste(String),
-    // or c-like structures.
-    Click { x: i64, y: i64 },
-}
+use custom_enums::bus::{Bus, Filter};
+use custom_enums::{EventKind, WebEvent};
+use mem_layout::enum_layout;
 
 // A function which takes a `WebEvent` enum as an argument and
 // returns nothing.
@@ -42,4 +31,55 @@ fn main() {
     inspect(click);
     inspect(load);
     inspect(unload);
+
+    // Events can be stored as JSON and inspected later.
+    let saved = r#"["PageLoad", {"KeyPress": "y"}, {"Click": {"x": 1, "y": 2}}]"#;
+    for event in json::from_str::<Vec<WebEvent>>(saved).unwrap() {
+        print!("{} -> ", json::to_string(&event));
+        inspect(event);
+    }
+
+    // Instead of one `match`, an event bus lets each handler subscribe to
+    // the variants it cares about. Higher priorities run first, and every
+    // event is appended to a log file before any handler sees it.
+    let log = std::env::temp_dir().join("custom_enums_events.log");
+    let _ = std::fs::remove_file(&log);
+    let mut bus = Bus::with_log(&log).unwrap();
+    bus.on_key_press(|c| println!("bus: key '{}'", c));
+    bus.on_click(|x, y| println!("bus: click at ({}, {})", x, y));
+    let audit = bus.subscribe(Filter::All, 10, |event| println!("bus: {:?} arrives", event.kind()));
+    bus.subscribe(Filter::Only(EventKind::Paste), 0, |event| println!("bus: {:?}", event));
+
+    let events = [
+        WebEvent::PageLoad,
+        WebEvent::KeyPress('z'),
+        WebEvent::Paste("hello".to_owned()),
+        WebEvent::Click { x: 3, y: 4 },
+    ];
+    for event in &events {
+        bus.dispatch(event).unwrap();
+    }
+    // Nothing but the audit handler was listening for this one, and now
+    // not even that.
+    bus.unsubscribe(audit);
+    bus.dispatch(&WebEvent::PageUnload).unwrap();
+
+    // The log can be replayed into a fresh bus to reproduce the session.
+    println!("{}", std::fs::read_to_string(&log).unwrap().trim_end());
+    let mut fresh = Bus::new();
+    fresh.subscribe(Filter::All, 0, |event| inspect(event.clone()));
+    let replayed = fresh.replay_file(&log).unwrap();
+    println!("replayed {} events", replayed);
+
+    // Every variant takes the space of the largest one, `Paste(String)`.
+    // A `String`'s capacity never uses its top bit, so the compiler keeps
+    // the discriminant there instead of adding a tag.
+    let layout = enum_layout!(WebEvent {
+        PageLoad,
+        PageUnload,
+        KeyPress(char),
+        Paste(String),
+        Click { x: i64, y: i64 },
+    });
+    println!("{}", layout);
 }
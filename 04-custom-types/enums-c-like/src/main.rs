@@ -1,7 +1,10 @@
 // An attribute to hide warnings for unused code.
 #![allow(dead_code)]
 
+use json::{FromJson, FromJsonError, Json, ToJson};
+
 // enum with implicit discriminator (starts at 0)
+#[derive(Debug, PartialEq)]
 enum Number {
     Zero,
     One,
@@ -9,12 +12,59 @@ enum Number {
 }
 
 // enum with explicit discriminator
+#[derive(Debug, PartialEq)]
 enum Color {
     Red = 0xff0000,
     Green = 0x00ff00,
     Blue = 0x0000ff,
 }
 
+// C-like enums are stored by variant name, which stays readable and does not
+// depend on the discriminant values.
+impl ToJson for Number {
+    fn to_json(&self) -> Json {
+        match self {
+            Number::Zero => "Zero",
+            Number::One => "One",
+            Number::Two => "Two",
+        }
+        .to_json()
+    }
+}
+
+impl FromJson<'_> for Number {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        match json.variant()? {
+            ("Zero", None) => Ok(Number::Zero),
+            ("One", None) => Ok(Number::One),
+            ("Two", None) => Ok(Number::Two),
+            (name, _) => Err(FromJsonError::new(format!("unknown Number variant `{}`", name))),
+        }
+    }
+}
+
+impl ToJson for Color {
+    fn to_json(&self) -> Json {
+        match self {
+            Color::Red => "Red",
+            Color::Green => "Green",
+            Color::Blue => "Blue",
+        }
+        .to_json()
+    }
+}
+
+impl FromJson<'_> for Color {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        match json.variant()? {
+            ("Red", None) => Ok(Color::Red),
+            ("Green", None) => Ok(Color::Green),
+            ("Blue", None) => Ok(Color::Blue),
+            (name, _) => Err(FromJsonError::new(format!("unknown Color variant `{}`", name))),
+        }
+    }
+}
+
 fn main() {
     // `enums` can be cast as integers.
     println!("zero is {}", Number::Zero as i32);
@@ -22,4 +72,23 @@ fn main() {
 
     println!("roses are #{:06x}", Color::Red as i32);
     println!("violets are #{:06x}", Color::Blue as i32);
-}
\ No newline at end of file
+
+    let violets: Color = json::from_str(r#""Blue""#).unwrap();
+    println!("violets from JSON are #{:06x}", violets as i32);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        for number in [Number::Zero, Number::One, Number::Two] {
+            assert_eq!(json::from_str::<Number>(&json::to_string(&number)), Ok(number));
+        }
+        for color in [Color::Red, Color::Green, Color::Blue] {
+            assert_eq!(json::from_str::<Color>(&json::to_string(&color)), Ok(color));
+        }
+        assert!(json::from_str::<Color>(r#""Purple""#).is_err());
+    }
+}
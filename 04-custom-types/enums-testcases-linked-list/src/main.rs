@@ -1,5 +1,8 @@
 use crate::List::*;
 
+use json::{FromJson, FromJsonError, Json, ToJson};
+
+#[derive(Debug, PartialEq)]
 enum List {
     // Cons: Tuple struct that wraps an element and a pointer to the next node
     Cons(u32, Box<List>),
@@ -55,6 +58,29 @@ impl List {
     }
 }
 
+// In JSON the list is a flat array, front first: `[3, 2, 1]`. Nesting a
+// `{"Cons": ...}` object per node would be faithful to the type but painful
+// to read.
+impl ToJson for List {
+    fn to_json(&self) -> Json {
+        let mut items = Vec::new();
+        let mut node = self;
+        while let Cons(head, tail) = node {
+            items.push(head.to_json());
+            node = tail;
+        }
+        Json::Array(items)
+    }
+}
+
+impl FromJson<'_> for List {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        let items = Vec::<u32>::from_json(json)?;
+        // Prepending builds the list back to front.
+        Ok(items.into_iter().rev().fold(List::new(), List::prepend))
+    }
+}
+
 fn main() {
     // Create an empty linked list
     let mut list = List::new();
@@ -67,4 +93,22 @@ fn main() {
     // Show the final state of the list
     println!("linked list has length: {}", list.len());
     println!("{}", list.stringify());
-}
\ No newline at end of file
+
+    // The same list as JSON, and read back.
+    let text = json::to_string(&list);
+    println!("as JSON: {}", text);
+    println!("from JSON: {}", json::from_str::<List>(&text).unwrap().stringify());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+        assert_eq!(json::to_string(&list), "[3,2,1]");
+        assert_eq!(json::from_str::<List>("[3, 2, 1]"), Ok(list));
+        assert_eq!(json::from_str::<List>("[]"), Ok(Nil));
+    }
+}
@@ -1,16 +1,65 @@
 // An attribute to hide warnings for unused code.
 #![allow(dead_code)]
 
+use json::{FromJson, FromJsonError, Json, ToJson};
+
+#[derive(Debug, PartialEq)]
 enum Stage {
     Beginner,
     Advanced,
 }
 
+#[derive(Debug, PartialEq)]
 enum Role {
     Student,
     Teacher,
 }
 
+// The `use` trick works inside `impl` blocks too.
+impl ToJson for Stage {
+    fn to_json(&self) -> Json {
+        use crate::Stage::*;
+        match self {
+            Beginner => "Beginner",
+            Advanced => "Advanced",
+        }
+        .to_json()
+    }
+}
+
+impl FromJson<'_> for Stage {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        use crate::Stage::*;
+        match json.variant()? {
+            ("Beginner", None) => Ok(Beginner),
+            ("Advanced", None) => Ok(Advanced),
+            (name, _) => Err(FromJsonError::new(format!("unknown Stage variant `{}`", name))),
+        }
+    }
+}
+
+impl ToJson for Role {
+    fn to_json(&self) -> Json {
+        use crate::Role::*;
+        match self {
+            Student => "Student",
+            Teacher => "Teacher",
+        }
+        .to_json()
+    }
+}
+
+impl FromJson<'_> for Role {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        use crate::Role::*;
+        match json.variant()? {
+            ("Student", None) => Ok(Student),
+            ("Teacher", None) => Ok(Teacher),
+            (name, _) => Err(FromJsonError::new(format!("unknown Role variant `{}`", name))),
+        }
+    }
+}
+
 fn main() {
     // Explicitly `use` each name so they are available without
     // manual scoping.
@@ -34,4 +83,22 @@ fn main() {
         Student => println!("Students are acquiring knowledge!"),
         Teacher => println!("Teachers are spreading knowledge!"),
     }
-}
\ No newline at end of file
+
+    // Both enums are written to JSON by variant name.
+    println!("{} {}", json::to_string(&stage), json::to_string(&role));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        for stage in [Stage::Beginner, Stage::Advanced] {
+            assert_eq!(json::from_str::<Stage>(&json::to_string(&stage)), Ok(stage));
+        }
+        for role in [Role::Student, Role::Teacher] {
+            assert_eq!(json::from_str::<Role>(&json::to_string(&role)), Ok(role));
+        }
+    }
+}
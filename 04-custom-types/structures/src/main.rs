@@ -1,25 +1,32 @@
 // An attribute to hide warnings for unused code.
 #![allow(dead_code)]
 
-#[derive(Debug)]
+use json::{FromJson, FromJsonError, Json, ToJson};
+use mem_layout::{assert_layout, layout};
+
+#[derive(Debug, PartialEq)]
 struct Person {
     name: String,
     age: u8,
 }
 
 // A unit struct
+#[derive(Debug, PartialEq)]
 struct Unit;
 
 // A tuple struct
+#[derive(Debug, PartialEq)]
 struct Pair(i32, f32);
 
 // A struct with two fields
+#[derive(Debug, PartialEq)]
 struct Point {
     x: f32,
     y: f32,
 }
 
 // Structs can be reused as fields of another struct
+#[derive(Debug, PartialEq)]
 struct Rectangle {
     // A rectangle can be specified by where the top left and bottom right
     // corners are in space.
@@ -27,6 +34,81 @@ struct Rectangle {
     bottom_right: Point,
 }
 
+// Two `f32`s with nothing in between, and two of those for a rectangle.
+// The build fails if that ever changes.
+assert_layout!(Point, size = 8, align = 4);
+assert_layout!(Rectangle, size = 16, align = 4);
+
+// Each kind of struct has a natural JSON shape: named fields become an
+// object, a tuple struct an array, and a unit struct `null`.
+impl ToJson for Person {
+    fn to_json(&self) -> Json {
+        Json::object([("name", self.name.to_json()), ("age", self.age.to_json())])
+    }
+}
+
+impl FromJson<'_> for Person {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        Ok(Person { name: json.field("name")?, age: json.field("age")? })
+    }
+}
+
+impl ToJson for Unit {
+    fn to_json(&self) -> Json {
+        Json::Null
+    }
+}
+
+impl FromJson<'_> for Unit {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        match json {
+            Json::Null => Ok(Unit),
+            other => Err(FromJsonError::expected("null", other)),
+        }
+    }
+}
+
+impl ToJson for Pair {
+    fn to_json(&self) -> Json {
+        (self.0, self.1).to_json()
+    }
+}
+
+impl FromJson<'_> for Pair {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        let (integer, decimal) = FromJson::from_json(json)?;
+        Ok(Pair(integer, decimal))
+    }
+}
+
+impl ToJson for Point {
+    fn to_json(&self) -> Json {
+        Json::object([("x", self.x.to_json()), ("y", self.y.to_json())])
+    }
+}
+
+impl FromJson<'_> for Point {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        Ok(Point { x: json.field("x")?, y: json.field("y")? })
+    }
+}
+
+// Nested structs nest in JSON as well.
+impl ToJson for Rectangle {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("top_left", self.top_left.to_json()),
+            ("bottom_right", self.bottom_right.to_json()),
+        ])
+    }
+}
+
+impl FromJson<'_> for Rectangle {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        Ok(Rectangle { top_left: json.field("top_left")?, bottom_right: json.field("bottom_right")? })
+    }
+}
+
 // Add this function above main
 
 fn rect_area(rect: &Rectangle) -> f32 {
@@ -109,4 +191,38 @@ fn main() {
         sq.top_left.x, sq.top_left.y, sq.bottom_right.x, sq.bottom_right.y
     );
     println!("Square area: {}", square_area(&sq));
-}
\ No newline at end of file
+
+    // Write the square as JSON and read it back.
+    let text = json::to_string(&sq);
+    println!("Square as JSON: {}", text);
+    let parsed: Rectangle = json::from_str(&text).unwrap();
+    println!("Parsed square area: {}", square_area(&parsed));
+
+    // How each struct is laid out in memory.
+    println!("{}", layout!(Person { name, age }));
+    println!("{}", layout!(Pair { 0, 1 }));
+    println!("{}", layout!(Rectangle { top_left, bottom_right }));
+    println!("{}", layout!(Unit {}));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let peter = Person { name: String::from("Peter"), age: 27 };
+        assert_eq!(json::from_str::<Person>(&json::to_string(&peter)), Ok(peter));
+        assert_eq!(json::from_str::<Unit>(&json::to_string(&Unit)), Ok(Unit));
+        assert_eq!(json::to_string(&Pair(1, 0.1)), "[1,0.1]");
+        assert_eq!(json::from_str::<Pair>("[1, 0.1]"), Ok(Pair(1, 0.1)));
+
+        let rectangle = square(Point { x: 1.0, y: 2.0 }, 3.0);
+        let text = json::to_string_pretty(&rectangle);
+        assert_eq!(json::from_str::<Rectangle>(&text), Ok(rectangle));
+
+        let err = json::from_str::<Rectangle>(r#"{"top_left": {"x": 1}, "bottom_right": {"x": 2, "y": 3}}"#)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "missing field `y` at `.top_left`");
+    }
+}
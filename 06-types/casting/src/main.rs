@@ -1,6 +1,8 @@
 // Suppress all warnings from casts which overflow.
 #![allow(overflowing_literals)]
 
+use table::{Align, Table};
+
 fn main() {
     let decimal = 65.4321_f32;
 
@@ -75,4 +77,37 @@ fn main() {
         // nan as u8 is 0
         println!("   nan as u8 is : {}", f32::NAN.to_int_unchecked::<u8>());
     }
-}
\ No newline at end of file
+
+    // All of the above side by side. Each row is one value, each column
+    // one target type.
+    let mut integers = Table::new(["i32", "as u8", "as i8", "as u16", "as i16"]);
+    for value in [128_i32, 232, 1000, -1, -129] {
+        integers.row([
+            value.to_string(),
+            (value as u8).to_string(),
+            (value as i8).to_string(),
+            (value as u16).to_string(),
+            (value as i16).to_string(),
+        ]);
+    }
+
+    let mut floats = Table::new(["f32", "as u8", "as i8", "as char"]);
+    for value in [65.4321_f32, 300.0, -100.0, f32::NAN] {
+        floats.row([
+            value.to_string(),
+            (value as u8).to_string(),
+            (value as i8).to_string(),
+            // `{:?}` shows the quotes, and escapes the control characters
+            // that would otherwise mess up the table.
+            format!("{:?}", value as u8 as char),
+        ]);
+    }
+
+    for table in [&mut integers, &mut floats] {
+        for column in 0..5 {
+            table.align(column, Align::Right);
+        }
+    }
+    println!("{}", integers);
+    println!("{}", floats);
+}
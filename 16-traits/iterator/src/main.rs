@@ -1,3 +1,5 @@
+use bigint::BigUint;
+
 struct Fibonacci {
     curr: u32,
     next: u32,
@@ -33,6 +35,28 @@ fn fibonacci() -> Fibonacci {
     Fibonacci { curr: 0, next: 1 }
 }
 
+// `u32` overflows after 47 terms. The same iterator over `BigUint` never
+// runs out of bits, so it really has no end.
+struct BigFibonacci {
+    curr: BigUint,
+    next: BigUint,
+}
+
+impl Iterator for BigFibonacci {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = &self.curr + &self.next;
+        let current = std::mem::replace(&mut self.curr, std::mem::replace(&mut self.next, next));
+
+        Some(current)
+    }
+}
+
+fn big_fibonacci() -> BigFibonacci {
+    BigFibonacci { curr: BigUint::zero(), next: BigUint::one() }
+}
+
 fn main() {
     // `0..3` is an `Iterator` that generates: 0, 1, and 2.
     let mut sequence = 0..3;
@@ -62,6 +86,11 @@ fn main() {
         println!("> {}", i);
     }
 
+    // `nth(n)` skips `n` terms and returns the one after them. The sequence
+    // starts at F(0) = 0, so skipping 100 terms lands on F(100).
+    println!("F(100) of the Fibonacci sequence is: ");
+    println!("> {}", big_fibonacci().nth(100).unwrap());
+
     let array = [1u32, 3, 3, 7];
 
     // The `iter` method produces an `Iterator` over an array/slice.
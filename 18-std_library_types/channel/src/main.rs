@@ -1,10 +1,30 @@
 use std::sync::mpsc::{Sender, Receiver};
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc};
 use std::thread;
 
+use message_catalog::{system_locale, Catalog};
+
 static NTHREADS: i32 = 3;
 
+// The messages below come from `locales/`, in the language of `LANG`
+// (try `LANG=fr cargo run` or `LANG=ru cargo run`).
+fn catalog() -> Catalog {
+    let mut catalog = Catalog::new("en");
+    let files = [
+        ("en", include_str!("../locales/en.txt")),
+        ("fr", include_str!("../locales/fr.txt")),
+        ("ru", include_str!("../locales/ru.txt")),
+    ];
+    for (locale, text) in files {
+        catalog.add(locale, text).unwrap();
+    }
+    catalog
+}
+
 fn main() {
+    let messages = Arc::new(catalog());
+    let locale = Arc::new(system_locale().unwrap_or_else(|| String::from("en")));
+
     // Channels have two endpoints: the `Sender<T>` and the `Receiver<T>`,
     // where `T` is the type of the message to be transferred
     // (type annotation is superfluous)
@@ -14,6 +34,7 @@ fn main() {
     for id in 0..NTHREADS {
         // The sender endpoint can be copied
         let thread_tx = tx.clone();
+        let (messages, locale) = (Arc::clone(&messages), Arc::clone(&locale));
 
         // Each thread will send its id via the channel
         let child = thread::spawn(move || {
@@ -23,7 +44,7 @@ fn main() {
 
             // Sending is a non-blocking operation, the thread will continue
             // immediately after sending its message
-            println!("thread {} finished", id);
+            println!("{}", messages.text(&locale, "thread.finished", &[("id", id.into())]).unwrap());
         });
 
         children.push(child);
@@ -43,5 +64,6 @@ fn main() {
     }
 
     // Show the order in which the messages were sent
-    println!("{:?}", ids);
+    let order = format!("{:?}", ids);
+    println!("{}", messages.plural(&locale, "received", ids.len() as i64, &[("order", order.into())]).unwrap());
 }
\ No newline at end of file
@@ -1,58 +1,54 @@
-use std::fmt;
+// `Complex` is the single precision instance of the complex type from
+// `01-fmt/fmt_display`. It is `#[repr(C)]` with the real part first, which
+// is the layout C gives `float complex`, so it can cross the FFI boundary.
+use fmt_display::Complex;
+use mem_layout::{assert_layout, layout};
+
+// The C side expects two floats and nothing else; if `Complex` ever
+// changes shape, this stops the build rather than corrupting values.
+assert_layout!(Complex<f32>, size = 8, align = 4);
 
 // this extern block links to the libm library
 #[cfg(target_family = "windows")]
 #[link(name = "msvcrt")]
-extern {
+unsafe extern "C" {
     // this is a foreign function
     // that computes the square root of a single precision complex number
-    fn csqrtf(z: Complex) -> Complex;
+    fn csqrtf(z: Complex<f32>) -> Complex<f32>;
 
-    fn ccosf(z: Complex) -> Complex;
+    fn ccosf(z: Complex<f32>) -> Complex<f32>;
 }
 #[cfg(target_family = "unix")]
 #[link(name = "m")]
-extern {
+unsafe extern "C" {
     // this is a foreign function
     // that computes the square root of a single precision complex number
-    fn csqrtf(z: Complex) -> Complex;
+    fn csqrtf(z: Complex<f32>) -> Complex<f32>;
 
-    fn ccosf(z: Complex) -> Complex;
+    fn ccosf(z: Complex<f32>) -> Complex<f32>;
 }
 
 // Since calling foreign functions is considered unsafe,
 // it's common to write safe wrappers around them.
-fn cos(z: Complex) -> Complex {
+fn cos(z: Complex<f32>) -> Complex<f32> {
     unsafe { ccosf(z) }
 }
 
 fn main() {
     // z = -1 + 0i
-    let z = Complex { re: -1., im: 0. };
+    let z = Complex::new(-1_f32, 0.);
 
     // calling a foreign function is an unsafe operation
     let z_sqrt = unsafe { csqrtf(z) };
 
-    println!("the square root of {:?} is {:?}", z, z_sqrt);
+    println!("the square root of {} is {}", z, z_sqrt);
 
     // calling safe API wrapped around unsafe operation
-    println!("cos({:?}) = {:?}", z, cos(z));
-}
+    println!("cos({}) = {}", z, cos(z));
 
-// Minimal implementation of single precision complex numbers
-#[repr(C)]
-#[derive(Clone, Copy)]
-struct Complex {
-    re: f32,
-    im: f32,
-}
+    // The Rust version agrees with libm.
+    println!("the square root computed in Rust is {}", z.sqrt());
 
-impl fmt::Debug for Complex {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.im < 0. {
-            write!(f, "{}-{}i", self.re, -self.im)
-        } else {
-            write!(f, "{}+{}i", self.re, self.im)
-        }
-    }
-}
\ No newline at end of file
+    // `#[repr(C)]` keeps the fields in declaration order, real part first.
+    print!("{}", layout!(Complex<f32> { real, imag }));
+}